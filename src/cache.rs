@@ -57,7 +57,8 @@ fn load_from_path(directory: PathBuf) -> Cache {
     cache_data
 }
 
-fn get_cache_directory(migrate: bool) -> Option<PathBuf> {
+/// Returns the cargo-make cache directory.
+pub(crate) fn get_cache_directory(migrate: bool) -> Option<PathBuf> {
     let os_directory = dirs::cache_dir();
     storage::get_storage_directory(os_directory, CACHE_FILE, migrate)
 }
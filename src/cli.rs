@@ -66,9 +66,11 @@ fn run(cli_args: CliArgs, global_config: &GlobalConfig) {
 
     let build_file = &cli_args.build_file;
     let task = &cli_args.task;
+    let profile_name = environment::setup_profile(cli_args.profile.clone());
 
     info!("Using Build File: {}", &build_file);
     info!("Task: {}", &task);
+    info!("Profile: {}", &profile_name);
 
     let env_file_entries = environment::parse_env_file(cli_args.env_file.clone());
     let env_cli_entries = cli_args.env.clone();
@@ -85,14 +87,17 @@ fn run(cli_args: CliArgs, global_config: &GlobalConfig) {
 
     let config = descriptor::load(&build_file, env, cli_args.experimental);
 
-    let env_info = environment::setup_env(&cli_args, &config, &task);
-
     if cli_args.list_all_steps {
+        // listing the tasks does not require evaluating the env
         descriptor::list_steps(&config);
-    } else if cli_args.print_only {
-        runner::print(&config, &task, cli_args.disable_workspace);
     } else {
-        runner::run(config, &task, env_info, &cli_args);
+        let env_info = environment::setup_env(&cli_args, &config, &task);
+
+        if cli_args.print_only {
+            runner::print(config, &task, env_info, &cli_args);
+        } else {
+            runner::run(config, &task, env_info, &cli_args);
+        }
     }
 }
 
@@ -132,10 +137,18 @@ fn run_for_args(matches: ArgMatches, global_config: &GlobalConfig) {
                 None => None,
             };
 
+            cli_args.profile = match cmd_matches.value_of("profile") {
+                Some(value) => Some(value.to_string()),
+                None => None,
+            };
+
             cli_args.disable_check_for_updates =
                 cmd_matches.is_present("disable-check-for-updates");
             cli_args.experimental = cmd_matches.is_present("experimental");
             cli_args.print_only = cmd_matches.is_present("print-steps");
+            cli_args.install_first = cmd_matches.is_present("install-first");
+            cli_args.install_only = cmd_matches.is_present("install-only");
+            cli_args.offline = cmd_matches.is_present("offline");
             cli_args.disable_workspace = cmd_matches.is_present("no-workspace");
             cli_args.disable_on_error = cmd_matches.is_present("no-on-error");
             cli_args.list_all_steps = cmd_matches.is_present("list-steps");
@@ -220,6 +233,15 @@ fn create_cli<'a, 'b>(global_config: &'a GlobalConfig) -> App<'a, 'b> {
                     .takes_value(true)
                     .number_of_values(1)
                     .help("Set environment variables"),
+            ).arg(
+                Arg::with_name("profile")
+                    .long("--profile")
+                    .short("-p")
+                    .value_name("PROFILE")
+                    .help(
+                        "The profile name used to select the env \
+                         (defaults to CARGO_MAKE_PROFILE env var or development)",
+                    ),
             ).arg(
                 Arg::from_usage("-l, --loglevel=[LOG LEVEL] 'The log level'")
                     .possible_values(&["verbose", "info", "error"])
@@ -241,6 +263,18 @@ fn create_cli<'a, 'b>(global_config: &'a GlobalConfig) -> App<'a, 'b> {
                 "Only prints the steps of the build in the order they will \
                  be invoked but without invoking them",
             )).arg(
+                Arg::with_name("install-first")
+                    .long("--install-first")
+                    .help("Installs the dependencies of all tasks before invoking them"),
+            ).arg(
+                Arg::with_name("install-only")
+                    .long("--install-only")
+                    .help("Only installs the dependencies of all tasks without invoking them"),
+            ).arg(
+                Arg::with_name("offline")
+                    .long("--offline")
+                    .help("Installs crates without accessing the network"),
+            ).arg(
                 Arg::with_name("list-steps")
                     .long("--list-all-steps")
                     .help("Lists all known steps"),
@@ -15,10 +15,14 @@ fn run_empty_task() {
             cwd: None,
             env: None,
             env_file: None,
+            profile: None,
             disable_workspace: false,
             disable_on_error: false,
             disable_check_for_updates: true,
             print_only: false,
+            install_first: false,
+            install_only: false,
+            offline: false,
             list_all_steps: false,
             experimental: false,
             arguments: None,
@@ -39,10 +43,14 @@ fn print_empty_task() {
             cwd: None,
             env: None,
             env_file: None,
+            profile: None,
             disable_workspace: false,
             disable_on_error: false,
             disable_check_for_updates: true,
             print_only: true,
+            install_first: false,
+            install_only: false,
+            offline: false,
             list_all_steps: false,
             experimental: false,
             arguments: None,
@@ -63,10 +71,14 @@ fn list_empty_task() {
             cwd: None,
             env: None,
             env_file: None,
+            profile: None,
             disable_workspace: false,
             disable_on_error: false,
             disable_check_for_updates: true,
             print_only: false,
+            install_first: false,
+            install_only: false,
+            offline: false,
             list_all_steps: true,
             experimental: false,
             arguments: None,
@@ -87,10 +99,14 @@ fn run_file_and_task() {
             cwd: None,
             env: None,
             env_file: None,
+            profile: None,
             disable_workspace: false,
             disable_on_error: false,
             disable_check_for_updates: true,
             print_only: false,
+            install_first: false,
+            install_only: false,
+            offline: false,
             list_all_steps: false,
             experimental: false,
             arguments: None,
@@ -114,10 +130,14 @@ fn run_cwd_with_file() {
             cwd: Some("..".to_string()),
             env: None,
             env_file: None,
+            profile: None,
             disable_workspace: false,
             disable_on_error: false,
             disable_check_for_updates: true,
             print_only: false,
+            install_first: false,
+            install_only: false,
+            offline: false,
             list_all_steps: false,
             experimental: false,
             arguments: None,
@@ -139,10 +159,14 @@ fn run_file_not_go_to_project_root() {
             cwd: None,
             env: None,
             env_file: None,
+            profile: None,
             disable_workspace: false,
             disable_on_error: false,
             disable_check_for_updates: true,
             print_only: false,
+            install_first: false,
+            install_only: false,
+            offline: false,
             list_all_steps: false,
             experimental: false,
             arguments: None,
@@ -164,10 +188,14 @@ fn run_cwd_go_to_project_root_current_dir() {
             cwd: None,
             env: None,
             env_file: None,
+            profile: None,
             disable_workspace: false,
             disable_on_error: false,
             disable_check_for_updates: true,
             print_only: false,
+            install_first: false,
+            install_only: false,
+            offline: false,
             list_all_steps: false,
             experimental: false,
             arguments: None,
@@ -192,10 +220,14 @@ fn run_cwd_go_to_project_root_child_dir() {
             cwd: None,
             env: None,
             env_file: None,
+            profile: None,
             disable_workspace: false,
             disable_on_error: false,
             disable_check_for_updates: true,
             print_only: false,
+            install_first: false,
+            install_only: false,
+            offline: false,
             list_all_steps: false,
             experimental: false,
             arguments: None,
@@ -220,10 +252,14 @@ fn run_cwd_task_not_found() {
             cwd: Some("..".to_string()),
             env: None,
             env_file: None,
+            profile: None,
             disable_workspace: false,
             disable_on_error: false,
             disable_check_for_updates: true,
             print_only: false,
+            install_first: false,
+            install_only: false,
+            offline: false,
             list_all_steps: false,
             experimental: false,
             arguments: None,
@@ -333,6 +369,29 @@ fn run_for_args_set_env_values() {
     assert_eq!(env::var("ENV3_TEST").unwrap(), "TEST3");
 }
 
+#[test]
+fn run_for_args_set_profile() {
+    let global_config = GlobalConfig::new();
+    let app = create_cli(&global_config);
+
+    env::set_var("CARGO_MAKE_PROFILE", "EMPTY");
+
+    let matches = app.get_matches_from(vec![
+        "cargo",
+        "make",
+        "--profile",
+        "profile_test",
+        "--disable-check-for-updates",
+        "-t",
+        "empty",
+    ]);
+
+    run_for_args(matches, &global_config);
+
+    assert_eq!(env::var("CARGO_MAKE_PROFILE").unwrap(), "profile_test");
+    env::remove_var("CARGO_MAKE_PROFILE");
+}
+
 #[test]
 fn run_for_args_set_env_via_file() {
     let global_config = GlobalConfig::new();
@@ -420,6 +479,50 @@ fn run_for_args_print_only() {
     run_for_args(matches, &global_config);
 }
 
+#[test]
+fn run_for_args_install_only() {
+    let global_config = GlobalConfig::new();
+    let app = create_cli(&global_config);
+
+    let matches = app.get_matches_from(vec![
+        "cargo",
+        "make",
+        "--makefile",
+        "./examples/dependencies.toml",
+        "-t",
+        "A",
+        "-l",
+        "error",
+        "--no-workspace",
+        "--no-on-error",
+        "--install-only",
+    ]);
+
+    run_for_args(matches, &global_config);
+}
+
+#[test]
+fn run_for_args_install_first() {
+    let global_config = GlobalConfig::new();
+    let app = create_cli(&global_config);
+
+    let matches = app.get_matches_from(vec![
+        "cargo",
+        "make",
+        "--makefile",
+        "./examples/dependencies.toml",
+        "-t",
+        "A",
+        "-l",
+        "error",
+        "--no-workspace",
+        "--no-on-error",
+        "--install-first",
+    ]);
+
+    run_for_args(matches, &global_config);
+}
+
 #[test]
 #[should_panic]
 fn run_protected_flow_example() {
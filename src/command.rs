@@ -12,6 +12,7 @@ use run_script::{ScriptError, ScriptOptions};
 use std::io;
 use std::io::Error;
 use std::process::{Command, ExitStatus, Output, Stdio};
+use toolchain;
 use types::Step;
 
 /// Returns the exit code (-1 if no exit code found)
@@ -142,9 +143,17 @@ pub(crate) fn run(step: &Step, cli_arguments: &Vec<String>) {
     let validate = !step.config.is_force();
 
     match step.config.command {
-        Some(ref command_string) => {
-            run_command(&command_string, &step.config.args, validate);
-        }
+        Some(ref command_string) => match step.config.toolchain {
+            Some(ref toolchain_name) => {
+                let (rustup_command, rustup_args) =
+                    toolchain::wrap_command(&toolchain_name, &command_string, &step.config.args);
+
+                run_command(&rustup_command, &rustup_args, validate);
+            }
+            None => {
+                run_command(&command_string, &step.config.args, validate);
+            }
+        },
         None => {
             match step.config.script {
                 Some(ref script) => {
@@ -78,6 +78,36 @@ fn run_command_error_force() {
     run(&step, &vec![]);
 }
 
+#[test]
+#[should_panic]
+fn run_command_toolchain_error() {
+    let mut task = Task::new();
+    task.command = Some("echo".to_string());
+    task.toolchain = Some("badbadbad".to_string());
+
+    let step = Step {
+        name: "test".to_string(),
+        config: task,
+    };
+
+    run(&step, &vec![]);
+}
+
+#[test]
+fn run_command_toolchain_error_force() {
+    let mut task = Task::new();
+    task.force = Some(true);
+    task.command = Some("echo".to_string());
+    task.toolchain = Some("badbadbad".to_string());
+
+    let step = Step {
+        name: "test".to_string(),
+        config: task,
+    };
+
+    run(&step, &vec![]);
+}
+
 #[test]
 fn run_script() {
     let mut task = Task::new();
@@ -8,172 +8,275 @@
 mod condition_test;
 
 use command;
-use rust_info;
+use environment;
+use environment::crateinfo;
+use environment::gitinfo;
+use glob::{glob, Pattern};
+use indexmap::IndexMap;
+use regex::Regex;
 use rust_info::types::{RustChannel, RustInfo};
+use semver::{Version, VersionReq};
 use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
 use types;
-use types::{FlowInfo, RustVersionCondition, Step, TaskCondition};
-use version::is_newer;
+use types::{
+    CrateTarget, FilesModifiedCondition, FlowInfo, NightlyDateCondition, RustVersion,
+    RustVersionCondition, SkipReason, Step, TaskCondition,
+};
 
-fn validate_env(condition: &TaskCondition) -> bool {
-    let env = condition.env.clone();
+fn criterion_not_met(criterion: &str, details: String) -> Result<(), SkipReason> {
+    Err(SkipReason::Criterion(criterion.to_string(), details))
+}
 
-    match env {
-        Some(env_vars) => {
-            let mut all_valid = true;
+fn get_env_value_details(key: &str) -> String {
+    match env::var(key) {
+        Ok(value) => format!("found: {}", value),
+        _ => "not set".to_string(),
+    }
+}
 
-            for (key, current_value) in env_vars.iter() {
-                match env::var(key) {
-                    Ok(value) => {
-                        all_valid = value == current_value.to_string();
-                    }
-                    _ => {
-                        all_valid = false;
-                    }
+fn get_env_mismatch_details(key: &str, description: &str, expected_value: &str) -> String {
+    format!(
+        "env {} expected {}{}, {}",
+        &key,
+        description,
+        &expected_value,
+        get_env_value_details(&key)
+    )
+}
+
+/// Validates the env vars against their expected values and returns the first env which failed the check.
+fn validate_env_map<F>(
+    env_vars: &Option<IndexMap<String, String>>,
+    criterion: &str,
+    description: &str,
+    is_valid: F,
+) -> Result<(), SkipReason>
+where
+    F: Fn(&str, &str) -> bool,
+{
+    match *env_vars {
+        Some(ref env_vars) => {
+            for (key, expected_value) in env_vars.iter() {
+                let valid = match env::var(key) {
+                    Ok(value) => is_valid(&value, &expected_value),
+                    _ => false,
                 };
 
-                if !all_valid {
-                    break;
+                if !valid {
+                    return criterion_not_met(
+                        criterion,
+                        get_env_mismatch_details(&key, description, &expected_value),
+                    );
                 }
             }
 
-            all_valid
+            Ok(())
         }
-        None => true,
+        None => Ok(()),
     }
 }
 
-fn validate_env_set(condition: &TaskCondition) -> bool {
-    let env = condition.env_set.clone();
-
-    match env {
-        Some(env_vars) => {
-            let mut all_valid = true;
-
+/// Validates the env vars and returns the first env which failed the check.
+fn validate_env_list<F>(
+    env_vars: &Option<Vec<String>>,
+    criterion: &str,
+    description: &str,
+    is_valid: F,
+) -> Result<(), SkipReason>
+where
+    F: Fn(&str) -> bool,
+{
+    match *env_vars {
+        Some(ref env_vars) => {
             for key in env_vars.iter() {
-                match env::var(key) {
-                    Err(_) => {
-                        all_valid = false;
-                    }
-                    _ => (),
-                };
-
-                if !all_valid {
-                    break;
+                if !is_valid(&key) {
+                    return criterion_not_met(criterion, format!("env {} {}", &key, description));
                 }
             }
 
-            all_valid
+            Ok(())
         }
-        None => true,
+        None => Ok(()),
     }
 }
 
-fn validate_env_not_set(condition: &TaskCondition) -> bool {
-    let env = condition.env_not_set.clone();
+fn validate_env(condition: &TaskCondition) -> Result<(), SkipReason> {
+    validate_env_map(&condition.env, "env", "", |value, expected| {
+        value == expected
+    })
+}
 
-    match env {
-        Some(env_vars) => {
-            let mut all_valid = true;
+fn validate_env_contains(condition: &TaskCondition) -> Result<(), SkipReason> {
+    validate_env_map(
+        &condition.env_contains,
+        "env_contains",
+        "to contain ",
+        |value, expected| value.contains(expected),
+    )
+}
 
-            for key in env_vars.iter() {
-                match env::var(key) {
-                    Ok(_) => {
-                        all_valid = false;
+fn validate_env_regex(condition: &TaskCondition) -> Result<(), SkipReason> {
+    match condition.env_regex {
+        Some(ref env_vars) => {
+            for (key, expression) in env_vars.iter() {
+                let regex = match Regex::new(&expression) {
+                    Ok(value) => value,
+                    Err(error) => {
+                        error!(
+                            "Invalid env regex condition: {} error: {}",
+                            &expression, error
+                        );
+                        panic!(
+                            "Invalid env regex condition: {} error: {}",
+                            &expression, error
+                        );
                     }
-                    _ => (),
                 };
 
-                if !all_valid {
-                    break;
+                let valid = match env::var(key) {
+                    Ok(value) => regex.is_match(&value),
+                    _ => false,
+                };
+
+                if !valid {
+                    return criterion_not_met(
+                        "env_regex",
+                        get_env_mismatch_details(&key, "to match ", &expression),
+                    );
                 }
             }
 
-            all_valid
+            Ok(())
         }
-        None => true,
+        None => Ok(()),
     }
 }
 
-fn validate_platform(condition: &TaskCondition) -> bool {
-    let platforms = condition.platforms.clone();
-    match platforms {
-        Some(platform_names) => {
+fn validate_env_true(condition: &TaskCondition) -> Result<(), SkipReason> {
+    validate_env_list(
+        &condition.env_true,
+        "env_true",
+        "expected to be true",
+        |key| environment::get_env_as_bool(&key, false),
+    )
+}
+
+fn validate_env_set(condition: &TaskCondition) -> Result<(), SkipReason> {
+    validate_env_list(&condition.env_set, "env_set", "is not set", |key| {
+        env::var(key).is_ok()
+    })
+}
+
+fn validate_env_not_set(condition: &TaskCondition) -> Result<(), SkipReason> {
+    validate_env_list(&condition.env_not_set, "env_not_set", "is set", |key| {
+        env::var(key).is_err()
+    })
+}
+
+fn validate_platform(condition: &TaskCondition) -> Result<(), SkipReason> {
+    match condition.platforms {
+        Some(ref platform_names) => {
             let platform_name = types::get_platform_name();
 
-            let index = platform_names
-                .iter()
-                .position(|value| *value == platform_name);
+            if platform_names.contains(&platform_name) {
+                Ok(())
+            } else {
+                criterion_not_met("platforms", format!("current platform: {}", &platform_name))
+            }
+        }
+        None => Ok(()),
+    }
+}
 
-            match index {
-                None => {
-                    debug!(
-                        "Failed platform condition, current platform: {}",
-                        &platform_name
-                    );
-                    false
-                }
-                _ => true,
+fn validate_profile(condition: &TaskCondition) -> Result<(), SkipReason> {
+    match condition.profiles {
+        Some(ref profile_names) => {
+            let profile_name = environment::get_profile_name();
+
+            if profile_names.contains(&profile_name) {
+                Ok(())
+            } else {
+                criterion_not_met("profiles", format!("current profile: {}", &profile_name))
             }
         }
-        None => true,
+        None => Ok(()),
     }
 }
 
-fn validate_channel(condition: &TaskCondition, flow_info: &FlowInfo) -> bool {
-    let channels = condition.channels.clone();
-    match channels {
-        Some(channel_names) => match flow_info.env_info.rust_info.channel {
-            Some(value) => {
-                let index = match value {
-                    RustChannel::Stable => channel_names
-                        .iter()
-                        .position(|value| *value == "stable".to_string()),
-                    RustChannel::Beta => channel_names
-                        .iter()
-                        .position(|value| *value == "beta".to_string()),
-                    RustChannel::Nightly => channel_names
-                        .iter()
-                        .position(|value| *value == "nightly".to_string()),
-                };
+fn get_channel_name(flow_info: &FlowInfo) -> String {
+    match flow_info.env_info.rust_info.channel {
+        Some(RustChannel::Stable) => "stable".to_string(),
+        Some(RustChannel::Beta) => "beta".to_string(),
+        Some(RustChannel::Nightly) => "nightly".to_string(),
+        None => "unknown".to_string(),
+    }
+}
 
-                match index {
-                    None => {
-                        debug!("Failed channel condition");
-                        false
-                    }
-                    _ => true,
-                }
+fn validate_channel(condition: &TaskCondition, flow_info: &FlowInfo) -> Result<(), SkipReason> {
+    match condition.channels {
+        Some(ref channel_names) => {
+            let channel_name = get_channel_name(&flow_info);
+
+            let valid = match flow_info.env_info.rust_info.channel {
+                Some(_) => channel_names.contains(&channel_name),
+                None => false,
+            };
+
+            if valid {
+                Ok(())
+            } else {
+                criterion_not_met("channels", format!("current channel: {}", &channel_name))
             }
-            None => false,
-        },
-        None => true,
+        }
+        None => Ok(()),
+    }
+}
+
+fn parse_version(version: &str) -> Option<Version> {
+    match Version::parse(version) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            debug!("Unable to parse version: {} error: {}", version, error);
+            None
+        }
     }
 }
 
 fn validate_rust_version_condition(rustinfo: RustInfo, condition: RustVersionCondition) -> bool {
     if rustinfo.version.is_some() {
-        let current_version = rustinfo.version.unwrap();
+        let current_string = rustinfo.version.unwrap();
+        let current_version = match parse_version(&current_string) {
+            Some(value) => value,
+            None => return true,
+        };
 
         let mut valid = match condition.min {
-            Some(version) => {
-                version == current_version || is_newer(&version, &current_version, true)
-            }
+            Some(version) => match parse_version(&version) {
+                Some(min_version) => current_version >= min_version,
+                None => true,
+            },
             None => true,
         };
 
         if valid {
             valid = match condition.max {
-                Some(version) => {
-                    version == current_version || is_newer(&current_version, &version, true)
-                }
+                Some(version) => match parse_version(&version) {
+                    Some(max_version) => current_version <= max_version,
+                    None => true,
+                },
                 None => true,
             };
         }
 
         if valid {
             valid = match condition.equal {
-                Some(version) => version == current_version,
+                Some(version) => match parse_version(&version) {
+                    Some(equal_version) => current_version == equal_version,
+                    None => version == current_string,
+                },
                 None => true,
             };
         }
@@ -184,35 +287,509 @@ fn validate_rust_version_condition(rustinfo: RustInfo, condition: RustVersionCon
     }
 }
 
-fn validate_rust_version(condition: &TaskCondition) -> bool {
-    let rust_version = condition.rust_version.clone();
-    match rust_version {
-        Some(rust_version_condition) => {
-            let rustinfo = rust_info::get();
+fn validate_rust_version_requirement(rustinfo: RustInfo, requirement: &str) -> bool {
+    let version_requirement = match VersionReq::parse(requirement) {
+        Ok(value) => value,
+        Err(error) => {
+            error!(
+                "Invalid rust version requirement: {} error: {}",
+                requirement, error
+            );
+            panic!(
+                "Invalid rust version requirement: {} error: {}",
+                requirement, error
+            );
+        }
+    };
+
+    match rustinfo.version {
+        Some(current_string) => match parse_version(&current_string) {
+            Some(mut current_version) => {
+                // pre release versions (nightly/beta) never match a requirement without a pre release
+                current_version.pre = vec![];
+                current_version.build = vec![];
+
+                version_requirement.matches(&current_version)
+            }
+            None => true,
+        },
+        None => true,
+    }
+}
+
+fn validate_rust_version(
+    condition: &TaskCondition,
+    flow_info: &FlowInfo,
+) -> Result<(), SkipReason> {
+    match condition.rust_version {
+        Some(ref rust_version) => {
+            let rustinfo = flow_info.env_info.rust_info.clone();
+
+            let valid = match rust_version {
+                RustVersion::Requirement(ref requirement) => {
+                    validate_rust_version_requirement(rustinfo, &requirement)
+                }
+                RustVersion::Range(ref rust_version_condition) => {
+                    validate_rust_version_condition(rustinfo, rust_version_condition.clone())
+                }
+            };
+
+            if valid {
+                Ok(())
+            } else {
+                let version = flow_info
+                    .env_info
+                    .rust_info
+                    .version
+                    .clone()
+                    .unwrap_or("unknown".to_string());
+
+                criterion_not_met("rust_version", format!("current rust version: {}", version))
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+fn validate_ci(condition: &TaskCondition) -> Result<(), SkipReason> {
+    match condition.ci {
+        Some(expected_ci) => {
+            let ci = environment::get_env_as_bool("CARGO_MAKE_CI", false);
+
+            if ci == expected_ci {
+                Ok(())
+            } else {
+                criterion_not_met("ci", format!("ci: {}", ci))
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+fn validate_ci_vendors(condition: &TaskCondition) -> Result<(), SkipReason> {
+    match condition.ci_vendors {
+        Some(ref vendors) => {
+            let vendor = environment::get_env("CARGO_MAKE_CI_VENDOR", "");
+
+            if vendor.len() > 0 && vendors.iter().any(|value| value.to_lowercase() == vendor) {
+                Ok(())
+            } else if vendor.len() > 0 {
+                criterion_not_met("ci_vendors", format!("current ci vendor: {}", &vendor))
+            } else {
+                criterion_not_met("ci_vendors", "current ci vendor: unknown".to_string())
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+fn validate_crate_has_feature(
+    condition: &TaskCondition,
+    flow_info: &FlowInfo,
+) -> Result<(), SkipReason> {
+    match condition.crate_has_feature {
+        Some(ref features) => match features
+            .iter()
+            .find(|feature| !crateinfo::has_feature(&flow_info.env_info.crate_info, &feature))
+        {
+            Some(feature) => criterion_not_met(
+                "crate_has_feature",
+                format!("feature {} not defined", &feature),
+            ),
+            None => Ok(()),
+        },
+        None => Ok(()),
+    }
+}
+
+fn validate_crate_has_dependency(
+    condition: &TaskCondition,
+    flow_info: &FlowInfo,
+) -> Result<(), SkipReason> {
+    match condition.crate_has_dependency {
+        Some(ref dependencies) => match dependencies.iter().find(|dependency| {
+            !crateinfo::has_dependency(&flow_info.env_info.crate_info, &dependency)
+        }) {
+            Some(dependency) => criterion_not_met(
+                "crate_has_dependency",
+                format!("dependency {} not defined", &dependency),
+            ),
+            None => Ok(()),
+        },
+        None => Ok(()),
+    }
+}
+
+fn has_targets(targets: &Option<Vec<CrateTarget>>) -> bool {
+    match *targets {
+        Some(ref targets) => !targets.is_empty(),
+        None => false,
+    }
+}
 
-            validate_rust_version_condition(rustinfo, rust_version_condition)
+fn validate_crate_has_targets(
+    expected: Option<bool>,
+    targets: &Option<Vec<CrateTarget>>,
+    target_type: &str,
+) -> Result<(), SkipReason> {
+    match expected {
+        Some(expected_value) => {
+            let found = has_targets(&targets);
+
+            if found == expected_value {
+                Ok(())
+            } else {
+                criterion_not_met(
+                    &format!("crate_has_{}", target_type),
+                    format!("crate has {}: {}", target_type, found),
+                )
+            }
         }
+        None => Ok(()),
+    }
+}
+
+fn validate_nightly_date_condition(date: &str, condition: &NightlyDateCondition) -> bool {
+    let valid = match condition.min {
+        Some(ref min_date) => date >= min_date.as_str(),
         None => true,
+    };
+
+    valid
+        && match condition.max {
+            Some(ref max_date) => date <= max_date.as_str(),
+            None => true,
+        }
+}
+
+/// Validates the compiler commit date (usually one day before the nightly toolchain name date).
+fn validate_nightly_date(
+    condition: &TaskCondition,
+    flow_info: &FlowInfo,
+) -> Result<(), SkipReason> {
+    match condition.nightly_date {
+        Some(ref nightly_date_condition) => {
+            let valid = match flow_info.env_info.rust_info.channel {
+                Some(RustChannel::Nightly) => match env::var("CARGO_MAKE_RUST_COMMIT_DATE") {
+                    Ok(date) => validate_nightly_date_condition(&date, &nightly_date_condition),
+                    _ => true,
+                },
+                Some(_) => false,
+                None => true,
+            };
+
+            if valid {
+                Ok(())
+            } else {
+                criterion_not_met(
+                    "nightly_date",
+                    format!(
+                        "current channel: {}, commit date: {}",
+                        get_channel_name(&flow_info),
+                        environment::get_env("CARGO_MAKE_RUST_COMMIT_DATE", "unknown")
+                    ),
+                )
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns all existing paths matching the provided path/glob (after env expansion).
+fn get_matching_paths(path_glob: &str) -> Vec<PathBuf> {
+    let pattern = environment::expand_value(&path_glob);
+
+    match glob(&pattern) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()).collect(),
+        Err(error) => {
+            debug!("Invalid glob: {} error: {:#?}", &pattern, error);
+            vec![]
+        }
+    }
+}
+
+fn validate_files_exist(condition: &TaskCondition) -> Result<(), SkipReason> {
+    match condition.files_exist {
+        Some(ref files) => {
+            for file in files {
+                if get_matching_paths(&file).is_empty() {
+                    return criterion_not_met("files_exist", format!("file {} not found", &file));
+                }
+            }
+
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
+fn validate_files_not_exist(condition: &TaskCondition) -> Result<(), SkipReason> {
+    match condition.files_not_exist {
+        Some(ref files) => {
+            for file in files {
+                if !get_matching_paths(&file).is_empty() {
+                    return criterion_not_met("files_not_exist", format!("file {} found", &file));
+                }
+            }
+
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns the latest modification time of all files matching the provided globs.
+fn get_latest_modified_time(globs: &Vec<String>) -> Option<SystemTime> {
+    let mut latest = None;
+
+    for path_glob in globs {
+        for path in get_matching_paths(&path_glob) {
+            let modified = match fs::metadata(&path) {
+                Ok(metadata) => metadata.modified().ok(),
+                _ => None,
+            };
+
+            latest = match (latest, modified) {
+                (Some(current), Some(value)) => {
+                    if value > current {
+                        Some(value)
+                    } else {
+                        Some(current)
+                    }
+                }
+                (None, value) => value,
+                (current, None) => current,
+            };
+        }
+    }
+
+    latest
+}
+
+fn validate_files_modified_condition(condition: &FilesModifiedCondition) -> bool {
+    match get_latest_modified_time(&condition.output) {
+        Some(output_time) => match get_latest_modified_time(&condition.input) {
+            Some(input_time) => input_time > output_time,
+            None => {
+                debug!("Failed files modified condition, no input files found");
+                false
+            }
+        },
+        // no output files means the outputs were never created
+        None => true,
+    }
+}
+
+fn validate_files_modified(condition: &TaskCondition) -> Result<(), SkipReason> {
+    match condition.files_modified {
+        Some(ref files_modified_condition) => {
+            if validate_files_modified_condition(&files_modified_condition) {
+                Ok(())
+            } else {
+                criterion_not_met(
+                    "files_modified",
+                    "input files were not modified".to_string(),
+                )
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+fn matches_pattern(patterns: &Vec<String>, value: &str) -> bool {
+    for pattern in patterns {
+        if pattern == value {
+            return true;
+        }
+
+        match Pattern::new(&pattern) {
+            Ok(glob_pattern) => {
+                if glob_pattern.matches(&value) {
+                    return true;
+                }
+            }
+            Err(error) => debug!("Invalid pattern: {} error: {:#?}", &pattern, error),
+        };
+    }
+
+    false
+}
+
+fn validate_git_branches(
+    condition: &TaskCondition,
+    flow_info: &FlowInfo,
+) -> Result<(), SkipReason> {
+    match condition.git_branches {
+        Some(ref branches) => match flow_info.env_info.git_info.branch {
+            Some(ref branch) => {
+                if matches_pattern(&branches, &branch) {
+                    Ok(())
+                } else {
+                    criterion_not_met("git_branches", format!("current branch: {}", &branch))
+                }
+            }
+            None => criterion_not_met("git_branches", "current branch: unknown".to_string()),
+        },
+        None => Ok(()),
+    }
+}
+
+fn validate_git_clean(
+    condition: &TaskCondition,
+    git_clean: Option<bool>,
+) -> Result<(), SkipReason> {
+    match condition.git_clean {
+        Some(expected_clean) => match git_clean {
+            Some(clean) => {
+                if clean == expected_clean {
+                    Ok(())
+                } else {
+                    criterion_not_met("git_clean", format!("working tree clean: {}", clean))
+                }
+            }
+            None => criterion_not_met("git_clean", "git status not found".to_string()),
+        },
+        None => Ok(()),
+    }
+}
+
+fn validate_git_changed_files(condition: &TaskCondition) -> Result<(), SkipReason> {
+    match condition.git_changed_files {
+        Some(ref patterns) => {
+            let base_ref = gitinfo::get_base_ref();
+
+            match gitinfo::get_changed_files(&base_ref) {
+                Some(files) => {
+                    if files.iter().any(|file| matches_pattern(&patterns, &file)) {
+                        Ok(())
+                    } else {
+                        criterion_not_met(
+                            "git_changed_files",
+                            format!("no changed files since: {}", &base_ref),
+                        )
+                    }
+                }
+                None => {
+                    // unable to find the changes, assume everything changed
+                    debug!("Unable to find changed files, base ref: {}", &base_ref);
+                    Ok(())
+                }
+            }
+        }
+        None => Ok(()),
     }
 }
 
-fn validate_criteria(flow_info: &FlowInfo, step: &Step) -> bool {
+fn validate_any(condition: &TaskCondition, flow_info: &FlowInfo) -> Result<(), SkipReason> {
+    match condition.any {
+        Some(ref conditions) => {
+            if conditions
+                .iter()
+                .any(|nested_condition| validate_task_condition(&nested_condition, &flow_info))
+            {
+                Ok(())
+            } else {
+                criterion_not_met("any", "no nested condition was met".to_string())
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+fn validate_all(condition: &TaskCondition, flow_info: &FlowInfo) -> Result<(), SkipReason> {
+    match condition.all {
+        Some(ref conditions) => {
+            for nested_condition in conditions {
+                match check_task_condition(&nested_condition, &flow_info) {
+                    Err(reason) => return criterion_not_met("all", format!("nested {}", reason)),
+                    _ => (),
+                };
+            }
+
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
+fn validate_not(condition: &TaskCondition, flow_info: &FlowInfo) -> Result<(), SkipReason> {
+    match condition.not {
+        Some(ref nested_condition) => {
+            if validate_task_condition(&nested_condition, &flow_info) {
+                criterion_not_met("not", "nested condition was met".to_string())
+            } else {
+                Ok(())
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+fn validate_task_condition(condition: &TaskCondition, flow_info: &FlowInfo) -> bool {
+    check_task_condition(&condition, &flow_info).is_ok()
+}
+
+/// Validates the provided condition and returns the first criterion which was not met.
+fn check_task_condition(condition: &TaskCondition, flow_info: &FlowInfo) -> Result<(), SkipReason> {
+    validate_platform(&condition)?;
+    validate_profile(&condition)?;
+    validate_channel(&condition, &flow_info)?;
+    validate_env(&condition)?;
+    validate_env_contains(&condition)?;
+    validate_env_regex(&condition)?;
+    validate_env_true(&condition)?;
+    validate_env_set(&condition)?;
+    validate_env_not_set(&condition)?;
+    validate_rust_version(&condition, &flow_info)?;
+    validate_nightly_date(&condition, &flow_info)?;
+    validate_ci(&condition)?;
+    validate_ci_vendors(&condition)?;
+    validate_crate_has_feature(&condition, &flow_info)?;
+    validate_crate_has_dependency(&condition, &flow_info)?;
+    validate_crate_has_targets(
+        condition.crate_has_bin,
+        &flow_info.env_info.crate_info.bin,
+        "bin",
+    )?;
+    validate_crate_has_targets(
+        condition.crate_has_examples,
+        &flow_info.env_info.crate_info.example,
+        "examples",
+    )?;
+    validate_crate_has_targets(
+        condition.crate_has_benches,
+        &flow_info.env_info.crate_info.bench,
+        "benches",
+    )?;
+    validate_files_exist(&condition)?;
+    validate_files_not_exist(&condition)?;
+    validate_files_modified(&condition)?;
+    validate_git_branches(&condition, &flow_info)?;
+    // git status is only invoked if needed as it may be slow for large repositories
+    let git_clean = match condition.git_clean {
+        Some(_) => gitinfo::is_clean(),
+        None => None,
+    };
+    validate_git_clean(&condition, git_clean)?;
+    validate_git_changed_files(&condition)?;
+    validate_any(&condition, &flow_info)?;
+    validate_all(&condition, &flow_info)?;
+    validate_not(&condition, &flow_info)
+}
+
+/// Validates the task condition structure which does not require running any script.
+pub(crate) fn validate_criteria(flow_info: &FlowInfo, step: &Step) -> Result<(), SkipReason> {
     match step.config.condition {
         Some(ref condition) => {
             debug!("Checking task condition structure.");
 
-            validate_platform(&condition)
-                && validate_channel(&condition, &flow_info)
-                && validate_env(&condition)
-                && validate_env_set(&condition)
-                && validate_env_not_set(&condition)
-                && validate_rust_version(&condition)
+            check_task_condition(&condition, &flow_info)
         }
-        None => true,
+        None => Ok(()),
     }
 }
 
-fn validate_script(step: &Step) -> bool {
+fn validate_script(step: &Step) -> Result<(), SkipReason> {
     match step.config.condition_script {
         Some(ref script) => {
             debug!("Checking task condition script.");
@@ -221,15 +798,18 @@ fn validate_script(step: &Step) -> bool {
                 command::run_script(&script, step.config.script_runner.clone(), &vec![], false);
 
             if exit_code == 0 {
-                true
+                Ok(())
             } else {
-                false
+                Err(SkipReason::ConditionScript(exit_code))
             }
         }
-        None => true,
+        None => Ok(()),
     }
 }
 
-pub(crate) fn validate_condition(flow_info: &FlowInfo, step: &Step) -> bool {
-    validate_criteria(&flow_info, &step) && validate_script(&step)
+/// Validates the task condition and condition script and returns the reason the task
+/// should be skipped (if any).
+pub(crate) fn validate_condition(flow_info: &FlowInfo, step: &Step) -> Result<(), SkipReason> {
+    validate_criteria(&flow_info, &step)?;
+    validate_script(&step)
 }
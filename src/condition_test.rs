@@ -1,9 +1,14 @@
 use super::*;
 use indexmap::IndexMap;
 use rust_info::types::{RustChannel, RustInfo};
+use std::collections::HashSet;
+use std::fs::{create_dir_all, remove_dir_all, File};
+use std::path::Path;
+use std::{thread, time};
+use toml;
 use types::{
-    Config, ConfigSection, CrateInfo, EnvInfo, FlowInfo, GitInfo, RustVersionCondition, Step, Task,
-    TaskCondition,
+    Config, ConfigSection, CrateInfo, EnvInfo, FlowInfo, GitInfo, NightlyDateCondition,
+    RustVersion, RustVersionCondition, Step, Task, TaskCondition,
 };
 
 #[test]
@@ -15,9 +20,30 @@ fn validate_env_set_empty() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_env_set(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env_set(&condition).is_ok();
 
     assert!(enabled);
 }
@@ -34,9 +60,30 @@ fn validate_env_set_valid() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_env_set(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env_set(&condition).is_ok();
 
     assert!(enabled);
 }
@@ -50,9 +97,30 @@ fn validate_env_set_invalid() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_env_set(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env_set(&condition).is_ok();
 
     assert!(!enabled);
 }
@@ -73,9 +141,30 @@ fn validate_env_set_invalid_partial_found() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_env_set(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env_set(&condition).is_ok();
 
     assert!(!enabled);
 }
@@ -89,9 +178,30 @@ fn validate_env_not_set_empty() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_env_not_set(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env_not_set(&condition).is_ok();
 
     assert!(enabled);
 }
@@ -105,9 +215,30 @@ fn validate_env_not_set_valid() {
         env_not_set: Some(vec!["BAD_ENV_SET1".to_string(), "BAD_ENV_SET2".to_string()]),
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_env_not_set(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env_not_set(&condition).is_ok();
 
     assert!(enabled);
 }
@@ -124,9 +255,30 @@ fn validate_env_not_set_invalid() {
         env_not_set: Some(vec!["ENV_SET1".to_string(), "ENV_SET2".to_string()]),
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_env_not_set(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env_not_set(&condition).is_ok();
 
     assert!(!enabled);
 }
@@ -147,9 +299,30 @@ fn validate_env_not_set_invalid_partial_found() {
         ]),
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_env_not_set(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env_not_set(&condition).is_ok();
 
     assert!(!enabled);
 }
@@ -163,9 +336,30 @@ fn validate_env_empty() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_env(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env(&condition).is_ok();
 
     assert!(enabled);
 }
@@ -186,9 +380,30 @@ fn validate_env_valid() {
         env_not_set: None,
         env: Some(env_values),
         rust_version: None,
-    };
-
-    let enabled = validate_env(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env(&condition).is_ok();
 
     assert!(enabled);
 }
@@ -206,9 +421,30 @@ fn validate_env_invalid_not_found() {
         env_not_set: None,
         env: Some(env_values),
         rust_version: None,
-    };
-
-    let enabled = validate_env(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env(&condition).is_ok();
 
     assert!(!enabled);
 }
@@ -227,9 +463,30 @@ fn validate_env_invalid_not_equal() {
         env_not_set: None,
         env: Some(env_values),
         rust_version: None,
-    };
-
-    let enabled = validate_env(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env(&condition).is_ok();
 
     assert!(!enabled);
 }
@@ -250,9 +507,30 @@ fn validate_env_invalid_partial_found() {
         env_not_set: None,
         env: Some(env_values),
         rust_version: None,
-    };
-
-    let enabled = validate_env(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_env(&condition).is_ok();
 
     assert!(!enabled);
 }
@@ -265,7 +543,7 @@ fn validate_script_empty() {
         config: task,
     };
 
-    let enabled = validate_script(&step);
+    let enabled = validate_script(&step).is_ok();
 
     assert!(enabled);
 }
@@ -279,7 +557,7 @@ fn validate_script_valid() {
         config: task,
     };
 
-    let enabled = validate_script(&step);
+    let enabled = validate_script(&step).is_ok();
 
     assert!(enabled);
 }
@@ -293,7 +571,7 @@ fn validate_script_invalid() {
         config: task,
     };
 
-    let enabled = validate_script(&step);
+    let enabled = validate_script(&step).is_ok();
 
     assert!(!enabled);
 }
@@ -311,9 +589,30 @@ fn validate_platform_valid() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_platform(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_platform(&condition).is_ok();
 
     assert!(enabled);
 }
@@ -327,9 +626,30 @@ fn validate_platform_invalid() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_platform(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_platform(&condition).is_ok();
 
     assert!(!enabled);
 }
@@ -352,6 +672,7 @@ fn validate_channel_valid() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     flow_info.env_info.rust_info.channel = Some(RustChannel::Stable);
@@ -366,8 +687,29 @@ fn validate_channel_valid() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-    let mut enabled = validate_channel(&condition, &flow_info);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+    let mut enabled = validate_channel(&condition, &flow_info).is_ok();
     assert!(enabled);
 
     flow_info.env_info.rust_info.channel = Some(RustChannel::Beta);
@@ -382,8 +724,29 @@ fn validate_channel_valid() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-    enabled = validate_channel(&condition, &flow_info);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+    enabled = validate_channel(&condition, &flow_info).is_ok();
 
     assert!(enabled);
 
@@ -399,8 +762,29 @@ fn validate_channel_valid() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-    enabled = validate_channel(&condition, &flow_info);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+    enabled = validate_channel(&condition, &flow_info).is_ok();
 
     assert!(enabled);
 }
@@ -423,6 +807,7 @@ fn validate_channel_invalid() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     flow_info.env_info.rust_info.channel = Some(RustChannel::Stable);
@@ -433,8 +818,29 @@ fn validate_channel_invalid() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-    let enabled = validate_channel(&condition, &flow_info);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+    let enabled = validate_channel(&condition, &flow_info).is_ok();
 
     assert!(!enabled);
 }
@@ -462,6 +868,7 @@ fn validate_criteria_empty() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     step.config.condition = Some(TaskCondition {
@@ -471,9 +878,30 @@ fn validate_criteria_empty() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
 
-    let enabled = validate_criteria(&flow_info, &step);
+    let enabled = validate_criteria(&flow_info, &step).is_ok();
 
     assert!(enabled);
 }
@@ -501,6 +929,7 @@ fn validate_criteria_valid_platform() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     step.config.condition = Some(TaskCondition {
@@ -514,9 +943,30 @@ fn validate_criteria_valid_platform() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
 
-    let enabled = validate_criteria(&flow_info, &step);
+    let enabled = validate_criteria(&flow_info, &step).is_ok();
 
     assert!(enabled);
 }
@@ -544,6 +994,7 @@ fn validate_criteria_invalid_platform() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     step.config.condition = Some(TaskCondition {
@@ -553,9 +1004,30 @@ fn validate_criteria_invalid_platform() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
 
-    let enabled = validate_criteria(&flow_info, &step);
+    let enabled = validate_criteria(&flow_info, &step).is_ok();
 
     assert!(!enabled);
 }
@@ -583,6 +1055,7 @@ fn validate_criteria_valid_channel() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     flow_info.env_info.rust_info.channel = Some(RustChannel::Stable);
@@ -597,8 +1070,29 @@ fn validate_criteria_valid_channel() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
-    let mut enabled = validate_criteria(&flow_info, &step);
+    let mut enabled = validate_criteria(&flow_info, &step).is_ok();
 
     assert!(enabled);
 
@@ -614,8 +1108,29 @@ fn validate_criteria_valid_channel() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
-    enabled = validate_criteria(&flow_info, &step);
+    enabled = validate_criteria(&flow_info, &step).is_ok();
 
     assert!(enabled);
 
@@ -631,8 +1146,29 @@ fn validate_criteria_valid_channel() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
-    enabled = validate_criteria(&flow_info, &step);
+    enabled = validate_criteria(&flow_info, &step).is_ok();
 
     assert!(enabled);
 }
@@ -660,6 +1196,7 @@ fn validate_criteria_invalid_channel() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     flow_info.env_info.rust_info.channel = Some(RustChannel::Stable);
@@ -670,8 +1207,29 @@ fn validate_criteria_invalid_channel() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
-    let enabled = validate_criteria(&flow_info, &step);
+    let enabled = validate_criteria(&flow_info, &step).is_ok();
 
     assert!(!enabled);
 }
@@ -699,6 +1257,7 @@ fn validate_condition_both_valid() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     step.config.condition = Some(TaskCondition {
@@ -712,10 +1271,31 @@ fn validate_condition_both_valid() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
     step.config.condition_script = Some(vec!["exit 0".to_string()]);
 
-    let enabled = validate_condition(&flow_info, &step);
+    let enabled = validate_condition(&flow_info, &step).is_ok();
 
     assert!(enabled);
 }
@@ -743,6 +1323,7 @@ fn validate_criteria_valid_script_invalid() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     step.config.condition = Some(TaskCondition {
@@ -756,10 +1337,31 @@ fn validate_criteria_valid_script_invalid() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
     step.config.condition_script = Some(vec!["exit 1".to_string()]);
 
-    let enabled = validate_condition(&flow_info, &step);
+    let enabled = validate_condition(&flow_info, &step).is_ok();
 
     assert!(!enabled);
 }
@@ -787,6 +1389,7 @@ fn validate_criteria_invalid_script_valid() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     step.config.condition = Some(TaskCondition {
@@ -796,10 +1399,31 @@ fn validate_criteria_invalid_script_valid() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
     step.config.condition_script = Some(vec!["exit 0".to_string()]);
 
-    let enabled = validate_condition(&flow_info, &step);
+    let enabled = validate_condition(&flow_info, &step).is_ok();
 
     assert!(!enabled);
 }
@@ -827,6 +1451,7 @@ fn validate_criteria_invalid_env_set() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     step.config.condition = Some(TaskCondition {
@@ -836,10 +1461,31 @@ fn validate_criteria_invalid_env_set() {
         env_not_set: None,
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
     step.config.condition_script = Some(vec!["exit 0".to_string()]);
 
-    let enabled = validate_condition(&flow_info, &step);
+    let enabled = validate_condition(&flow_info, &step).is_ok();
 
     assert!(!enabled);
 }
@@ -867,6 +1513,7 @@ fn validate_criteria_invalid_env_not_set() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     env::set_var("ENV_SET1", "bad");
@@ -878,10 +1525,31 @@ fn validate_criteria_invalid_env_not_set() {
         env_not_set: Some(vec!["ENV_SET1".to_string()]),
         env: None,
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
     step.config.condition_script = Some(vec!["exit 0".to_string()]);
 
-    let enabled = validate_condition(&flow_info, &step);
+    let enabled = validate_condition(&flow_info, &step).is_ok();
 
     assert!(!enabled);
 }
@@ -909,6 +1577,7 @@ fn validate_criteria_valid_env() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     env::set_var("ENV_SET1", "good1");
@@ -925,10 +1594,31 @@ fn validate_criteria_valid_env() {
         env_not_set: None,
         env: Some(env_values),
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
     step.config.condition_script = Some(vec!["exit 0".to_string()]);
 
-    let enabled = validate_condition(&flow_info, &step);
+    let enabled = validate_condition(&flow_info, &step).is_ok();
 
     assert!(enabled);
 }
@@ -956,6 +1646,7 @@ fn validate_criteria_invalid_env_not_found() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut env_values = IndexMap::<String, String>::new();
@@ -969,10 +1660,31 @@ fn validate_criteria_invalid_env_not_found() {
         env_not_set: None,
         env: Some(env_values),
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
     step.config.condition_script = Some(vec!["exit 0".to_string()]);
 
-    let enabled = validate_condition(&flow_info, &step);
+    let enabled = validate_condition(&flow_info, &step).is_ok();
 
     assert!(!enabled);
 }
@@ -1000,6 +1712,7 @@ fn validate_criteria_invalid_env_not_equal() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     env::set_var("ENV_SET1", "good");
@@ -1016,10 +1729,31 @@ fn validate_criteria_invalid_env_not_equal() {
         env_not_set: None,
         env: Some(env_values),
         rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
     step.config.condition_script = Some(vec!["exit 0".to_string()]);
 
-    let enabled = validate_condition(&flow_info, &step);
+    let enabled = validate_condition(&flow_info, &step).is_ok();
 
     assert!(!enabled);
 }
@@ -1040,13 +1774,14 @@ fn validate_criteria_valid_rust_version() {
         config,
         task: "test".to_string(),
         env_info: EnvInfo {
-            rust_info: RustInfo::new(),
+            rust_info: rust_info::get(),
             crate_info: CrateInfo::new(),
             git_info: GitInfo::new(),
         },
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let rustinfo = rust_info::get();
@@ -1058,14 +1793,35 @@ fn validate_criteria_valid_rust_version() {
         env_set: None,
         env_not_set: None,
         env: None,
-        rust_version: Some(RustVersionCondition {
+        rust_version: Some(RustVersion::Range(RustVersionCondition {
             min: None,
             max: None,
             equal: Some(version),
-        }),
+        })),
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
 
-    let enabled = validate_condition(&flow_info, &step);
+    let enabled = validate_condition(&flow_info, &step).is_ok();
 
     assert!(enabled);
 }
@@ -1086,13 +1842,14 @@ fn validate_criteria_invalid_rust_version() {
         config,
         task: "test".to_string(),
         env_info: EnvInfo {
-            rust_info: RustInfo::new(),
+            rust_info: rust_info::get(),
             crate_info: CrateInfo::new(),
             git_info: GitInfo::new(),
         },
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let rustinfo = rust_info::get();
@@ -1105,14 +1862,35 @@ fn validate_criteria_invalid_rust_version() {
         env_set: None,
         env_not_set: None,
         env: None,
-        rust_version: Some(RustVersionCondition {
+        rust_version: Some(RustVersion::Range(RustVersionCondition {
             min: None,
             max: None,
             equal: Some(version),
-        }),
+        })),
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
     });
 
-    let enabled = validate_condition(&flow_info, &step);
+    let enabled = validate_condition(&flow_info, &step).is_ok();
 
     assert!(!enabled);
 }
@@ -1380,16 +2158,39 @@ fn validate_rust_version_no_condition() {
         env_not_set: None,
         env: None,
         rust_version: None,
-    };
-
-    let enabled = validate_rust_version(&condition);
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_rust_version(&condition, &create_flow_info()).is_ok();
 
     assert!(enabled);
 }
 
 #[test]
 fn validate_rust_version_with_valid_condition() {
-    let rustinfo = rust_info::get();
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.rust_info = rust_info::get();
+    let rustinfo = flow_info.env_info.rust_info.clone();
     let version = rustinfo.version.unwrap();
 
     let condition = TaskCondition {
@@ -1398,21 +2199,44 @@ fn validate_rust_version_with_valid_condition() {
         env_set: None,
         env_not_set: None,
         env: None,
-        rust_version: Some(RustVersionCondition {
+        rust_version: Some(RustVersion::Range(RustVersionCondition {
             min: Some(version.clone()),
             max: Some(version.clone()),
             equal: Some(version.clone()),
-        }),
-    };
-
-    let enabled = validate_rust_version(&condition);
+        })),
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_rust_version(&condition, &flow_info).is_ok();
 
     assert!(enabled);
 }
 
 #[test]
 fn validate_rust_version_with_invalid_condition() {
-    let rustinfo = rust_info::get();
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.rust_info = rust_info::get();
+    let rustinfo = flow_info.env_info.rust_info.clone();
     let mut version = rustinfo.version.unwrap();
     version.push_str("1");
 
@@ -1422,14 +2246,1088 @@ fn validate_rust_version_with_invalid_condition() {
         env_set: None,
         env_not_set: None,
         env: None,
-        rust_version: Some(RustVersionCondition {
+        rust_version: Some(RustVersion::Range(RustVersionCondition {
             min: None,
             max: None,
             equal: Some(version),
-        }),
+        })),
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_rust_version(&condition, &flow_info).is_ok();
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_profile_empty() {
+    let condition = TaskCondition {
+        platforms: None,
+        channels: None,
+        env_set: None,
+        env_not_set: None,
+        env: None,
+        rust_version: None,
+        profiles: None,
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_profile(&condition).is_ok();
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_profile_valid() {
+    env::set_var("CARGO_MAKE_PROFILE", "production");
+
+    let condition = TaskCondition {
+        platforms: None,
+        channels: None,
+        env_set: None,
+        env_not_set: None,
+        env: None,
+        rust_version: None,
+        profiles: Some(vec!["ci".to_string(), "production".to_string()]),
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_profile(&condition).is_ok();
+
+    env::remove_var("CARGO_MAKE_PROFILE");
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_profile_invalid() {
+    env::set_var("CARGO_MAKE_PROFILE", "development");
+
+    let condition = TaskCondition {
+        platforms: None,
+        channels: None,
+        env_set: None,
+        env_not_set: None,
+        env: None,
+        rust_version: None,
+        profiles: Some(vec!["production".to_string()]),
+        any: None,
+        all: None,
+        not: None,
+        files_exist: None,
+        files_not_exist: None,
+        files_modified: None,
+        git_branches: None,
+        git_clean: None,
+        git_changed_files: None,
+        env_contains: None,
+        env_regex: None,
+        env_true: None,
+        nightly_date: None,
+        ci: None,
+        ci_vendors: None,
+        crate_has_feature: None,
+        crate_has_dependency: None,
+        crate_has_bin: None,
+        crate_has_examples: None,
+        crate_has_benches: None,
+    };
+
+    let enabled = validate_profile(&condition).is_ok();
+
+    env::remove_var("CARGO_MAKE_PROFILE");
+
+    assert!(!enabled);
+}
+
+fn create_flow_info() -> FlowInfo {
+    let config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+
+    FlowInfo {
+        config,
+        task: "test".to_string(),
+        env_info: EnvInfo {
+            rust_info: RustInfo::new(),
+            crate_info: CrateInfo::new(),
+            git_info: GitInfo::new(),
+        },
+        disable_workspace: false,
+        disable_on_error: false,
+        cli_arguments: None,
+        installed: HashSet::new(),
+    }
+}
+
+fn parse_condition(condition_toml: &str) -> TaskCondition {
+    toml::from_str(condition_toml).unwrap()
+}
+
+#[test]
+fn validate_task_condition_empty() {
+    let condition = parse_condition("");
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_any_valid() {
+    let condition = parse_condition(&format!(
+        r#"
+any = [{{ platforms = ["bad"] }}, {{ platforms = ["{}"] }}]
+"#,
+        types::get_platform_name()
+    ));
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_any_invalid() {
+    let condition = parse_condition(
+        r#"
+any = [{ platforms = ["bad1"] }, { env_set = ["VALIDATE_ANY_INVALID_NOT_SET"] }]
+"#,
+    );
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_any_empty() {
+    let condition = parse_condition("any = []");
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_all_valid() {
+    env::set_var("VALIDATE_ALL_VALID_SET", "true");
+
+    let condition = parse_condition(&format!(
+        r#"
+all = [{{ env_set = ["VALIDATE_ALL_VALID_SET"] }}, {{ platforms = ["{}"] }}]
+"#,
+        types::get_platform_name()
+    ));
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_all_invalid() {
+    env::set_var("VALIDATE_ALL_INVALID_SET", "true");
+
+    let condition = parse_condition(
+        r#"
+all = [{ env_set = ["VALIDATE_ALL_INVALID_SET"] }, { platforms = ["bad"] }]
+"#,
+    );
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_not_valid() {
+    let condition = parse_condition(
+        r#"
+not = { env_set = ["VALIDATE_NOT_VALID_NOT_SET"] }
+"#,
+    );
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_not_invalid() {
+    env::set_var("VALIDATE_NOT_INVALID_SET", "true");
+
+    let condition = parse_condition(
+        r#"
+not = { env_set = ["VALIDATE_NOT_INVALID_SET"] }
+"#,
+    );
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_nested_combinators() {
+    env::set_var("VALIDATE_NESTED_SET", "true");
+    env::remove_var("VALIDATE_NESTED_NOT_SET");
+
+    let condition = parse_condition(&format!(
+        r#"
+platforms = ["{}"]
+any = [
+    {{ all = [{{ env_set = ["VALIDATE_NESTED_SET"] }}, {{ env_set = ["VALIDATE_NESTED_NOT_SET"] }}] }},
+    {{ not = {{ any = [{{ env_set = ["VALIDATE_NESTED_NOT_SET"] }}] }} }}
+]
+"#,
+        types::get_platform_name()
+    ));
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_combinators_and_fields() {
+    let condition = parse_condition(
+        r#"
+platforms = ["bad"]
+not = { env_set = ["VALIDATE_COMBINATORS_AND_FIELDS_NOT_SET"] }
+"#,
+    );
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_files_exist_valid() {
+    let condition = parse_condition(
+        r#"
+files_exist = ["./Cargo.toml", "${CARGO_MAKE_TEST_FILES_DIRECTORY:-./examples}/*.toml"]
+"#,
+    );
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_files_exist_invalid() {
+    let condition = parse_condition(
+        r#"
+files_exist = ["./Cargo.toml", "./examples/*.bad_extension"]
+"#,
+    );
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_files_not_exist_valid() {
+    let condition = parse_condition(
+        r#"
+files_not_exist = ["./bad_file", "./examples/*.bad_extension"]
+"#,
+    );
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_files_not_exist_invalid() {
+    let condition = parse_condition(
+        r#"
+files_not_exist = ["./bad_file", "./Cargo.toml"]
+"#,
+    );
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(!enabled);
+}
+
+fn create_files_modified_directory(name: &str) -> String {
+    let directory = format!("./target/_cargo_make_temp/condition/{}", name);
+    if Path::new(&directory).exists() {
+        remove_dir_all(&directory).unwrap();
+    }
+    create_dir_all(&directory).unwrap();
+
+    directory
+}
+
+#[test]
+fn validate_files_modified_no_output() {
+    let directory = create_files_modified_directory("no_output");
+    File::create(format!("{}/input.txt", &directory)).unwrap();
+
+    let condition = FilesModifiedCondition {
+        input: vec![format!("{}/*.txt", &directory)],
+        output: vec![format!("{}/*.out", &directory)],
+    };
+
+    assert!(validate_files_modified_condition(&condition));
+}
+
+#[test]
+fn validate_files_modified_no_input() {
+    let directory = create_files_modified_directory("no_input");
+    File::create(format!("{}/output.out", &directory)).unwrap();
+
+    let condition = FilesModifiedCondition {
+        input: vec![format!("{}/*.txt", &directory)],
+        output: vec![format!("{}/*.out", &directory)],
+    };
+
+    assert!(!validate_files_modified_condition(&condition));
+}
+
+#[test]
+fn validate_files_modified_input_newer() {
+    let directory = create_files_modified_directory("input_newer");
+    File::create(format!("{}/output.out", &directory)).unwrap();
+    thread::sleep(time::Duration::from_millis(1100));
+    File::create(format!("{}/input1.txt", &directory)).unwrap();
+
+    let condition = FilesModifiedCondition {
+        input: vec![format!("{}/*.txt", &directory)],
+        output: vec![format!("{}/*.out", &directory)],
     };
 
-    let enabled = validate_rust_version(&condition);
+    assert!(validate_files_modified_condition(&condition));
+}
+
+#[test]
+fn validate_files_modified_output_newer() {
+    let directory = create_files_modified_directory("output_newer");
+    File::create(format!("{}/input1.txt", &directory)).unwrap();
+    File::create(format!("{}/output1.out", &directory)).unwrap();
+    thread::sleep(time::Duration::from_millis(1100));
+    File::create(format!("{}/output2.out", &directory)).unwrap();
+
+    let condition = parse_condition(&format!(
+        r#"
+files_modified = {{ input = ["{0}/*.txt"], output = ["{0}/*.out"] }}
+"#,
+        &directory
+    ));
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_git_branches_valid() {
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.git_info.branch = Some("release/1.0".to_string());
+
+    let condition = parse_condition(r#"git_branches = ["master", "release/*"]"#);
+
+    let enabled = validate_task_condition(&condition, &flow_info);
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_git_branches_invalid() {
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.git_info.branch = Some("feature/test".to_string());
+
+    let condition = parse_condition(r#"git_branches = ["master", "release/*"]"#);
+
+    let enabled = validate_task_condition(&condition, &flow_info);
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_git_branches_no_branch() {
+    let condition = parse_condition(r#"git_branches = ["*"]"#);
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
 
     assert!(!enabled);
 }
+
+#[test]
+fn validate_git_clean_valid() {
+    let mut condition = parse_condition("git_clean = true");
+    assert!(validate_git_clean(&condition, Some(true)).is_ok());
+
+    condition = parse_condition("git_clean = false");
+    assert!(validate_git_clean(&condition, Some(false)).is_ok());
+}
+
+#[test]
+fn validate_git_clean_invalid() {
+    let mut condition = parse_condition("git_clean = true");
+    assert!(!validate_git_clean(&condition, Some(false)).is_ok());
+
+    condition = parse_condition("git_clean = false");
+    assert!(!validate_git_clean(&condition, None).is_ok());
+}
+
+#[test]
+fn validate_git_clean_not_defined() {
+    let condition = parse_condition(r#"platforms = ["linux", "mac", "windows"]"#);
+
+    assert!(validate_git_clean(&condition, None).is_ok());
+}
+
+#[test]
+fn validate_git_clean_current_status() {
+    let clean = gitinfo::is_clean().unwrap();
+
+    let mut condition = parse_condition(&format!("git_clean = {}", clean));
+    assert!(validate_task_condition(&condition, &create_flow_info()));
+
+    condition = parse_condition(&format!("git_clean = {}", !clean));
+    assert!(!validate_task_condition(&condition, &create_flow_info()));
+}
+
+#[test]
+fn validate_git_changed_files_not_changed() {
+    env::set_var("CARGO_MAKE_GIT_BASE_REF", "HEAD");
+
+    let condition = parse_condition(r#"git_changed_files = ["bad_directory/**"]"#);
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    env::remove_var("CARGO_MAKE_GIT_BASE_REF");
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_git_changed_files_unknown_ref() {
+    env::set_var("CARGO_MAKE_GIT_BASE_REF", "bad_ref_for_test");
+
+    let condition = parse_condition(r#"git_changed_files = ["bad_directory/**"]"#);
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    env::remove_var("CARGO_MAKE_GIT_BASE_REF");
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_env_contains_valid() {
+    env::set_var("VALIDATE_ENV_CONTAINS_VALID", "first,second,third");
+
+    let condition = parse_condition(r#"env_contains = { VALIDATE_ENV_CONTAINS_VALID = "second" }"#);
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_env_contains_invalid() {
+    env::set_var("VALIDATE_ENV_CONTAINS_INVALID", "first,second,third");
+    env::remove_var("VALIDATE_ENV_CONTAINS_NOT_SET");
+
+    let mut condition =
+        parse_condition(r#"env_contains = { VALIDATE_ENV_CONTAINS_INVALID = "fourth" }"#);
+    assert!(!validate_task_condition(&condition, &create_flow_info()));
+
+    condition = parse_condition(r#"env_contains = { VALIDATE_ENV_CONTAINS_NOT_SET = "" }"#);
+    assert!(!validate_task_condition(&condition, &create_flow_info()));
+}
+
+#[test]
+fn validate_env_regex_valid() {
+    env::set_var("VALIDATE_ENV_REGEX_VALID", "release-1.0");
+
+    let condition = parse_condition(r#"env_regex = { VALIDATE_ENV_REGEX_VALID = "^release-.*" }"#);
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_env_regex_invalid() {
+    env::set_var("VALIDATE_ENV_REGEX_INVALID", "feature-release-1.0");
+    env::remove_var("VALIDATE_ENV_REGEX_NOT_SET");
+
+    let mut condition =
+        parse_condition(r#"env_regex = { VALIDATE_ENV_REGEX_INVALID = "^release-.*" }"#);
+    assert!(!validate_task_condition(&condition, &create_flow_info()));
+
+    condition = parse_condition(r#"env_regex = { VALIDATE_ENV_REGEX_NOT_SET = ".*" }"#);
+    assert!(!validate_task_condition(&condition, &create_flow_info()));
+}
+
+#[test]
+#[should_panic]
+fn validate_env_regex_bad_expression() {
+    env::set_var("VALIDATE_ENV_REGEX_BAD_EXPRESSION", "value");
+
+    let condition =
+        parse_condition(r#"env_regex = { VALIDATE_ENV_REGEX_BAD_EXPRESSION = "(unclosed" }"#);
+
+    validate_task_condition(&condition, &create_flow_info());
+}
+
+#[test]
+fn validate_env_true_valid() {
+    env::set_var("VALIDATE_ENV_TRUE_VALID1", "TRUE");
+    env::set_var("VALIDATE_ENV_TRUE_VALID2", "1");
+    env::set_var("VALIDATE_ENV_TRUE_VALID3", "Yes");
+
+    let condition = parse_condition(
+        r#"env_true = ["VALIDATE_ENV_TRUE_VALID1", "VALIDATE_ENV_TRUE_VALID2", "VALIDATE_ENV_TRUE_VALID3"]"#,
+    );
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_env_true_invalid() {
+    env::set_var("VALIDATE_ENV_TRUE_INVALID", "false");
+    env::remove_var("VALIDATE_ENV_TRUE_NOT_SET");
+
+    let mut condition = parse_condition(r#"env_true = ["VALIDATE_ENV_TRUE_INVALID"]"#);
+    assert!(!validate_task_condition(&condition, &create_flow_info()));
+
+    condition = parse_condition(r#"env_true = ["VALIDATE_ENV_TRUE_NOT_SET"]"#);
+    assert!(!validate_task_condition(&condition, &create_flow_info()));
+}
+
+#[test]
+fn validate_rust_version_condition_pre_release() {
+    let mut rustinfo = RustInfo::new();
+    rustinfo.version = Some("1.30.0-beta.1".to_string());
+
+    let enabled = validate_rust_version_condition(
+        rustinfo,
+        RustVersionCondition {
+            min: Some("1.30.0".to_string()),
+            max: None,
+            equal: None,
+        },
+    );
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_rust_version_condition_build_metadata() {
+    let mut rustinfo = RustInfo::new();
+    rustinfo.version = Some("1.30.0+build".to_string());
+
+    let enabled = validate_rust_version_condition(
+        rustinfo,
+        RustVersionCondition {
+            min: Some("1.30.0".to_string()),
+            max: Some("1.30.0".to_string()),
+            equal: Some("1.30.0".to_string()),
+        },
+    );
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_rust_version_requirement_valid() {
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.rust_info.version = Some("1.35.2".to_string());
+
+    let condition = parse_condition(r#"rust_version = ">=1.30, <1.40""#);
+
+    let enabled = validate_task_condition(&condition, &flow_info);
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_rust_version_requirement_nightly() {
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.rust_info.version = Some("1.31.0-nightly".to_string());
+
+    let condition = parse_condition(r#"rust_version = ">=1.30, <1.40""#);
+
+    let enabled = validate_task_condition(&condition, &flow_info);
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_rust_version_requirement_invalid() {
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.rust_info.version = Some("1.40.0".to_string());
+
+    let condition = parse_condition(r#"rust_version = ">=1.30, <1.40""#);
+
+    let enabled = validate_task_condition(&condition, &flow_info);
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_rust_version_requirement_no_rustinfo() {
+    let condition = parse_condition(r#"rust_version = ">=1.30, <1.40""#);
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+#[should_panic]
+fn validate_rust_version_requirement_bad_expression() {
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.rust_info.version = Some("1.35.2".to_string());
+
+    let condition = parse_condition(r#"rust_version = ">=1.30, <""#);
+
+    validate_task_condition(&condition, &flow_info);
+}
+
+#[test]
+fn validate_rust_version_range_parsed() {
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.rust_info.version = Some("1.35.2".to_string());
+
+    let mut condition = parse_condition(r#"rust_version = { min = "1.30.0", max = "1.40.0" }"#);
+    assert!(validate_task_condition(&condition, &flow_info));
+
+    condition = parse_condition(r#"rust_version = { equal = "1.36.0" }"#);
+    assert!(!validate_task_condition(&condition, &flow_info));
+}
+
+#[test]
+fn validate_nightly_date_condition_in_range() {
+    let condition = NightlyDateCondition {
+        min: Some("2018-09-01".to_string()),
+        max: Some("2018-09-30".to_string()),
+    };
+
+    assert!(validate_nightly_date_condition("2018-09-01", &condition));
+    assert!(validate_nightly_date_condition("2018-09-15", &condition));
+    assert!(validate_nightly_date_condition("2018-09-30", &condition));
+}
+
+#[test]
+fn validate_nightly_date_condition_out_of_range() {
+    let condition = NightlyDateCondition {
+        min: Some("2018-09-01".to_string()),
+        max: Some("2018-09-30".to_string()),
+    };
+
+    assert!(!validate_nightly_date_condition("2018-08-31", &condition));
+    assert!(!validate_nightly_date_condition("2018-10-01", &condition));
+}
+
+#[test]
+fn validate_nightly_date_nightly_channel() {
+    env::set_var("CARGO_MAKE_RUST_COMMIT_DATE", "2018-09-15");
+
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.rust_info.channel = Some(RustChannel::Nightly);
+
+    let mut condition = parse_condition(r#"nightly_date = { min = "2018-09-01" }"#);
+    assert!(validate_task_condition(&condition, &flow_info));
+
+    condition = parse_condition(r#"nightly_date = { max = "2018-09-01" }"#);
+    assert!(!validate_task_condition(&condition, &flow_info));
+}
+
+#[test]
+fn validate_nightly_date_stable_channel() {
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.rust_info.channel = Some(RustChannel::Stable);
+
+    let condition = parse_condition(r#"nightly_date = { min = "2018-09-01" }"#);
+
+    let enabled = validate_task_condition(&condition, &flow_info);
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_nightly_date_unknown_channel() {
+    let condition = parse_condition(r#"nightly_date = { min = "2018-09-01" }"#);
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn check_task_condition_valid() {
+    let condition = parse_condition(r#"platforms = ["linux", "windows", "mac"]"#);
+
+    let result = check_task_condition(&condition, &create_flow_info());
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_task_condition_platform() {
+    let condition = parse_condition(r#"platforms = ["bad_platform"]"#);
+
+    let result = check_task_condition(&condition, &create_flow_info());
+
+    assert_eq!(
+        result.unwrap_err(),
+        SkipReason::Criterion(
+            "platforms".to_string(),
+            format!("current platform: {}", types::get_platform_name())
+        )
+    );
+}
+
+#[test]
+fn check_task_condition_channel() {
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.rust_info.channel = Some(RustChannel::Stable);
+
+    let condition = parse_condition(r#"channels = ["nightly"]"#);
+
+    let result = check_task_condition(&condition, &flow_info);
+
+    assert_eq!(
+        result.unwrap_err(),
+        SkipReason::Criterion(
+            "channels".to_string(),
+            "current channel: stable".to_string()
+        )
+    );
+}
+
+#[test]
+fn check_task_condition_env() {
+    env::set_var("CHECK_TASK_CONDITION_ENV1", "value1");
+    env::set_var("CHECK_TASK_CONDITION_ENV2", "other");
+    env::remove_var("CHECK_TASK_CONDITION_ENV_NOT_SET");
+
+    let mut condition = parse_condition(
+        r#"env = { CHECK_TASK_CONDITION_ENV1 = "value1", CHECK_TASK_CONDITION_ENV2 = "value2" }"#,
+    );
+    assert_eq!(
+        check_task_condition(&condition, &create_flow_info()).unwrap_err(),
+        SkipReason::Criterion(
+            "env".to_string(),
+            "env CHECK_TASK_CONDITION_ENV2 expected value2, found: other".to_string()
+        )
+    );
+
+    condition = parse_condition(r#"env = { CHECK_TASK_CONDITION_ENV_NOT_SET = "value" }"#);
+    assert_eq!(
+        check_task_condition(&condition, &create_flow_info()).unwrap_err(),
+        SkipReason::Criterion(
+            "env".to_string(),
+            "env CHECK_TASK_CONDITION_ENV_NOT_SET expected value, not set".to_string()
+        )
+    );
+}
+
+#[test]
+fn check_task_condition_env_regex() {
+    env::set_var("CHECK_TASK_CONDITION_ENV_REGEX", "feature/test");
+
+    let condition =
+        parse_condition(r#"env_regex = { CHECK_TASK_CONDITION_ENV_REGEX = "^release/" }"#);
+
+    let result = check_task_condition(&condition, &create_flow_info());
+
+    assert_eq!(
+        result.unwrap_err(),
+        SkipReason::Criterion(
+            "env_regex".to_string(),
+            "env CHECK_TASK_CONDITION_ENV_REGEX expected to match ^release/, found: feature/test"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn check_task_condition_env_set() {
+    env::remove_var("CHECK_TASK_CONDITION_ENV_SET");
+
+    let condition = parse_condition(r#"env_set = ["CHECK_TASK_CONDITION_ENV_SET"]"#);
+
+    let result = check_task_condition(&condition, &create_flow_info());
+
+    assert_eq!(
+        result.unwrap_err(),
+        SkipReason::Criterion(
+            "env_set".to_string(),
+            "env CHECK_TASK_CONDITION_ENV_SET is not set".to_string()
+        )
+    );
+}
+
+#[test]
+fn check_task_condition_files_exist() {
+    let condition = parse_condition(r#"files_exist = ["./Cargo.toml", "./bad_file"]"#);
+
+    let result = check_task_condition(&condition, &create_flow_info());
+
+    assert_eq!(
+        result.unwrap_err(),
+        SkipReason::Criterion(
+            "files_exist".to_string(),
+            "file ./bad_file not found".to_string()
+        )
+    );
+}
+
+#[test]
+fn check_task_condition_all_nested() {
+    env::remove_var("CHECK_TASK_CONDITION_ALL_NESTED");
+
+    let condition = parse_condition(
+        r#"all = [ { platforms = ["linux", "windows", "mac"] }, { env_set = ["CHECK_TASK_CONDITION_ALL_NESTED"] } ]"#,
+    );
+
+    let result = check_task_condition(&condition, &create_flow_info());
+
+    assert_eq!(
+        result.unwrap_err(),
+        SkipReason::Criterion(
+            "all".to_string(),
+            "nested env_set condition not met, env CHECK_TASK_CONDITION_ALL_NESTED is not set"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn validate_script_invalid_exit_code() {
+    let mut task = Task::new();
+    task.condition_script = Some(vec!["exit 3".to_string()]);
+    let step = Step {
+        name: "test".to_string(),
+        config: task,
+    };
+
+    let result = validate_script(&step);
+
+    assert_eq!(result.unwrap_err(), SkipReason::ConditionScript(3));
+}
+
+#[test]
+fn validate_condition_criteria_before_script() {
+    let mut task = Task::new();
+    task.condition = Some(parse_condition(r#"platforms = ["bad_platform"]"#));
+    task.condition_script = Some(vec!["exit 3".to_string()]);
+    let step = Step {
+        name: "test".to_string(),
+        config: task,
+    };
+
+    let result = validate_condition(&create_flow_info(), &step);
+
+    match result.unwrap_err() {
+        SkipReason::Criterion(name, _) => assert_eq!(name, "platforms"),
+        _ => panic!("Invalid skip reason."),
+    };
+}
+
+#[test]
+fn validate_ci_valid() {
+    env::set_var("CARGO_MAKE_CI", "TRUE");
+
+    let mut condition = parse_condition("ci = true");
+    assert!(validate_task_condition(&condition, &create_flow_info()));
+
+    env::set_var("CARGO_MAKE_CI", "FALSE");
+
+    condition = parse_condition("ci = false");
+    assert!(validate_task_condition(&condition, &create_flow_info()));
+}
+
+#[test]
+fn validate_ci_invalid() {
+    env::set_var("CARGO_MAKE_CI", "FALSE");
+
+    let condition = parse_condition("ci = true");
+
+    let result = check_task_condition(&condition, &create_flow_info());
+
+    assert_eq!(
+        result.unwrap_err(),
+        SkipReason::Criterion("ci".to_string(), "ci: false".to_string())
+    );
+}
+
+#[test]
+fn validate_ci_vendors_valid() {
+    env::set_var("CARGO_MAKE_CI_VENDOR", "gitlab");
+
+    let condition = parse_condition(r#"ci_vendors = ["travis", "GitLab"]"#);
+
+    let enabled = validate_task_condition(&condition, &create_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_ci_vendors_invalid() {
+    env::set_var("CARGO_MAKE_CI_VENDOR", "appveyor");
+
+    let mut condition = parse_condition(r#"ci_vendors = ["travis", "gitlab"]"#);
+    assert!(!validate_task_condition(&condition, &create_flow_info()));
+
+    env::remove_var("CARGO_MAKE_CI_VENDOR");
+
+    condition = parse_condition(r#"ci_vendors = ["travis", "gitlab"]"#);
+    assert!(!validate_task_condition(&condition, &create_flow_info()));
+}
+
+fn create_crate_flow_info() -> FlowInfo {
+    let mut flow_info = create_flow_info();
+    flow_info.env_info.crate_info =
+        crateinfo::load_from(Path::new("./examples/crate_targets/Cargo.toml"));
+
+    flow_info
+}
+
+#[test]
+fn validate_crate_has_feature_valid() {
+    let condition = parse_condition(r#"crate_has_feature = ["tls", "serde"]"#);
+
+    let enabled = validate_task_condition(&condition, &create_crate_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_crate_has_feature_invalid() {
+    let condition = parse_condition(r#"crate_has_feature = ["tls", "unknown"]"#);
+
+    let result = check_task_condition(&condition, &create_crate_flow_info());
+
+    assert_eq!(
+        result.unwrap_err(),
+        SkipReason::Criterion(
+            "crate_has_feature".to_string(),
+            "feature unknown not defined".to_string()
+        )
+    );
+}
+
+#[test]
+fn validate_crate_has_dependency_valid() {
+    let condition = parse_condition(r#"crate_has_dependency = ["log", "tempfile", "cc"]"#);
+
+    let enabled = validate_task_condition(&condition, &create_crate_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_crate_has_dependency_invalid() {
+    let condition = parse_condition(r#"crate_has_dependency = ["regex"]"#);
+
+    let enabled = validate_task_condition(&condition, &create_crate_flow_info());
+
+    assert!(!enabled);
+}
+
+#[test]
+fn validate_crate_has_targets_valid() {
+    let condition = parse_condition(
+        "crate_has_bin = true\ncrate_has_examples = true\ncrate_has_benches = false",
+    );
+
+    let enabled = validate_task_condition(&condition, &create_crate_flow_info());
+
+    assert!(enabled);
+}
+
+#[test]
+fn validate_crate_has_targets_invalid() {
+    let mut condition = parse_condition("crate_has_benches = true");
+    assert_eq!(
+        check_task_condition(&condition, &create_crate_flow_info()).unwrap_err(),
+        SkipReason::Criterion(
+            "crate_has_benches".to_string(),
+            "crate has benches: false".to_string()
+        )
+    );
+
+    condition = parse_condition("crate_has_examples = false");
+    assert!(!validate_task_condition(
+        &condition,
+        &create_crate_flow_info()
+    ));
+
+    condition = parse_condition("crate_has_bin = true");
+    assert!(!validate_task_condition(&condition, &create_flow_info()));
+}
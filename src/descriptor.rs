@@ -11,6 +11,8 @@
 mod descriptor_test;
 
 use command;
+use environment;
+use environment::crateinfo;
 use indexmap::IndexMap;
 use std::collections::BTreeMap;
 use std::env;
@@ -18,7 +20,71 @@ use std::fs::{canonicalize, File};
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use toml;
-use types::{Config, ConfigSection, EnvValue, ExternalConfig, Task};
+use types::{
+    Config, ConfigSection, CrateInfo, CrateMetadata, EnvFile, EnvValue, Extend, ExternalConfig,
+    Task,
+};
+
+/// The attribute names of the env value tables, which are not expected in profile tables
+static ENV_VALUE_ATTRIBUTES: &'static [&'static str] = &[
+    "value",
+    "script",
+    "secret",
+    "cache",
+    "cache_ttl",
+    "separator",
+    "file",
+    "unset",
+    "path_prepend",
+    "path_append",
+];
+
+/// Validates that the env tables parsed as profiles are not malformed env values.<br>
+/// Untagged env value tables which do not match any env value type (for example a wrong
+/// attribute type) are parsed as profiles, so profile tables holding env value attributes
+/// are rejected and profile tables holding only lower case keys (probably misspelled
+/// attributes) are reported.
+fn validate_env_profiles(env: &IndexMap<String, EnvValue>) {
+    for (key, value) in env.iter() {
+        match *value {
+            EnvValue::Profile(ref profile_env) => {
+                for profile_key in profile_env.keys() {
+                    if ENV_VALUE_ATTRIBUTES.contains(&profile_key.as_str()) {
+                        error!(
+                            "Invalid env value: {}, contains attribute: {} but does not match any env value type (check the attribute types).",
+                            &key, &profile_key
+                        );
+                        panic!(
+                            "Invalid env value: {}, contains attribute: {} but does not match any env value type (check the attribute types).",
+                            &key, &profile_key
+                        );
+                    }
+                }
+
+                let lower_case_only = profile_env.keys().all(|profile_key| {
+                    profile_key
+                        .chars()
+                        .all(|character| character.is_ascii_lowercase() || character == '_')
+                });
+                if lower_case_only {
+                    warn!(
+                        "Env: {} is handled as a profile, check its attribute names if it is an env value.",
+                        &key
+                    );
+                }
+
+                validate_env_profiles(&profile_env);
+            }
+            _ => (),
+        }
+    }
+}
+
+fn validate_external_config_env(external_config: &ExternalConfig) {
+    for env in environment::get_config_env_maps(&external_config) {
+        validate_env_profiles(&env);
+    }
+}
 
 fn merge_env(
     base: &mut IndexMap<String, EnvValue>,
@@ -34,7 +100,19 @@ fn merge_env(
 
     for (key, value) in extended.iter() {
         let key_str = key.to_string();
-        let value_clone = value.clone();
+
+        let value_clone = match (base.get(key), value) {
+            (Some(&EnvValue::Profile(ref base_profile)), &EnvValue::Profile(ref profile)) => {
+                // profile env is merged and not replaced
+                let mut base_profile_clone = base_profile.clone();
+                let mut profile_clone = profile.clone();
+                let merged_profile = merge_env(&mut base_profile_clone, &mut profile_clone);
+
+                EnvValue::Profile(merged_profile)
+            }
+            _ => value.clone(),
+        };
+
         merged.insert(key_str, value_clone);
     }
 
@@ -139,7 +217,52 @@ fn merge_external_configs(config: ExternalConfig, parent_config: ExternalConfig)
     }
 }
 
-fn load_external_descriptor(base_path: &str, file_name: &str, set_env: bool) -> ExternalConfig {
+fn get_parent_directory(file_path: &Path) -> String {
+    let parent_path_buf = match file_path.parent() {
+        Some(parent) => parent.to_path_buf(),
+        None => PathBuf::from("."),
+    };
+
+    match parent_path_buf.to_str() {
+        Some(value) => {
+            if value.len() == 0 {
+                ".".to_string()
+            } else {
+                value.to_string()
+            }
+        }
+        None => ".".to_string(),
+    }
+}
+
+fn load_extended_descriptors(base_path: &str, extend: &Extend) -> ExternalConfig {
+    match *extend {
+        Extend::Path(ref base_file) => load_external_descriptor(base_path, base_file, true, false),
+        Extend::Options(ref options) => {
+            let force = !options.optional.unwrap_or(false);
+
+            load_external_descriptor(base_path, &options.path, force, false)
+        }
+        Extend::List(ref extend_list) => {
+            let mut merged_config = ExternalConfig::new();
+
+            for extend_entry in extend_list.iter() {
+                let extended_config = load_extended_descriptors(base_path, extend_entry);
+
+                merged_config = merge_external_configs(extended_config, merged_config);
+            }
+
+            merged_config
+        }
+    }
+}
+
+fn load_external_descriptor(
+    base_path: &str,
+    file_name: &str,
+    force: bool,
+    set_env: bool,
+) -> ExternalConfig {
     debug!(
         "Loading tasks from file: {} base directory: {}",
         &file_name, &base_path
@@ -168,30 +291,34 @@ fn load_external_descriptor(base_path: &str, file_name: &str, set_env: bool) ->
         let mut external_descriptor = String::new();
         file.read_to_string(&mut external_descriptor).unwrap();
 
-        let file_config: ExternalConfig = match toml::from_str(&external_descriptor) {
+        let mut file_config: ExternalConfig = match toml::from_str(&external_descriptor) {
             Ok(value) => value,
             Err(error) => panic!("Unable to parse external descriptor, {}", error),
         };
+        environment::add_config_secrets(&file_config);
         debug!("Loaded external config: {:#?}", &file_config);
 
+        validate_external_config_env(&file_config);
+
+        let parent_path = get_parent_directory(&file_path);
+        resolve_env_files(&mut file_config, &parent_path);
+
         run_load_script(&file_config);
 
         match file_config.extend {
-            Some(ref base_file) => {
-                let parent_path_buf = Path::new(base_path).join(file_name).join("..");
-                let parent_path = file_path
-                    .parent()
-                    .unwrap_or(&parent_path_buf)
-                    .to_str()
-                    .unwrap_or(".");
+            Some(ref extend) => {
                 debug!("External config parent path: {}", &parent_path);
-                let base_file_config = load_external_descriptor(parent_path, base_file, false);
+
+                let base_file_config = load_extended_descriptors(&parent_path, extend);
 
                 // merge configs
                 merge_external_configs(file_config.clone(), base_file_config)
             }
             None => file_config,
         }
+    } else if force {
+        error!("Descriptor file: {:#?} not found.", &file_path);
+        panic!("Descriptor file: {:#?} not found.", &file_path);
     } else {
         info!("External file not found, skipping.");
 
@@ -199,6 +326,131 @@ fn load_external_descriptor(base_path: &str, file_name: &str, set_env: bool) ->
     }
 }
 
+fn resolve_env_file_path(base_path: &str, path: &str) -> String {
+    let file_path = Path::new(path);
+
+    if file_path.is_absolute() {
+        path.to_string()
+    } else {
+        Path::new(base_path)
+            .join(file_path)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Updates all relative env file paths to be relative to the provided base path.
+fn resolve_env_files(external_config: &mut ExternalConfig, base_path: &str) {
+    match external_config.config {
+        Some(ref mut config_section) => match config_section.env_files {
+            Some(ref mut env_files) => {
+                for env_file in env_files.iter_mut() {
+                    match *env_file {
+                        EnvFile::Path(ref mut path) => {
+                            *path = resolve_env_file_path(base_path, path);
+                        }
+                        EnvFile::Info(ref mut info) => {
+                            info.path = resolve_env_file_path(base_path, &info.path);
+                        }
+                    }
+                }
+            }
+            None => (),
+        },
+        None => (),
+    }
+}
+
+fn parse_env_pairs(values: &Vec<String>) -> IndexMap<String, EnvValue> {
+    let mut env = IndexMap::new();
+
+    for env_pair in values {
+        debug!("Checking env pair: {}", &env_pair);
+        let env_part: Vec<&str> = env_pair.splitn(2, '=').collect();
+
+        if env_part.len() == 2 {
+            env.insert(
+                env_part[0].to_string(),
+                EnvValue::Value(env_part[1].to_string()),
+            );
+        }
+    }
+
+    env
+}
+
+/// Loads all env files defined in the config section which match the current profile.
+fn load_env_files(config_section: &Option<ConfigSection>) -> IndexMap<String, EnvValue> {
+    let mut env = IndexMap::new();
+
+    let env_files = match *config_section {
+        Some(ref config) => match config.env_files {
+            Some(ref env_files) => env_files.clone(),
+            None => vec![],
+        },
+        None => vec![],
+    };
+
+    let profile_name = environment::get_profile_name();
+
+    for env_file in env_files {
+        let (path, profile, optional) = match env_file {
+            EnvFile::Path(path) => (path, None, false),
+            EnvFile::Info(info) => (info.path, info.profile, info.optional.unwrap_or(false)),
+        };
+
+        let load = match profile {
+            Some(ref value) => value == &profile_name,
+            None => true,
+        };
+
+        if load {
+            let file_path = Path::new(&path);
+
+            if file_path.exists() {
+                let mut file_env = parse_env_pairs(&environment::load_env_file(&file_path));
+                env = merge_env(&mut env, &mut file_env);
+            } else if optional {
+                info!("Env file: {} not found, skipping.", &path);
+            } else {
+                error!("Env file: {} not found.", &path);
+                panic!("Env file: {} not found.", &path);
+            }
+        }
+    }
+
+    env
+}
+
+fn get_metadata_config(metadata: &Option<CrateMetadata>) -> ExternalConfig {
+    match *metadata {
+        Some(ref metadata_info) => match metadata_info.cargo_make {
+            Some(ref config) => config.clone(),
+            None => ExternalConfig::new(),
+        },
+        None => ExternalConfig::new(),
+    }
+}
+
+/// Loads the cargo-make configuration defined in the Cargo.toml metadata sections.<br>
+/// The package metadata extends the workspace metadata.
+fn load_cargo_descriptor(crate_info: &CrateInfo) -> ExternalConfig {
+    let workspace_config = match crate_info.workspace {
+        Some(ref workspace) => get_metadata_config(&workspace.metadata),
+        None => ExternalConfig::new(),
+    };
+
+    let package_config = match crate_info.package {
+        Some(ref package) => get_metadata_config(&package.metadata),
+        None => ExternalConfig::new(),
+    };
+
+    let cargo_config = merge_external_configs(package_config, workspace_config);
+    debug!("Loaded Cargo.toml metadata config: {:#?}", &cargo_config);
+
+    cargo_config
+}
+
 fn load_default(stable: bool, experimental: bool) -> Config {
     debug!("Loading base tasks.");
 
@@ -246,7 +498,7 @@ fn load_descriptors(
 ) -> Config {
     let default_config = load_default(stable, experimental);
 
-    let mut external_config: ExternalConfig = load_external_descriptor(".", file_name, true);
+    let mut external_config: ExternalConfig = load_external_descriptor(".", file_name, false, true);
 
     external_config = match env::var("CARGO_MAKE_WORKSPACE_MAKEFILE") {
         Ok(workspace_makefile) => {
@@ -262,6 +514,7 @@ fn load_descriptors(
                                     directory,
                                     workspace_file_name_str,
                                     false,
+                                    false,
                                 );
                                 merge_external_configs(external_config, workspace_config)
                             }
@@ -276,6 +529,11 @@ fn load_descriptors(
         _ => external_config,
     };
 
+    // Cargo.toml metadata config is extended by the makefiles
+    let crate_info = crateinfo::load();
+    let cargo_config = load_cargo_descriptor(&crate_info);
+    external_config = merge_external_configs(external_config, cargo_config);
+
     let mut external_tasks = match external_config.tasks {
         Some(tasks) => tasks,
         None => IndexMap::new(),
@@ -290,21 +548,11 @@ fn load_descriptors(
 
     // merge configs
     let mut all_env = merge_env(&mut default_env, &mut external_env);
+    let mut files_env = load_env_files(&external_config.config);
+    all_env = merge_env(&mut all_env, &mut files_env);
     all_env = match env_map {
         Some(values) => {
-            let mut cli_env = IndexMap::new();
-
-            for env_pair in &values {
-                let env_part: Vec<&str> = env_pair.split('=').collect();
-                debug!("Checking env pair: {}", &env_pair);
-
-                if env_part.len() == 2 {
-                    cli_env.insert(
-                        env_part[0].to_string(),
-                        EnvValue::Value(env_part[1].to_string()),
-                    );
-                }
-            }
+            let mut cli_env = parse_env_pairs(&values);
 
             merge_env(&mut all_env, &mut cli_env)
         }
@@ -316,6 +564,9 @@ fn load_descriptors(
     let mut config_section = default_config.config.clone();
     config_section.extend(&mut external_config.config.unwrap_or(ConfigSection::new()));
 
+    // env files and cli env values are not part of the parsed configs
+    environment::add_env_secrets(&all_env);
+
     let config = Config {
         config: config_section,
         env: all_env,
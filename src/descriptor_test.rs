@@ -1,6 +1,7 @@
 use super::*;
 
 use std::env;
+use types::{EnvFileInfo, ExtendOptions, InstallCrate};
 
 #[test]
 fn merge_env_both_empty() {
@@ -71,6 +72,38 @@ fn merge_env_both_with_values() {
     };
 }
 
+#[test]
+fn merge_env_both_with_profiles() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    let mut profile1 = IndexMap::<String, EnvValue>::new();
+    profile1.insert("profile1".to_string(), EnvValue::Value("1".to_string()));
+    profile1.insert("profile2".to_string(), EnvValue::Value("1".to_string()));
+    map1.insert("production".to_string(), EnvValue::Profile(profile1));
+
+    let mut profile2 = IndexMap::<String, EnvValue>::new();
+    profile2.insert("profile2".to_string(), EnvValue::Value("2".to_string()));
+    profile2.insert("profile3".to_string(), EnvValue::Value("2".to_string()));
+    map2.insert("production".to_string(), EnvValue::Profile(profile2));
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    let profile = match output.get("production").unwrap() {
+        &EnvValue::Profile(ref profile) => profile.clone(),
+        _ => panic!("wrong value type"),
+    };
+    assert_eq!(profile.len(), 3);
+    match profile.get("profile1").unwrap() {
+        &EnvValue::Value(ref value_string) => assert_eq!(value_string, "1"),
+        _ => panic!("wrong value type"),
+    };
+    match profile.get("profile2").unwrap() {
+        &EnvValue::Value(ref value_string) => assert_eq!(value_string, "2"),
+        _ => panic!("wrong value type"),
+    };
+}
+
 #[test]
 fn merge_tasks_both_empty() {
     let mut map1 = IndexMap::<String, Task>::new();
@@ -86,7 +119,7 @@ fn merge_tasks_first_empty() {
     let mut map2 = IndexMap::<String, Task>::new();
 
     let mut task = Task::new();
-    task.install_crate = Some("my crate".to_string());
+    task.install_crate = Some(InstallCrate::Value("my crate".to_string()));
     task.command = Some("test".to_string());
 
     map2.insert("test".to_string(), task);
@@ -116,7 +149,7 @@ fn merge_tasks_second_empty() {
     let mut map2 = IndexMap::<String, Task>::new();
 
     let mut task = Task::new();
-    task.install_crate = Some("my crate".to_string());
+    task.install_crate = Some(InstallCrate::Value("my crate".to_string()));
     task.command = Some("test".to_string());
 
     map1.insert("test".to_string(), task);
@@ -146,7 +179,7 @@ fn merge_tasks_both_with_values() {
     let mut map2 = IndexMap::<String, Task>::new();
 
     let mut task1 = Task::new();
-    task1.install_crate = Some("my crate".to_string());
+    task1.install_crate = Some(InstallCrate::Value("my crate".to_string()));
     task1.command = Some("test".to_string());
 
     map1.insert("test".to_string(), task1);
@@ -199,7 +232,7 @@ fn merge_tasks_extend_task() {
 
     let mut task1 = Task::new();
     task1.disabled = Some(false);
-    task1.install_crate = Some("my crate".to_string());
+    task1.install_crate = Some(InstallCrate::Value("my crate".to_string()));
     task1.command = Some("test1".to_string());
 
     map1.insert("test".to_string(), task1);
@@ -231,7 +264,10 @@ fn merge_tasks_extend_task() {
 
     let task_clone = task.clone();
     assert!(task_clone.disabled.unwrap());
-    assert_eq!(task_clone.install_crate.unwrap(), "my crate");
+    assert_eq!(
+        task_clone.install_crate.unwrap(),
+        InstallCrate::Value("my crate".to_string())
+    );
     assert_eq!(task_clone.command.unwrap(), "test2");
 }
 
@@ -336,7 +372,7 @@ fn load_default_with_experimental() {
 
 #[test]
 fn load_external_descriptor_no_file() {
-    let config = load_external_descriptor(".", "bad_file.toml2", false);
+    let config = load_external_descriptor(".", "bad_file.toml2", false, false);
 
     assert!(config.config.is_none());
     assert!(config.env.is_none());
@@ -345,7 +381,7 @@ fn load_external_descriptor_no_file() {
 
 #[test]
 fn load_external_descriptor_simple_file() {
-    let config = load_external_descriptor(".", "./examples/alias.toml", false);
+    let config = load_external_descriptor(".", "./examples/alias.toml", false, false);
 
     assert!(config.config.is_none());
     assert!(config.env.is_none());
@@ -359,7 +395,7 @@ fn load_external_descriptor_simple_file() {
 
 #[test]
 fn load_external_descriptor_extending_file() {
-    let config = load_external_descriptor(".", "examples/extending.toml", false);
+    let config = load_external_descriptor(".", "examples/extending.toml", false, false);
 
     assert!(config.config.is_some());
     assert!(config.env.is_some());
@@ -379,7 +415,7 @@ fn load_external_descriptor_extending_file() {
 
 #[test]
 fn load_external_descriptor_extending_file_sub_folder() {
-    let config = load_external_descriptor(".", "examples/files/extending.toml", false);
+    let config = load_external_descriptor(".", "examples/files/extending.toml", false, false);
 
     assert!(config.config.is_some());
     assert!(config.env.is_some());
@@ -406,12 +442,92 @@ fn load_external_descriptor_extending_file_sub_folder() {
     assert_eq!(alias.unwrap(), "extended");
 }
 
+#[test]
+#[should_panic]
+fn load_external_descriptor_no_file_force() {
+    load_external_descriptor(".", "bad_file.toml2", true, false);
+}
+
+#[test]
+fn load_external_descriptor_extending_list() {
+    let config = load_external_descriptor(".", "examples/extending_list.toml", false, false);
+
+    assert!(config.config.is_some());
+    assert!(config.tasks.is_some());
+
+    let config_section = config.config.unwrap();
+    assert_eq!(config_section.init_task.unwrap(), "test_init");
+
+    let tasks = config.tasks.unwrap();
+    let mut test_task = tasks.get("A").unwrap();
+    assert!(test_task.dependencies.is_some());
+
+    test_task = tasks.get("D2").unwrap();
+    let mut alias = test_task.alias.clone();
+    assert_eq!(alias.unwrap(), "A");
+
+    test_task = tasks.get("extended2").unwrap();
+    alias = test_task.alias.clone();
+    assert_eq!(alias.unwrap(), "extended");
+}
+
+#[test]
+fn load_extended_descriptors_optional_missing() {
+    let extend = Extend::Options(ExtendOptions {
+        path: "bad_file.toml2".to_string(),
+        optional: Some(true),
+    });
+
+    let config = load_extended_descriptors(".", &extend);
+
+    assert!(config.tasks.is_none());
+}
+
+#[test]
+#[should_panic]
+fn load_extended_descriptors_required_missing() {
+    let extend = Extend::Options(ExtendOptions {
+        path: "bad_file.toml2".to_string(),
+        optional: Some(false),
+    });
+
+    load_extended_descriptors(".", &extend);
+}
+
+#[test]
+fn load_extended_descriptors_list_merged_in_order() {
+    let extend = Extend::List(vec![
+        Extend::Path("./examples/extending.toml".to_string()),
+        Extend::Path("./examples/alias.toml".to_string()),
+    ]);
+
+    let config = load_extended_descriptors(".", &extend);
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.get("extended").is_some());
+    assert!(tasks.get("A").is_some());
+}
+
+#[test]
+fn get_parent_directory_no_directory() {
+    let directory = get_parent_directory(Path::new("Makefile.toml"));
+
+    assert_eq!(directory, ".");
+}
+
+#[test]
+fn get_parent_directory_sub_directory() {
+    let directory = get_parent_directory(Path::new("./examples/files/extending.toml"));
+
+    assert_eq!(directory, "./examples/files");
+}
+
 #[test]
 fn load_external_descriptor_set_env() {
     env::set_var("CARGO_MAKE_MAKEFILE_PATH", "EMPTY");
     assert_eq!(env::var("CARGO_MAKE_MAKEFILE_PATH").unwrap(), "EMPTY");
 
-    load_external_descriptor(".", "./examples/alias.toml", true);
+    load_external_descriptor(".", "./examples/alias.toml", false, true);
 
     assert!(
         env::var("CARGO_MAKE_MAKEFILE_PATH")
@@ -420,6 +536,330 @@ fn load_external_descriptor_set_env() {
     );
 }
 
+#[test]
+fn load_cargo_descriptor_no_metadata() {
+    let crate_info = CrateInfo::new();
+
+    let config = load_cargo_descriptor(&crate_info);
+
+    assert!(config.config.is_some());
+    assert_eq!(config.env.unwrap().len(), 0);
+    assert_eq!(config.tasks.unwrap().len(), 0);
+}
+
+#[test]
+fn load_cargo_descriptor_package_and_workspace_metadata() {
+    let crate_info: CrateInfo = toml::from_str(
+        r#"
+[package]
+name = "test"
+
+[package.metadata.cargo-make.env]
+PACKAGE_ENV = "package"
+OVERRIDE_ENV = "package"
+
+[package.metadata.cargo-make.tasks.package-task]
+command = "echo"
+
+[workspace.metadata.cargo-make.env]
+WORKSPACE_ENV = "workspace"
+OVERRIDE_ENV = "workspace"
+
+[workspace.metadata.cargo-make.tasks.workspace-task]
+command = "echo"
+"#,
+    ).unwrap();
+
+    let config = load_cargo_descriptor(&crate_info);
+
+    let env = config.env.unwrap();
+    assert_eq!(env.len(), 3);
+    match env.get("OVERRIDE_ENV").unwrap() {
+        &EnvValue::Value(ref value_string) => assert_eq!(value_string, "package"),
+        _ => panic!("wrong value type"),
+    };
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.get("package-task").is_some());
+    assert!(tasks.get("workspace-task").is_some());
+}
+
+#[test]
+fn load_descriptors_makefile_extends_cargo_metadata() {
+    let crate_info: CrateInfo = toml::from_str(
+        r#"
+[package.metadata.cargo-make.tasks.D2]
+alias = "A"
+
+[package.metadata.cargo-make.tasks.metadata-task]
+command = "echo"
+"#,
+    ).unwrap();
+
+    let cargo_config = load_cargo_descriptor(&crate_info);
+    let external_config = load_external_descriptor(".", "./examples/alias.toml", false, false);
+    let config = merge_external_configs(external_config, cargo_config);
+
+    let tasks = config.tasks.unwrap();
+    assert_eq!(tasks.get("D2").unwrap().alias.clone().unwrap(), "D");
+    assert!(tasks.get("metadata-task").is_some());
+}
+
+#[test]
+fn parse_env_pairs_value_with_separator() {
+    let env = parse_env_pairs(&vec![
+        "URL=a=b".to_string(),
+        "EMPTY=".to_string(),
+        "INVALID".to_string(),
+    ]);
+
+    assert_eq!(env.len(), 2);
+    match env.get("URL").unwrap() {
+        &EnvValue::Value(ref value) => assert_eq!(value, "a=b"),
+        _ => panic!("Invalid env value type."),
+    };
+    match env.get("EMPTY").unwrap() {
+        &EnvValue::Value(ref value) => assert_eq!(value, ""),
+        _ => panic!("Invalid env value type."),
+    };
+}
+
+#[test]
+fn load_external_descriptor_env_files_resolved() {
+    let config = load_external_descriptor(".", "examples/env_files.toml", false, false);
+
+    let env_files = config.config.unwrap().env_files.unwrap();
+    assert_eq!(env_files.len(), 3);
+    match env_files[0] {
+        EnvFile::Info(ref info) => assert!(Path::new(&info.path).exists()),
+        _ => panic!("Invalid env file type."),
+    };
+}
+
+#[test]
+fn resolve_env_files_absolute_path() {
+    let absolute_path = env::current_dir()
+        .unwrap()
+        .join("test.env")
+        .to_string_lossy()
+        .into_owned();
+    let mut config_section = ConfigSection::new();
+    config_section.env_files = Some(vec![EnvFile::Path(absolute_path.clone())]);
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(config_section);
+
+    resolve_env_files(&mut external_config, "examples");
+
+    let env_files = external_config.config.unwrap().env_files.unwrap();
+    match env_files[0] {
+        EnvFile::Path(ref path) => assert_eq!(path, &absolute_path),
+        _ => panic!("Invalid env file type."),
+    };
+}
+
+#[test]
+fn load_env_files_none() {
+    let env = load_env_files(&None);
+
+    assert!(env.is_empty());
+}
+
+#[test]
+fn load_env_files_profiles_and_optional() {
+    env::set_var("CARGO_MAKE_PROFILE", "development");
+    let config = load_external_descriptor(".", "examples/env_files.toml", false, false);
+
+    let env = load_env_files(&config.config);
+
+    assert_eq!(env.len(), 6);
+    assert!(env.get("DEPLOY_TARGET").is_none());
+    match env.get("DOTENV_URL").unwrap() {
+        &EnvValue::Value(ref value) => assert_eq!(value, "https://example.com/?a=b&c=d"),
+        _ => panic!("Invalid env value type."),
+    };
+}
+
+#[test]
+fn load_env_files_profile_match() {
+    let mut config_section = ConfigSection::new();
+    config_section.env_files = Some(vec![EnvFile::Info(EnvFileInfo {
+        path: "./examples/env/production.env".to_string(),
+        profile: Some(environment::get_profile_name()),
+        optional: None,
+    })]);
+
+    let env = load_env_files(&Some(config_section));
+
+    match env.get("DEPLOY_TARGET").unwrap() {
+        &EnvValue::Value(ref value) => assert_eq!(value, "remote"),
+        _ => panic!("Invalid env value type."),
+    };
+}
+
+#[test]
+#[should_panic]
+fn load_env_files_required_missing() {
+    let mut config_section = ConfigSection::new();
+    config_section.env_files = Some(vec![EnvFile::Path("./examples/env/bad.env".to_string())]);
+
+    load_env_files(&Some(config_section));
+}
+
+#[test]
+fn load_external_descriptor_secret_env() {
+    let config = load_external_descriptor(".", "examples/secrets.toml", false, false);
+
+    let config_section = config.config.unwrap();
+    assert_eq!(config_section.secret_env_patterns.unwrap().len(), 2);
+
+    let env = config.env.unwrap();
+    match env.get("DEPLOY_PASSWORD").unwrap() {
+        &EnvValue::Definition(ref definition) => {
+            assert_eq!(definition.value, "${CI_DEPLOY_PASSWORD}");
+            assert!(definition.secret.unwrap());
+        }
+        _ => panic!("Invalid env value type."),
+    };
+}
+
+fn parse_env(env_toml: &str) -> IndexMap<String, EnvValue> {
+    let config: ExternalConfig = toml::from_str(env_toml).unwrap();
+
+    config.env.unwrap()
+}
+
+#[test]
+fn validate_env_profiles_valid() {
+    let env = parse_env(
+        r#"
+[env]
+SIMPLE = "1"
+SECRET = { value = "x", secret = true }
+
+[env.production]
+SIMPLE = "2"
+"#,
+    );
+
+    validate_env_profiles(&env);
+}
+
+#[test]
+#[should_panic]
+fn validate_env_profiles_invalid_attribute_type() {
+    let env = parse_env(
+        r#"
+[env]
+SECRET = { value = "x", secret = "true" }
+"#,
+    );
+
+    validate_env_profiles(&env);
+}
+
+#[test]
+#[should_panic]
+fn validate_env_profiles_nested_invalid_attribute_type() {
+    let env = parse_env(
+        r#"
+[env.production]
+SECRET = { value = "x", secret = "true" }
+"#,
+    );
+
+    validate_env_profiles(&env);
+}
+
+#[test]
+fn validate_env_profiles_misspelled_attribute() {
+    // only reported as the table is a valid profile
+    let env = parse_env(
+        r#"
+[env]
+SCRIPT = { scirpt = "echo 1" }
+"#,
+    );
+
+    validate_env_profiles(&env);
+}
+
+#[test]
+#[should_panic]
+fn validate_external_config_env_invalid_task_env() {
+    let config: ExternalConfig = toml::from_str(
+        r#"
+[tasks.test.env]
+SECRET = { value = "x", secret = "true" }
+"#,
+    )
+    .unwrap();
+
+    validate_external_config_env(&config);
+}
+
+#[test]
+#[should_panic]
+fn validate_external_config_env_invalid_platform_env() {
+    let config: ExternalConfig = toml::from_str(
+        r#"
+[tasks.test.linux.env]
+SECRET = { value = "x", secret = "true" }
+"#,
+    )
+    .unwrap();
+
+    validate_external_config_env(&config);
+}
+
+#[test]
+fn parse_decimal_env_value() {
+    // decimal values are rejected as their text is not preserved (1.0 would become 1)
+    let config: Result<ExternalConfig, _> = toml::from_str("[env]\nVERSION = 1.0");
+
+    assert!(config.is_err());
+}
+
+#[test]
+fn load_external_descriptor_typed_env() {
+    let config = load_external_descriptor(".", "examples/env.toml", false, false);
+
+    let env = config.env.unwrap();
+    match env.get("SIMPLE").unwrap() {
+        &EnvValue::Value(ref value) => assert_eq!(value, "SIMPLE VALUE"),
+        _ => panic!("Invalid env value type."),
+    };
+    match env.get("BOOLEAN").unwrap() {
+        &EnvValue::Boolean(value) => assert!(value),
+        _ => panic!("Invalid env value type."),
+    };
+    match env.get("NUMBER").unwrap() {
+        &EnvValue::Number(value) => assert_eq!(value, 10),
+        _ => panic!("Invalid env value type."),
+    };
+    match env.get("LIST").unwrap() {
+        &EnvValue::List(ref list) => {
+            assert_eq!(list.value.len(), 2);
+            assert_eq!(list.separator.clone().unwrap(), ";");
+        }
+        _ => panic!("Invalid env value type."),
+    };
+    match env.get("FILE").unwrap() {
+        &EnvValue::File(ref file) => assert_eq!(file.file, "./examples/files/VERSION"),
+        _ => panic!("Invalid env value type."),
+    };
+    match env.get("UNSET_ME").unwrap() {
+        &EnvValue::Unset(ref unset) => assert!(unset.unset),
+        _ => panic!("Invalid env value type."),
+    };
+    match env.get("PATH").unwrap() {
+        &EnvValue::PathList(ref path_list) => {
+            assert_eq!(path_list.path_prepend.clone().unwrap(), vec!["./bin"]);
+            assert!(path_list.path_append.is_none());
+        }
+        _ => panic!("Invalid env value type."),
+    };
+}
+
 #[test]
 fn run_load_script_no_config_section() {
     let external_config = ExternalConfig::new();
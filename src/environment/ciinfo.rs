@@ -0,0 +1,80 @@
+//! # ciinfo
+//!
+//! Loads CI information such as the vendor, pull request and branch name.
+//!
+
+#[cfg(test)]
+#[path = "./ciinfo_test.rs"]
+mod ciinfo_test;
+
+use ci_info::types::Vendor;
+use std::env;
+
+fn get_env(name: &str) -> Option<String> {
+    match env::var(name) {
+        Ok(value) => {
+            if value.len() > 0 {
+                Some(value)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns true if the env var is defined and not equal to the provided value.
+fn is_env_set_and_not(name: &str, value: &str) -> bool {
+    match get_env(name) {
+        Some(current_value) => current_value != value,
+        None => false,
+    }
+}
+
+/// Returns the vendor name in lower case (for example: travis, gitlab).
+pub(crate) fn get_vendor_name(vendor: &Vendor) -> String {
+    format!("{:?}", vendor).to_lowercase()
+}
+
+/// Returns true if the current CI build was triggered by a pull request.
+pub(crate) fn is_pr(vendor: &Vendor) -> bool {
+    match *vendor {
+        Vendor::TRAVIS => is_env_set_and_not("TRAVIS_PULL_REQUEST", "false"),
+        Vendor::CIRCLE => get_env("CIRCLE_PULL_REQUEST").is_some(),
+        Vendor::GITLAB => get_env("CI_MERGE_REQUEST_ID").is_some(),
+        Vendor::APPVEYOR => get_env("APPVEYOR_PULL_REQUEST_NUMBER").is_some(),
+        Vendor::DRONE => get_env("DRONE_PULL_REQUEST").is_some(),
+        Vendor::SEMAPHORE => get_env("PULL_REQUEST_NUMBER").is_some(),
+        Vendor::JENKINS => get_env("CHANGE_ID").is_some(),
+        Vendor::TFS => get_env("SYSTEM_PULLREQUEST_PULLREQUESTID").is_some(),
+        Vendor::BUILDKITE => is_env_set_and_not("BUILDKITE_PULL_REQUEST", "false"),
+        Vendor::BITBUCKET => get_env("BITBUCKET_PR_ID").is_some(),
+        Vendor::CODEBUILD => match get_env("CODEBUILD_WEBHOOK_EVENT") {
+            Some(event) => event.starts_with("PULL_REQUEST"),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns the branch name of the current CI build (the source branch for pull requests).
+pub(crate) fn get_branch_name(vendor: &Vendor) -> Option<String> {
+    match *vendor {
+        Vendor::TRAVIS => get_env("TRAVIS_PULL_REQUEST_BRANCH").or(get_env("TRAVIS_BRANCH")),
+        Vendor::CIRCLE => get_env("CIRCLE_BRANCH"),
+        Vendor::GITLAB => get_env("CI_COMMIT_REF_NAME"),
+        Vendor::APPVEYOR => {
+            get_env("APPVEYOR_PULL_REQUEST_HEAD_REPO_BRANCH").or(get_env("APPVEYOR_REPO_BRANCH"))
+        }
+        Vendor::DRONE => get_env("DRONE_SOURCE_BRANCH").or(get_env("DRONE_BRANCH")),
+        Vendor::SEMAPHORE => get_env("BRANCH_NAME"),
+        Vendor::JENKINS => get_env("CHANGE_BRANCH").or(get_env("BRANCH_NAME")),
+        Vendor::TFS => {
+            get_env("SYSTEM_PULLREQUEST_SOURCEBRANCH").or(get_env("BUILD_SOURCEBRANCHNAME"))
+        }
+        Vendor::BUILDKITE => get_env("BUILDKITE_BRANCH"),
+        Vendor::BITBUCKET => get_env("BITBUCKET_BRANCH"),
+        Vendor::TEAMCITY => get_env("TEAMCITY_BUILD_BRANCH"),
+        _ => None,
+    }
+}
@@ -0,0 +1,58 @@
+use super::*;
+
+#[test]
+fn get_vendor_name_lower_case() {
+    assert_eq!(get_vendor_name(&Vendor::TRAVIS), "travis");
+    assert_eq!(get_vendor_name(&Vendor::GITLAB), "gitlab");
+    assert_eq!(get_vendor_name(&Vendor::APPVEYOR), "appveyor");
+}
+
+#[test]
+fn is_pr_travis() {
+    env::set_var("TRAVIS_PULL_REQUEST", "false");
+    assert!(!is_pr(&Vendor::TRAVIS));
+
+    env::set_var("TRAVIS_PULL_REQUEST", "123");
+    assert!(is_pr(&Vendor::TRAVIS));
+
+    env::remove_var("TRAVIS_PULL_REQUEST");
+    assert!(!is_pr(&Vendor::TRAVIS));
+}
+
+#[test]
+fn is_pr_gitlab() {
+    env::remove_var("CI_MERGE_REQUEST_ID");
+    assert!(!is_pr(&Vendor::GITLAB));
+
+    env::set_var("CI_MERGE_REQUEST_ID", "5");
+    assert!(is_pr(&Vendor::GITLAB));
+
+    env::remove_var("CI_MERGE_REQUEST_ID");
+}
+
+#[test]
+fn is_pr_unsupported_vendor() {
+    assert!(!is_pr(&Vendor::GOCD));
+}
+
+#[test]
+fn get_branch_name_travis() {
+    env::set_var("TRAVIS_BRANCH", "master");
+    env::set_var("TRAVIS_PULL_REQUEST_BRANCH", "");
+    assert_eq!(get_branch_name(&Vendor::TRAVIS).unwrap(), "master");
+
+    env::set_var("TRAVIS_PULL_REQUEST_BRANCH", "feature");
+    assert_eq!(get_branch_name(&Vendor::TRAVIS).unwrap(), "feature");
+
+    env::remove_var("TRAVIS_BRANCH");
+    env::remove_var("TRAVIS_PULL_REQUEST_BRANCH");
+}
+
+#[test]
+fn get_branch_name_not_found() {
+    env::remove_var("CIRCLE_BRANCH");
+
+    let branch = get_branch_name(&Vendor::CIRCLE);
+
+    assert!(branch.is_none());
+}
@@ -7,12 +7,14 @@
 #[path = "./crateinfo_test.rs"]
 mod crateinfo_test;
 
+use environment;
 use glob::glob;
+use indexmap::IndexMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 use toml;
-use types::{CrateDependency, CrateInfo};
+use types::{CrateDependency, CrateInfo, CrateMetadata, CrateTarget};
 
 fn expand_glob_members(glob_member: &str) -> Vec<String> {
     match glob(glob_member) {
@@ -149,11 +151,115 @@ fn load_workspace_members(crate_info: &mut CrateInfo) {
     }
 }
 
-/// Loads the crate info based on the Cargo.toml found in the current working directory.
-pub(crate) fn load() -> CrateInfo {
-    // load crate info
-    let file_path = Path::new("Cargo.toml");
+/// Returns all target files found in the provided directory (*.rs and */main.rs).
+fn discover_targets(base_path: &Path, directory: &str) -> Vec<CrateTarget> {
+    let mut targets = vec![];
+
+    for pattern in &["*.rs", "*/main.rs"] {
+        let glob_pattern = base_path.join(directory).join(pattern);
+
+        for file_path in expand_glob_members(&glob_pattern.to_string_lossy()) {
+            let path = Path::new(&file_path);
+            let name_path = if pattern == &"*.rs" {
+                path.file_stem()
+            } else {
+                path.parent().and_then(|parent| parent.file_name())
+            };
+
+            match name_path {
+                Some(name) => targets.push(CrateTarget {
+                    name: Some(name.to_string_lossy().into_owned()),
+                    path: Some(file_path.clone()),
+                }),
+                None => (),
+            };
+        }
+    }
+
+    targets
+}
+
+/// Adds the auto discovered targets which are not explicitly defined in the Cargo.toml.
+fn add_targets(targets: &mut Option<Vec<CrateTarget>>, discovered_targets: Vec<CrateTarget>) {
+    if !discovered_targets.is_empty() {
+        let mut all_targets = targets.take().unwrap_or(vec![]);
+
+        for target in discovered_targets {
+            if !all_targets
+                .iter()
+                .any(|existing| existing.name == target.name)
+            {
+                all_targets.push(target);
+            }
+        }
+
+        *targets = Some(all_targets);
+    }
+}
+
+fn load_targets(crate_info: &mut CrateInfo, base_path: &Path) {
+    let package_name = match crate_info.package {
+        Some(ref package) => package.name.clone(),
+        None => return, // targets are only defined for packages
+    };
+
+    let mut bins = vec![];
+    let main_file = base_path.join("src").join("main.rs");
+    if main_file.exists() {
+        bins.push(CrateTarget {
+            name: package_name,
+            path: Some(main_file.to_string_lossy().replace("\\", "/")),
+        });
+    }
+    bins.append(&mut discover_targets(&base_path, "src/bin"));
+
+    add_targets(&mut crate_info.bin, bins);
+    add_targets(
+        &mut crate_info.example,
+        discover_targets(&base_path, "examples"),
+    );
+    add_targets(
+        &mut crate_info.bench,
+        discover_targets(&base_path, "benches"),
+    );
+}
 
+fn has_dependency_in(
+    dependencies: &Option<IndexMap<String, CrateDependency>>,
+    dependency: &str,
+) -> bool {
+    match *dependencies {
+        Some(ref dependencies) => dependencies.contains_key(dependency),
+        None => false,
+    }
+}
+
+/// Returns true if the crate defines the provided dependency (including dev and build dependencies).
+pub(crate) fn has_dependency(crate_info: &CrateInfo, dependency: &str) -> bool {
+    has_dependency_in(&crate_info.dependencies, &dependency)
+        || has_dependency_in(&crate_info.dev_dependencies, &dependency)
+        || has_dependency_in(&crate_info.build_dependencies, &dependency)
+}
+
+/// Returns true if the crate defines the provided feature (including optional dependencies).
+pub(crate) fn has_feature(crate_info: &CrateInfo, feature: &str) -> bool {
+    let defined = match crate_info.features {
+        Some(ref features) => features.contains_key(feature),
+        None => false,
+    };
+
+    defined
+        || match crate_info.dependencies {
+            Some(ref dependencies) => match dependencies.get(feature) {
+                Some(&CrateDependency::Info(ref info)) => info.optional.unwrap_or(false),
+                _ => false,
+            },
+            None => false,
+        }
+}
+
+/// Loads the crate info from the provided Cargo.toml file.
+pub(crate) fn load_from(file_path: &Path) -> CrateInfo {
     if file_path.exists() {
         debug!("Opening file: {:#?}", &file_path);
         let mut file = match File::open(&file_path) {
@@ -170,6 +276,13 @@ pub(crate) fn load() -> CrateInfo {
 
         load_workspace_members(&mut crate_info);
 
+        let base_path = match file_path.parent() {
+            Some(directory) => directory,
+            None => Path::new("."),
+        };
+        load_targets(&mut crate_info, &base_path);
+
+        add_metadata_secrets(&crate_info);
         debug!("Loaded Cargo.toml: {:#?}", &crate_info);
 
         crate_info
@@ -177,3 +290,32 @@ pub(crate) fn load() -> CrateInfo {
         CrateInfo::new()
     }
 }
+
+/// Registers the secrets defined in the cargo-make metadata config sections.
+fn add_metadata_secrets(crate_info: &CrateInfo) {
+    let workspace_metadata = match crate_info.workspace {
+        Some(ref workspace) => &workspace.metadata,
+        None => &None,
+    };
+    let package_metadata = match crate_info.package {
+        Some(ref package) => &package.metadata,
+        None => &None,
+    };
+
+    for metadata in [workspace_metadata, package_metadata].iter() {
+        match **metadata {
+            Some(CrateMetadata {
+                cargo_make: Some(ref config),
+            }) => environment::add_config_secrets(&config),
+            _ => (),
+        };
+    }
+}
+
+/// Loads the crate info based on the Cargo.toml found in the current working directory.
+pub(crate) fn load() -> CrateInfo {
+    // load crate info
+    let file_path = Path::new("Cargo.toml");
+
+    load_from(&file_path)
+}
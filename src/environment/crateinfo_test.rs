@@ -13,6 +13,31 @@ fn crate_info_load() {
     assert_eq!(package.name.unwrap(), "cargo-make");
 }
 
+#[test]
+fn crate_info_load_from_with_metadata() {
+    let crate_info = load_from(Path::new("./examples/cargo_metadata/Cargo.toml"));
+
+    let package = crate_info.package.unwrap();
+    assert_eq!(package.name.unwrap(), "cargo_metadata");
+    let package_config = package.metadata.unwrap().cargo_make.unwrap();
+    assert_eq!(package_config.config.unwrap().init_task.unwrap(), "metadata-init");
+    assert_eq!(package_config.env.unwrap().len(), 2);
+    assert!(package_config.tasks.unwrap().get("metadata-init").is_some());
+
+    let workspace = crate_info.workspace.unwrap();
+    let workspace_config = workspace.metadata.unwrap().cargo_make.unwrap();
+    assert_eq!(workspace_config.env.unwrap().len(), 2);
+    assert!(workspace_config.tasks.unwrap().get("metadata-workspace").is_some());
+}
+
+#[test]
+fn crate_info_load_from_not_exists() {
+    let crate_info = load_from(Path::new("./examples/Cargo.toml"));
+
+    assert!(crate_info.package.is_none());
+    assert!(crate_info.workspace.is_none());
+}
+
 #[test]
 fn add_members_workspace_none_members_empty() {
     let mut crate_info = CrateInfo::new();
@@ -151,7 +176,10 @@ fn get_members_from_dependencies_no_paths() {
     );
     dependencies.insert(
         "test3".to_string(),
-        CrateDependency::Info(CrateDependencyInfo { path: None }),
+        CrateDependency::Info(CrateDependencyInfo {
+            path: None,
+            optional: None,
+        }),
     );
 
     let mut crate_info = CrateInfo::new();
@@ -177,7 +205,10 @@ fn get_members_from_dependencies_no_workspace_paths() {
     );
     dependencies.insert(
         "test3".to_string(),
-        CrateDependency::Info(CrateDependencyInfo { path: None }),
+        CrateDependency::Info(CrateDependencyInfo {
+            path: None,
+            optional: None,
+        }),
     );
 
     let mut crate_info = CrateInfo::new();
@@ -202,18 +233,21 @@ fn get_members_from_dependencies_workspace_paths() {
         "test3".to_string(),
         CrateDependency::Info(CrateDependencyInfo {
             path: Some("somepath".to_string()),
+            optional: None,
         }),
     );
     dependencies.insert(
         "valid1".to_string(),
         CrateDependency::Info(CrateDependencyInfo {
             path: Some("./member1".to_string()),
+            optional: None,
         }),
     );
     dependencies.insert(
         "valid2".to_string(),
         CrateDependency::Info(CrateDependencyInfo {
             path: Some("./member2".to_string()),
+            optional: None,
         }),
     );
 
@@ -504,24 +538,28 @@ fn load_workspace_members_mixed() {
         "test3".to_string(),
         CrateDependency::Info(CrateDependencyInfo {
             path: Some("somepath".to_string()),
+            optional: None,
         }),
     );
     dependencies.insert(
         "valid1".to_string(),
         CrateDependency::Info(CrateDependencyInfo {
             path: Some("./path1".to_string()),
+            optional: None,
         }),
     );
     dependencies.insert(
         "valid2".to_string(),
         CrateDependency::Info(CrateDependencyInfo {
             path: Some("./path2".to_string()),
+            optional: None,
         }),
     );
     dependencies.insert(
         "valid3".to_string(),
         CrateDependency::Info(CrateDependencyInfo {
             path: Some("./member1".to_string()),
+            optional: None,
         }),
     );
     crate_info.dependencies = Some(dependencies);
@@ -586,3 +624,87 @@ fn load_workspace_members_mixed() {
     );
     assert_eq!(members.len(), 7);
 }
+
+#[test]
+fn crate_info_load_from_with_targets() {
+    let crate_info = load_from(Path::new("./examples/crate_targets/Cargo.toml"));
+
+    let bins: Vec<String> = crate_info
+        .bin
+        .unwrap()
+        .iter()
+        .map(|target| target.name.clone().unwrap())
+        .collect();
+    assert_eq!(bins, vec!["custom", "crate_targets", "helper", "tool"]);
+
+    let examples = crate_info.example.unwrap();
+    assert_eq!(examples.len(), 2);
+    assert_eq!(examples[0].name.clone().unwrap(), "demo");
+    assert_eq!(examples[1].name.clone().unwrap(), "other");
+
+    assert!(crate_info.bench.is_none());
+}
+
+#[test]
+fn crate_info_load_from_with_dependencies_and_features() {
+    let crate_info = load_from(Path::new("./examples/crate_targets/Cargo.toml"));
+
+    assert_eq!(crate_info.features.clone().unwrap().len(), 2);
+    assert_eq!(crate_info.dev_dependencies.clone().unwrap().len(), 1);
+    assert_eq!(crate_info.build_dependencies.clone().unwrap().len(), 1);
+}
+
+#[test]
+fn has_dependency_all_sections() {
+    let crate_info = load_from(Path::new("./examples/crate_targets/Cargo.toml"));
+
+    assert!(has_dependency(&crate_info, "log"));
+    assert!(has_dependency(&crate_info, "tempfile"));
+    assert!(has_dependency(&crate_info, "cc"));
+    assert!(!has_dependency(&crate_info, "regex"));
+}
+
+#[test]
+fn has_feature_defined_and_optional() {
+    let crate_info = load_from(Path::new("./examples/crate_targets/Cargo.toml"));
+
+    assert!(has_feature(&crate_info, "tls"));
+    assert!(has_feature(&crate_info, "serde"));
+    assert!(!has_feature(&crate_info, "log"));
+    assert!(!has_feature(&crate_info, "unknown"));
+}
+
+#[test]
+fn has_feature_empty() {
+    let crate_info = CrateInfo::new();
+
+    assert!(!has_feature(&crate_info, "tls"));
+    assert!(!has_dependency(&crate_info, "log"));
+}
+
+#[test]
+fn add_targets_no_duplicates() {
+    let mut targets = Some(vec![CrateTarget {
+        name: Some("first".to_string()),
+        path: Some("src/first.rs".to_string()),
+    }]);
+
+    add_targets(
+        &mut targets,
+        vec![
+            CrateTarget {
+                name: Some("first".to_string()),
+                path: Some("examples/first.rs".to_string()),
+            },
+            CrateTarget {
+                name: Some("second".to_string()),
+                path: Some("examples/second.rs".to_string()),
+            },
+        ],
+    );
+
+    let targets = targets.unwrap();
+    assert_eq!(targets.len(), 2);
+    assert_eq!(targets[0].path.clone().unwrap(), "src/first.rs");
+    assert_eq!(targets[1].name.clone().unwrap(), "second");
+}
@@ -0,0 +1,162 @@
+//! # envcache
+//!
+//! Caches env script outputs in the project state directory.<br>
+//! The cached values are mapped by the hash of the script text and working directory.
+//!
+
+#[cfg(test)]
+#[path = "./envcache_test.rs"]
+mod envcache_test;
+
+use std::env;
+use std::fs::{create_dir_all, File};
+use std::io::prelude::*;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use storage;
+use toml;
+use types::{EnvCache, EnvCacheEntry};
+
+static CACHE_FILE: &'static str = "env_cache.toml";
+
+fn get_now_as_seconds() -> u64 {
+    let now = SystemTime::now();
+    match now.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs(),
+        _ => 0,
+    }
+}
+
+fn get_cache_file() -> PathBuf {
+    storage::get_project_state_directory().join(CACHE_FILE)
+}
+
+/// Returns the cache key of the script, the working directory is part of the key as the same
+/// script may return different values for different crates sharing the target directory.
+fn get_script_hash(script: &Vec<String>, working_directory: &str) -> String {
+    let mut values = vec![working_directory];
+    values.extend(script.iter().map(|line| line.as_str()));
+
+    storage::get_stable_hash(&values)
+}
+
+fn get_working_directory() -> String {
+    match env::current_dir() {
+        Ok(directory) => directory.to_string_lossy().into_owned(),
+        Err(_) => "".to_string(),
+    }
+}
+
+fn load_from_file(file_path: &Path) -> EnvCache {
+    if file_path.exists() {
+        match File::open(&file_path) {
+            Ok(mut file) => {
+                let mut cache_str = String::new();
+                file.read_to_string(&mut cache_str).unwrap();
+
+                match toml::from_str(&cache_str) {
+                    Ok(value) => value,
+                    Err(error) => {
+                        info!("Unable to parse env cache file, {}", error);
+                        EnvCache::new()
+                    }
+                }
+            }
+            Err(error) => {
+                info!(
+                    "Unable to open env cache file: {:#?} error: {}",
+                    &file_path, error
+                );
+                EnvCache::new()
+            }
+        }
+    } else {
+        EnvCache::new()
+    }
+}
+
+fn store_to_file(file_path: &Path, cache_data: &EnvCache) {
+    match file_path.parent() {
+        Some(directory) => {
+            if !directory.exists() {
+                match create_dir_all(&directory) {
+                    Err(error) => info!(
+                        "Unable to create env cache directory: {:#?} error: {}",
+                        &directory, error
+                    ),
+                    _ => (),
+                };
+            }
+        }
+        None => (),
+    };
+
+    match toml::to_string_pretty(cache_data) {
+        Ok(toml_str) => match File::create(&file_path) {
+            Ok(mut file) => match file.write_all(toml_str.as_bytes()) {
+                Err(error) => info!(
+                    "Error while writing to env cache file: {:#?}, error: {:#?}",
+                    &file_path, error
+                ),
+                _ => (),
+            },
+            Err(error) => info!(
+                "Error while creating env cache file: {:#?}, error: {:#?}",
+                &file_path, error
+            ),
+        },
+        Err(error) => info!(
+            "Error during serialization of env cache, file: {:#?}, error: {:#?}",
+            &file_path, error
+        ),
+    };
+}
+
+fn get_from_file(file_path: &Path, script: &Vec<String>, ttl: Option<u64>) -> Option<String> {
+    let cache_data = load_from_file(&file_path);
+
+    match cache_data
+        .values
+        .get(&get_script_hash(&script, &get_working_directory()))
+    {
+        Some(entry) => {
+            let valid = match ttl {
+                Some(seconds) => entry.created + seconds > get_now_as_seconds(),
+                None => true,
+            };
+
+            if valid {
+                Some(entry.value.clone())
+            } else {
+                debug!("Env cache entry expired.");
+                None
+            }
+        }
+        None => None,
+    }
+}
+
+fn set_in_file(file_path: &Path, script: &Vec<String>, value: &str) {
+    let mut cache_data = load_from_file(&file_path);
+
+    cache_data.values.insert(
+        get_script_hash(&script, &get_working_directory()),
+        EnvCacheEntry {
+            value: value.to_string(),
+            created: get_now_as_seconds(),
+        },
+    );
+
+    store_to_file(&file_path, &cache_data);
+}
+
+/// Returns the cached output of the provided script if it exists and did not expire.
+pub(crate) fn get(script: &Vec<String>, ttl: Option<u64>) -> Option<String> {
+    get_from_file(&get_cache_file(), &script, ttl)
+}
+
+/// Caches the output of the provided script.
+pub(crate) fn set(script: &Vec<String>, value: &str) {
+    set_in_file(&get_cache_file(), &script, &value);
+}
@@ -0,0 +1,88 @@
+use super::*;
+use std::env;
+use std::fs::remove_file;
+
+fn get_test_file(name: &str) -> PathBuf {
+    let directory = env::current_dir()
+        .unwrap()
+        .join("target/_cargo_make_temp/envcache");
+    let file_path = directory.join(name);
+
+    if file_path.exists() {
+        remove_file(&file_path).unwrap();
+    }
+
+    file_path
+}
+
+#[test]
+fn get_script_hash_same_script() {
+    let hash1 = get_script_hash(&vec!["echo 1".to_string(), "echo 2".to_string()], "/a");
+    let hash2 = get_script_hash(&vec!["echo 1".to_string(), "echo 2".to_string()], "/a");
+
+    assert_eq!(hash1, hash2);
+}
+
+#[test]
+fn get_script_hash_different_script() {
+    let hash1 = get_script_hash(&vec!["echo 1".to_string(), "echo 2".to_string()], "/a");
+    let hash2 = get_script_hash(&vec!["echo 1".to_string(), "echo 3".to_string()], "/a");
+
+    assert_ne!(hash1, hash2);
+}
+
+#[test]
+fn get_script_hash_different_working_directory() {
+    let hash1 = get_script_hash(&vec!["cat Cargo.toml".to_string()], "/workspace/member1");
+    let hash2 = get_script_hash(&vec!["cat Cargo.toml".to_string()], "/workspace/member2");
+
+    assert_ne!(hash1, hash2);
+}
+
+#[test]
+fn load_from_file_not_exists() {
+    let cache_data = load_from_file(&get_test_file("not_exists.toml"));
+
+    assert!(cache_data.values.is_empty());
+}
+
+#[test]
+fn get_from_file_not_cached() {
+    let file_path = get_test_file("not_cached.toml");
+
+    let value = get_from_file(&file_path, &vec!["echo 1".to_string()], None);
+
+    assert!(value.is_none());
+}
+
+#[test]
+fn set_and_get_from_file() {
+    let file_path = get_test_file("set_and_get.toml");
+    let script = vec!["echo 1".to_string()];
+
+    set_in_file(&file_path, &script, "1");
+    set_in_file(&file_path, &vec!["echo 2".to_string()], "2");
+
+    assert_eq!(get_from_file(&file_path, &script, None).unwrap(), "1");
+    assert_eq!(get_from_file(&file_path, &script, Some(3600)).unwrap(), "1");
+    assert_eq!(load_from_file(&file_path).values.len(), 2);
+}
+
+#[test]
+fn get_from_file_expired() {
+    let file_path = get_test_file("expired.toml");
+    let script = vec!["echo 1".to_string()];
+
+    let mut cache_data = EnvCache::new();
+    cache_data.values.insert(
+        get_script_hash(&script, &get_working_directory()),
+        EnvCacheEntry {
+            value: "1".to_string(),
+            created: get_now_as_seconds() - 100,
+        },
+    );
+    store_to_file(&file_path, &cache_data);
+
+    assert!(get_from_file(&file_path, &script, Some(10)).is_none());
+    assert_eq!(get_from_file(&file_path, &script, Some(1000)).unwrap(), "1");
+}
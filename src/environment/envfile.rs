@@ -0,0 +1,165 @@
+//! # envfile
+//!
+//! Parses env files using the dotenv syntax.<br>
+//! Supports comments, export prefix, single/double quoted values, escapes and multiline values.
+//!
+
+#[cfg(test)]
+#[path = "./envfile_test.rs"]
+mod envfile_test;
+
+fn skip_to_line_end(chars: &[char], start: usize) -> usize {
+    let mut index = start;
+    while index < chars.len() && chars[index] != '\n' {
+        index = index + 1;
+    }
+
+    index
+}
+
+fn skip_inline_whitespace(chars: &[char], start: usize) -> usize {
+    let mut index = start;
+    while index < chars.len() && (chars[index] == ' ' || chars[index] == '\t') {
+        index = index + 1;
+    }
+
+    index
+}
+
+/// Reads a double quoted value which supports escapes and may span multiple lines.
+fn read_double_quoted(chars: &[char], start: usize) -> (String, usize) {
+    let mut value = String::new();
+    let mut index = start;
+
+    while index < chars.len() {
+        let current = chars[index];
+
+        if current == '"' {
+            return (value, index + 1);
+        } else if current == '\\' && index + 1 < chars.len() {
+            index = index + 1;
+
+            match chars[index] {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                '"' => value.push('"'),
+                '\\' => value.push('\\'),
+                '$' => {
+                    // prevent env expansion of escaped references
+                    if chars.get(index + 1) == Some(&'{') {
+                        value.push('$');
+                    }
+                    value.push('$');
+                }
+                other => {
+                    value.push('\\');
+                    value.push(other);
+                }
+            };
+        } else {
+            value.push(current);
+        }
+
+        index = index + 1;
+    }
+
+    (value, index)
+}
+
+/// Reads a single quoted value which is taken as is (no escapes and no env expansion).
+fn read_single_quoted(chars: &[char], start: usize) -> (String, usize) {
+    let mut value = String::new();
+    let mut index = start;
+
+    while index < chars.len() {
+        let current = chars[index];
+
+        if current == '\'' {
+            return (value, index + 1);
+        }
+
+        // prevent env expansion of literal values
+        if current == '$' && chars.get(index + 1) == Some(&'{') {
+            value.push('$');
+        }
+        value.push(current);
+
+        index = index + 1;
+    }
+
+    (value, index)
+}
+
+/// Reads an unquoted value until the end of the line or an inline comment.
+fn read_unquoted(chars: &[char], start: usize) -> (String, usize) {
+    let end = skip_to_line_end(chars, start);
+
+    let mut value = String::new();
+    for index in start..end {
+        let current = chars[index];
+
+        if current == '#' && index > start && chars[index - 1].is_whitespace() {
+            break;
+        }
+
+        value.push(current);
+    }
+
+    (value.trim().to_string(), end)
+}
+
+/// Parses the env file content and returns a list of KEY=VALUE entries.
+pub(crate) fn parse(content: &str) -> Vec<String> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut env = vec![];
+
+    let mut index = 0;
+    while index < chars.len() {
+        let line_start = index;
+        let line_end = skip_to_line_end(&chars, index);
+        let line: String = chars[line_start..line_end].iter().collect();
+        let trimmed_line = line.trim();
+
+        if trimmed_line.len() == 0 || trimmed_line.starts_with("#") {
+            index = line_end + 1;
+            continue;
+        }
+
+        let mut key_start = skip_inline_whitespace(&chars, line_start);
+        if trimmed_line.starts_with("export ") || trimmed_line.starts_with("export\t") {
+            key_start = skip_inline_whitespace(&chars, key_start + 6);
+        }
+
+        let separator = match chars[key_start..line_end].iter().position(|&c| c == '=') {
+            Some(offset) => key_start + offset,
+            None => {
+                debug!("Skipping invalid env file line: {}", &line);
+                index = line_end + 1;
+                continue;
+            }
+        };
+
+        let key: String = chars[key_start..separator].iter().collect();
+        let key = key.trim().to_string();
+
+        let value_start = skip_inline_whitespace(&chars, separator + 1);
+        let (value, value_end) = match chars.get(value_start) {
+            Some(&'"') => read_double_quoted(&chars, value_start + 1),
+            Some(&'\'') => read_single_quoted(&chars, value_start + 1),
+            _ => read_unquoted(&chars, value_start),
+        };
+
+        if key.len() > 0 {
+            let mut entry = key;
+            entry.push('=');
+            entry.push_str(&value);
+            env.push(entry);
+        }
+
+        // ignore anything after the value (such as comments)
+        index = skip_to_line_end(&chars, value_end) + 1;
+    }
+
+    env
+}
@@ -0,0 +1,134 @@
+use super::*;
+
+#[test]
+fn parse_empty() {
+    let output = parse("");
+
+    assert!(output.is_empty());
+}
+
+#[test]
+fn parse_comments_and_empty_lines() {
+    let output = parse("# comment\n\n   # indented comment\n\nKEY=value\n");
+
+    assert_eq!(output, vec!["KEY=value"]);
+}
+
+#[test]
+fn parse_simple() {
+    let output = parse("KEY1=value1\nKEY2=value2");
+
+    assert_eq!(output, vec!["KEY1=value1", "KEY2=value2"]);
+}
+
+#[test]
+fn parse_windows_line_endings() {
+    let output = parse("KEY1=value1\r\nKEY2=value2\r\n");
+
+    assert_eq!(output, vec!["KEY1=value1", "KEY2=value2"]);
+}
+
+#[test]
+fn parse_value_with_equals() {
+    let output = parse("URL=http://test.com/?a=b&c=d");
+
+    assert_eq!(output, vec!["URL=http://test.com/?a=b&c=d"]);
+}
+
+#[test]
+fn parse_empty_value() {
+    let output = parse("KEY=\nKEY2=\"\"");
+
+    assert_eq!(output, vec!["KEY=", "KEY2="]);
+}
+
+#[test]
+fn parse_spaces_around_separator() {
+    let output = parse("  KEY  =  some value  ");
+
+    assert_eq!(output, vec!["KEY=some value"]);
+}
+
+#[test]
+fn parse_export_prefix() {
+    let output = parse("export KEY=value\nexport\tKEY2=value2\nexported=value3");
+
+    assert_eq!(output, vec!["KEY=value", "KEY2=value2", "exported=value3"]);
+}
+
+#[test]
+fn parse_inline_comment() {
+    let output = parse("KEY=value # comment\nKEY2=value#not comment");
+
+    assert_eq!(output, vec!["KEY=value", "KEY2=value#not comment"]);
+}
+
+#[test]
+fn parse_double_quoted() {
+    let output = parse("KEY=\"  value # not comment  \" # comment");
+
+    assert_eq!(output, vec!["KEY=  value # not comment  "]);
+}
+
+#[test]
+fn parse_double_quoted_escapes() {
+    let output = parse(r#"KEY="a\nb\tc\"d\\e\$f""#);
+
+    assert_eq!(output, vec!["KEY=a\nb\tc\"d\\e$f"]);
+}
+
+#[test]
+fn parse_double_quoted_escaped_reference() {
+    let output = parse(r#"KEY="\${OTHER}""#);
+
+    assert_eq!(output, vec!["KEY=$${OTHER}"]);
+}
+
+#[test]
+fn parse_double_quoted_keeps_references() {
+    let output = parse("KEY=\"value: ${OTHER}\"");
+
+    assert_eq!(output, vec!["KEY=value: ${OTHER}"]);
+}
+
+#[test]
+fn parse_double_quoted_multiline() {
+    let output = parse("KEY=\"line1\nline2\"\nKEY2=value2");
+
+    assert_eq!(output, vec!["KEY=line1\nline2", "KEY2=value2"]);
+}
+
+#[test]
+fn parse_single_quoted() {
+    let output = parse(r#"KEY='a\nb "c" # d'"#);
+
+    assert_eq!(output, vec![r#"KEY=a\nb "c" # d"#]);
+}
+
+#[test]
+fn parse_single_quoted_escapes_references() {
+    let output = parse("KEY='value: ${OTHER}'");
+
+    assert_eq!(output, vec!["KEY=value: $${OTHER}"]);
+}
+
+#[test]
+fn parse_single_quoted_multiline() {
+    let output = parse("KEY='line1\nline2'\nKEY2=value2");
+
+    assert_eq!(output, vec!["KEY=line1\nline2", "KEY2=value2"]);
+}
+
+#[test]
+fn parse_unterminated_quote() {
+    let output = parse("KEY=\"value\nKEY2=value2");
+
+    assert_eq!(output, vec!["KEY=value\nKEY2=value2"]);
+}
+
+#[test]
+fn parse_invalid_lines() {
+    let output = parse("NO_SEPARATOR\n=no key\nKEY=value");
+
+    assert_eq!(output, vec!["KEY=value"]);
+}
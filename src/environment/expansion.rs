@@ -0,0 +1,201 @@
+//! # expansion
+//!
+//! Expands environment variable references found in text values.<br>
+//! The following syntax is supported:
+//!
+//! * `${VAR}` - replaced with the variable value or an empty string if not defined
+//! * `${VAR:-default}` - replaced with the default value if the variable is not defined or empty
+//! * `${VAR:?error message}` - fails with the error message if the variable is not defined or empty
+//! * `$${literal}` - escaped reference which is left as `${literal}`
+//!
+//! Default values and error messages may contain nested references.<br>
+//! Callers may provide additional variables which are resolved before the env vars.
+//!
+
+#[cfg(test)]
+#[path = "./expansion_test.rs"]
+mod expansion_test;
+
+use std::env;
+
+/// Holds the result of a partial expansion
+struct ExpandOutput {
+    /// The expanded text
+    value: String,
+    /// The index of the next char to process
+    next_index: usize,
+    /// True if a closing brace ended the expansion
+    closed: bool,
+}
+
+fn get_no_variable(_name: &str) -> Option<String> {
+    None
+}
+
+fn get_value<F>(name: &str, get_variable: &F) -> Option<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let value = match get_variable(name) {
+        Some(value) => Some(value),
+        None => env::var(name).ok(),
+    };
+
+    match value {
+        Some(value) => {
+            if value.len() > 0 {
+                Some(value)
+            } else {
+                None
+            }
+        }
+        None => None,
+    }
+}
+
+fn to_string(chars: &[char]) -> String {
+    chars.iter().collect()
+}
+
+/// Returns the index of the brace closing the reference which its content starts at the given index.
+fn find_closing_brace(chars: &[char], start: usize) -> Option<usize> {
+    let mut depth = 1;
+
+    for index in start..chars.len() {
+        if chars[index] == '{' {
+            depth = depth + 1;
+        } else if chars[index] == '}' {
+            depth = depth - 1;
+
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+
+    None
+}
+
+/// Parses the reference which its name starts at the given index (after the `${` prefix).<br>
+/// Returns None if the reference is not valid, in which case it should be left as is.
+fn expand_reference<F>(chars: &[char], start: usize, get_variable: &F) -> Option<(String, usize)>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut index = start;
+    while index < chars.len() && !":}${".contains(chars[index]) {
+        index = index + 1;
+    }
+
+    if index == start || index >= chars.len() {
+        return None;
+    }
+
+    let name = to_string(&chars[start..index]);
+
+    if chars[index] == '}' {
+        let value = get_value(&name, get_variable).unwrap_or("".to_string());
+
+        Some((value, index + 1))
+    } else if chars[index] == ':' && index + 1 < chars.len() {
+        let operator = chars[index + 1];
+
+        if operator != '-' && operator != '?' {
+            return None;
+        }
+
+        let output = expand_chars(chars, index + 2, true, get_variable);
+        if !output.closed {
+            return None;
+        }
+
+        match get_value(&name, get_variable) {
+            Some(value) => Some((value, output.next_index)),
+            None => {
+                if operator == '-' {
+                    Some((output.value, output.next_index))
+                } else {
+                    error!("{}: {}", &name, &output.value);
+                    panic!("{}: {}", &name, &output.value);
+                }
+            }
+        }
+    } else {
+        None
+    }
+}
+
+/// Expands all references starting at the given index.<br>
+/// In nested mode, the expansion stops after the first unmatched closing brace.
+fn expand_chars<F>(chars: &[char], start: usize, nested: bool, get_variable: &F) -> ExpandOutput
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut value = String::new();
+    let mut index = start;
+
+    while index < chars.len() {
+        let current = chars[index];
+        let next = chars.get(index + 1).cloned();
+
+        if current == '$' && next == Some('$') && chars.get(index + 2) == Some(&'{') {
+            // escaped reference is copied without the escape char
+            match find_closing_brace(chars, index + 3) {
+                Some(end_index) => {
+                    value.push_str(&to_string(&chars[index + 1..end_index + 1]));
+                    index = end_index + 1;
+                }
+                None => {
+                    value.push_str(&to_string(&chars[index + 1..]));
+                    index = chars.len();
+                }
+            }
+        } else if current == '$' && next == Some('{') {
+            match expand_reference(chars, index + 2, get_variable) {
+                Some((expanded, next_index)) => {
+                    value.push_str(&expanded);
+                    index = next_index;
+                }
+                None => {
+                    value.push_str("${");
+                    index = index + 2;
+                }
+            }
+        } else if nested && current == '}' {
+            return ExpandOutput {
+                value,
+                next_index: index + 1,
+                closed: true,
+            };
+        } else {
+            value.push(current);
+            index = index + 1;
+        }
+    }
+
+    ExpandOutput {
+        value,
+        next_index: index,
+        closed: false,
+    }
+}
+
+/// Expands all environment variable references in the provided value.
+pub(crate) fn expand(value: &str) -> String {
+    expand_with_variables(&value, &get_no_variable)
+}
+
+/// Expands all variable references in the provided value.<br>
+/// Variables returned by the provided function take precedence over the environment variables.
+pub(crate) fn expand_with_variables<F>(value: &str, get_variable: &F) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if value.contains("${") {
+        let chars: Vec<char> = value.chars().collect();
+
+        expand_chars(&chars, 0, false, get_variable).value
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,146 @@
+use super::*;
+
+#[test]
+fn expand_no_references() {
+    let output = expand("no references $HOME $ {}");
+
+    assert_eq!(output, "no references $HOME $ {}");
+}
+
+#[test]
+fn expand_simple() {
+    env::set_var("EXPAND_SIMPLE_TEST", "value");
+
+    let output = expand("1: ${EXPAND_SIMPLE_TEST} 2: ${EXPAND_SIMPLE_TEST}");
+
+    assert_eq!(output, "1: value 2: value");
+}
+
+#[test]
+fn expand_not_defined() {
+    env::remove_var("EXPAND_NOT_DEFINED_TEST");
+
+    let output = expand("before${EXPAND_NOT_DEFINED_TEST}after");
+
+    assert_eq!(output, "beforeafter");
+}
+
+#[test]
+fn expand_default_not_defined() {
+    env::remove_var("EXPAND_DEFAULT_NOT_DEFINED_TEST");
+
+    let output = expand("${EXPAND_DEFAULT_NOT_DEFINED_TEST:-default value}");
+
+    assert_eq!(output, "default value");
+}
+
+#[test]
+fn expand_default_empty() {
+    env::set_var("EXPAND_DEFAULT_EMPTY_TEST", "");
+
+    let output = expand("${EXPAND_DEFAULT_EMPTY_TEST:-default}");
+
+    assert_eq!(output, "default");
+}
+
+#[test]
+fn expand_default_defined() {
+    env::set_var("EXPAND_DEFAULT_DEFINED_TEST", "value");
+
+    let output = expand("${EXPAND_DEFAULT_DEFINED_TEST:-default}");
+
+    assert_eq!(output, "value");
+}
+
+#[test]
+fn expand_default_empty_default() {
+    env::remove_var("EXPAND_DEFAULT_EMPTY_DEFAULT_TEST");
+
+    let output = expand("[${EXPAND_DEFAULT_EMPTY_DEFAULT_TEST:-}]");
+
+    assert_eq!(output, "[]");
+}
+
+#[test]
+fn expand_default_nested() {
+    env::remove_var("EXPAND_DEFAULT_NESTED_TEST1");
+    env::remove_var("EXPAND_DEFAULT_NESTED_TEST2");
+    env::set_var("EXPAND_DEFAULT_NESTED_TEST3", "nested");
+
+    let output = expand(
+        "${EXPAND_DEFAULT_NESTED_TEST1:-${EXPAND_DEFAULT_NESTED_TEST2:-a ${EXPAND_DEFAULT_NESTED_TEST3} b}}",
+    );
+
+    assert_eq!(output, "a nested b");
+}
+
+#[test]
+fn expand_error_defined() {
+    env::set_var("EXPAND_ERROR_DEFINED_TEST", "value");
+
+    let output = expand("${EXPAND_ERROR_DEFINED_TEST:?not defined}");
+
+    assert_eq!(output, "value");
+}
+
+#[test]
+#[should_panic]
+fn expand_error_not_defined() {
+    env::remove_var("EXPAND_ERROR_NOT_DEFINED_TEST");
+
+    expand("${EXPAND_ERROR_NOT_DEFINED_TEST:?not defined}");
+}
+
+#[test]
+fn expand_escaped() {
+    env::set_var("EXPAND_ESCAPED_TEST", "value");
+
+    let output =
+        expand("$${EXPAND_ESCAPED_TEST} ${EXPAND_ESCAPED_TEST} $${EXPAND_ESCAPED_TEST:-${A}}");
+
+    assert_eq!(
+        output,
+        "${EXPAND_ESCAPED_TEST} value ${EXPAND_ESCAPED_TEST:-${A}}"
+    );
+}
+
+#[test]
+fn expand_not_closed() {
+    env::set_var("EXPAND_NOT_CLOSED_TEST", "value");
+
+    let output = expand("${EXPAND_NOT_CLOSED_TEST ${EXPAND_NOT_CLOSED_TEST}");
+
+    assert_eq!(output, "${EXPAND_NOT_CLOSED_TEST value");
+}
+
+#[test]
+fn expand_default_not_closed() {
+    let output = expand("${EXPAND_DEFAULT_NOT_CLOSED_TEST:-value");
+
+    assert_eq!(output, "${EXPAND_DEFAULT_NOT_CLOSED_TEST:-value");
+}
+
+#[test]
+fn expand_invalid_operator() {
+    let output = expand("${EXPAND_INVALID_OPERATOR_TEST:+value}");
+
+    assert_eq!(output, "${EXPAND_INVALID_OPERATOR_TEST:+value}");
+}
+
+#[test]
+fn expand_with_variables_precedence() {
+    env::set_var("EXPAND_WITH_VARIABLES_TEST", "env value");
+
+    let output = expand_with_variables(
+        "${EXPAND_WITH_VARIABLES_TEST} $${EXPAND_WITH_VARIABLES_TEST} ${var}",
+        &|name: &str| {
+            if name == "EXPAND_WITH_VARIABLES_TEST" {
+                Some("${var}".to_string())
+            } else {
+                None
+            }
+        },
+    );
+
+    assert_eq!(output, "${var} ${EXPAND_WITH_VARIABLES_TEST} ");
+}
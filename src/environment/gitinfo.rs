@@ -8,9 +8,45 @@
 mod gitinfo_test;
 
 use command;
+use std::env;
 use std::process::Command;
 use types::GitInfo;
 
+static DEFAULT_BASE_REF: &str = "origin/master";
+
+/// Runs the git command with the provided arguments and returns its output lines.<br>
+/// Returns None if the command failed.
+fn run_git(args: &[&str]) -> Option<Vec<String>> {
+    let result = Command::new("git").args(args).output();
+
+    match result {
+        Ok(output) => {
+            let exit_code = command::get_exit_code(Ok(output.status), true);
+
+            if exit_code == 0 {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+
+                Some(
+                    stdout
+                        .split('\n')
+                        .map(|line| line.trim().to_string())
+                        .filter(|line| line.len() > 0)
+                        .collect(),
+                )
+            } else {
+                None
+            }
+        }
+        Err(error) => {
+            info!(
+                "Error while running git {:#?} command: {:#?}",
+                &args, &error
+            );
+            None
+        }
+    }
+}
+
 fn load_from_git_config(git_info: &mut GitInfo) {
     let result = Command::new("git").arg("config").arg("--list").output();
 
@@ -72,6 +108,37 @@ fn load_branch(git_info: &mut GitInfo) {
     };
 }
 
+/// Returns true if the working tree has no uncommitted changes or None if the git status
+/// could not be resolved.<br>
+/// This is not part of the loaded git info as git status may be slow for large repositories.
+pub(crate) fn is_clean() -> Option<bool> {
+    match run_git(&["status", "--porcelain"]) {
+        Some(lines) => Some(lines.is_empty()),
+        None => None,
+    }
+}
+
+/// Returns the git ref which changed files are compared to (CARGO_MAKE_GIT_BASE_REF env var
+/// or origin/master).
+pub(crate) fn get_base_ref() -> String {
+    match env::var("CARGO_MAKE_GIT_BASE_REF") {
+        Ok(value) => value,
+        _ => DEFAULT_BASE_REF.to_string(),
+    }
+}
+
+/// Returns the files changed (including uncommitted changes) since the common ancestor of
+/// the provided ref and HEAD.<br>
+/// Returns None if the changed files could not be resolved.
+pub(crate) fn get_changed_files(base_ref: &str) -> Option<Vec<String>> {
+    let base = match run_git(&["merge-base", base_ref, "HEAD"]) {
+        Some(ref lines) if lines.len() > 0 => lines[0].clone(),
+        _ => base_ref.to_string(),
+    };
+
+    run_git(&["diff", "--name-only", &base])
+}
+
 pub(crate) fn load() -> GitInfo {
     debug!("Searching for git info.");
 
@@ -6,3 +6,42 @@ fn load_with_values() {
 
     assert!(git_info.branch.is_some());
 }
+
+#[test]
+fn is_clean_status() {
+    assert!(is_clean().is_some());
+}
+
+#[test]
+fn get_base_ref_default() {
+    env::remove_var("CARGO_MAKE_GIT_BASE_REF");
+
+    let base_ref = get_base_ref();
+
+    assert_eq!(base_ref, "origin/master");
+}
+
+#[test]
+fn get_base_ref_from_env() {
+    env::set_var("CARGO_MAKE_GIT_BASE_REF", "develop");
+
+    let base_ref = get_base_ref();
+
+    env::remove_var("CARGO_MAKE_GIT_BASE_REF");
+
+    assert_eq!(base_ref, "develop");
+}
+
+#[test]
+fn get_changed_files_valid_ref() {
+    let files = get_changed_files("HEAD");
+
+    assert!(files.is_some());
+}
+
+#[test]
+fn get_changed_files_invalid_ref() {
+    let files = get_changed_files("bad_ref_for_test");
+
+    assert!(files.is_none());
+}
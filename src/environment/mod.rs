@@ -3,13 +3,19 @@
 //! Sets up the env vars before running the tasks.
 //!
 
+mod ciinfo;
 pub(crate) mod crateinfo;
-mod gitinfo;
+mod envcache;
+mod envfile;
+mod expansion;
+pub(crate) mod gitinfo;
+pub(crate) mod secrets;
 
 #[cfg(test)]
 #[path = "./mod_test.rs"]
 mod mod_test;
 
+use ci_info;
 use command;
 use indexmap::IndexMap;
 use rust_info;
@@ -18,11 +24,15 @@ use std::env;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use types::{
-    CliArgs, Config, CrateInfo, EnvInfo, EnvValue, EnvValueInfo, GitInfo, PackageInfo, Step, Task,
-    Workspace,
+    CliArgs, Config, ConfigSection, CrateInfo, EnvInfo, EnvValue, EnvValueFile, EnvValueInfo,
+    EnvValueList, EnvValuePathList, ExternalConfig, GitInfo, PackageInfo, PlatformOverrideTask,
+    Step, Task, Workspace,
 };
 
+static DEFAULT_PROFILE: &str = "development";
+
 fn evaluate_env_value(env_value: &EnvValueInfo) -> String {
     match command::run_script_get_output(&env_value.script, None, &vec![], true) {
         Ok(output) => {
@@ -52,22 +62,18 @@ fn evaluate_env_value(env_value: &EnvValueInfo) -> String {
     }
 }
 
-fn expand_value(value: &str) -> String {
-    let mut value_string = value.to_string();
-    match value_string.find("${") {
-        Some(_) => {
-            for (existing_key, existing_value) in env::vars() {
-                let mut key_pattern = "${".to_string();
-                key_pattern.push_str(&existing_key);
-                key_pattern.push_str("}");
-
-                value_string = str::replace(&value_string, &key_pattern, &existing_value);
-            }
+/// Expands all env var references in the provided value.
+pub(crate) fn expand_value(value: &str) -> String {
+    expansion::expand(&value)
+}
 
-            value_string
-        }
-        None => value_string,
-    }
+/// Expands all variable references in the provided value, variables resolved by the provided
+/// function take precedence over the env vars.
+pub(crate) fn expand_value_with_variables<F>(value: &str, get_variable: &F) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    expansion::expand_with_variables(&value, get_variable)
 }
 
 fn evaluate_and_set_env(key: &str, value: &str) {
@@ -77,29 +83,350 @@ fn evaluate_and_set_env(key: &str, value: &str) {
 }
 
 fn set_env_for_info(key: &str, env_value: &EnvValueInfo) {
-    let value = evaluate_env_value(&env_value);
+    // secret values are never persisted
+    let cache = (env_value.cache.unwrap_or(false) || env_value.cache_ttl.is_some())
+        && !secrets::is_secret(&key);
+
+    let value = if cache {
+        match envcache::get(&env_value.script, env_value.cache_ttl) {
+            Some(value) => {
+                debug!("Using cached env value for: {}", &key);
+                value
+            }
+            None => {
+                let value = evaluate_env_value(&env_value);
+                envcache::set(&env_value.script, &value);
+                value
+            }
+        }
+    } else {
+        evaluate_env_value(&env_value)
+    };
 
     evaluate_and_set_env(&key, &value);
 }
 
+fn set_env_for_list(key: &str, env_value: &EnvValueList) {
+    let separator = match env_value.separator {
+        Some(ref value) => value.clone(),
+        None => ",".to_string(),
+    };
+
+    let values: Vec<String> = env_value
+        .value
+        .iter()
+        .map(|value| expand_value(&value))
+        .collect();
+
+    env::set_var(&key, values.join(&separator));
+}
+
+fn set_env_for_file(key: &str, env_value: &EnvValueFile) {
+    let file_path = expand_value(&env_value.file);
+
+    let mut file = match File::open(&file_path) {
+        Ok(value) => value,
+        Err(error) => {
+            error!(
+                "Unable to open env value file: {} error: {}",
+                &file_path, error
+            );
+            panic!(
+                "Unable to open env value file: {} error: {}",
+                &file_path, error
+            );
+        }
+    };
+
+    let mut content = String::new();
+    file.read_to_string(&mut content).unwrap();
+
+    env::set_var(&key, content.trim());
+}
+
+fn set_env_for_path_list(key: &str, env_value: &EnvValuePathList) {
+    let mut paths = vec![];
+
+    match env_value.path_prepend {
+        Some(ref values) => {
+            for value in values {
+                paths.push(PathBuf::from(expand_value(&value)));
+            }
+        }
+        None => (),
+    };
+
+    match env::var_os(&key) {
+        Some(current_value) => {
+            for path in env::split_paths(&current_value) {
+                paths.push(path);
+            }
+        }
+        None => (),
+    };
+
+    match env_value.path_append {
+        Some(ref values) => {
+            for value in values {
+                paths.push(PathBuf::from(expand_value(&value)));
+            }
+        }
+        None => (),
+    };
+
+    if paths.len() > 0 {
+        match env::join_paths(paths) {
+            Ok(value) => env::set_var(&key, value),
+            Err(error) => {
+                error!("Unable to join paths for env: {} error: {}", &key, error);
+                panic!("Unable to join paths for env: {} error: {}", &key, error);
+            }
+        };
+    }
+}
+
+/// Returns true if the env value is marked as secret.
+fn is_secret_env_value(env_value: &EnvValue) -> bool {
+    match *env_value {
+        EnvValue::Info(ref info) => info.secret.unwrap_or(false),
+        EnvValue::Definition(ref definition) => definition.secret.unwrap_or(false),
+        EnvValue::List(ref list) => list.secret.unwrap_or(false),
+        EnvValue::File(ref file) => file.secret.unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Returns the values as written in the config (values referencing other env vars are
+/// skipped as the referenced env vars are redacted once set).
+fn get_env_value_literals(env_value: &EnvValue) -> Vec<String> {
+    let values = match *env_value {
+        EnvValue::Value(ref value) => vec![value.clone()],
+        EnvValue::Definition(ref definition) => vec![definition.value.clone()],
+        EnvValue::List(ref list) => list.value.clone(),
+        _ => vec![],
+    };
+
+    values
+        .into_iter()
+        .filter(|value| !value.contains("${"))
+        .collect()
+}
+
+/// Marks the secret env vars defined in the provided env (including profile tables) and their
+/// values as secrets, so they are redacted before the env is set (for example when printing
+/// the config or the execution plan).
+pub(crate) fn add_env_secrets(env: &IndexMap<String, EnvValue>) {
+    for (key, env_value) in env {
+        match *env_value {
+            EnvValue::Profile(ref profile_env) => add_env_secrets(profile_env),
+            _ => {
+                if is_secret_env_value(&env_value) {
+                    secrets::add_secret(&key);
+                }
+
+                if secrets::is_secret(&key) {
+                    for value in get_env_value_literals(&env_value) {
+                        secrets::add_secret_value(&value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns all env maps defined in the config (global, task and task platform overrides).
+pub(crate) fn get_config_env_maps(
+    external_config: &ExternalConfig,
+) -> Vec<&IndexMap<String, EnvValue>> {
+    let mut env_maps = vec![];
+
+    match external_config.env {
+        Some(ref env) => env_maps.push(env),
+        None => (),
+    };
+
+    match external_config.tasks {
+        Some(ref tasks) => {
+            for task in tasks.values() {
+                match task.env {
+                    Some(ref env) => env_maps.push(env),
+                    None => (),
+                };
+
+                for platform_task in [&task.linux, &task.windows, &task.mac].iter() {
+                    match **platform_task {
+                        Some(PlatformOverrideTask {
+                            env: Some(ref env), ..
+                        }) => env_maps.push(env),
+                        _ => (),
+                    };
+                }
+            }
+        }
+        None => (),
+    };
+
+    env_maps
+}
+
+/// Registers the secret env patterns and secret env values defined in the config.<br>
+/// Invoked once the config is parsed so the secrets are redacted from any output, even
+/// if their env is only set later on (such as task level env).
+pub(crate) fn add_config_secrets(external_config: &ExternalConfig) {
+    match external_config.config {
+        Some(ConfigSection {
+            secret_env_patterns: Some(ref patterns),
+            ..
+        }) => secrets::add_patterns(patterns),
+        _ => (),
+    };
+
+    for env in get_config_env_maps(&external_config) {
+        add_env_secrets(env);
+    }
+}
+
 /// Updates the env based on the provided data
 pub(crate) fn set_env(env: IndexMap<String, EnvValue>) {
     debug!("Setting Up Env.");
 
     for (key, env_value) in &env {
-        debug!("Setting env: {} = {:#?}", &key, &env_value);
+        if is_secret_env_value(&env_value) {
+            secrets::add_secret(&key);
+        }
+
+        if secrets::is_secret(&key) {
+            debug!("Setting secret env: {}", &key);
+        } else {
+            debug!("Setting env: {} = {:#?}", &key, &env_value);
+        }
 
         match *env_value {
             EnvValue::Value(ref value) => evaluate_and_set_env(&key, value),
+            EnvValue::Boolean(value) => env::set_var(&key, value.to_string()),
+            EnvValue::Number(value) => env::set_var(&key, value.to_string()),
             EnvValue::Info(ref info) => set_env_for_info(&key, info),
+            EnvValue::Definition(ref definition) => evaluate_and_set_env(&key, &definition.value),
+            EnvValue::List(ref list) => set_env_for_list(&key, list),
+            EnvValue::File(ref file) => set_env_for_file(&key, file),
+            EnvValue::Unset(ref unset) => {
+                if unset.unset {
+                    env::remove_var(&key);
+                }
+            }
+            EnvValue::PathList(ref path_list) => set_env_for_path_list(&key, path_list),
+            EnvValue::Profile(_) => (),
         };
     }
+
+    // the active profile env overrides the base env
+    let profile_name = get_profile_name();
+    match env.get(&profile_name) {
+        Some(&EnvValue::Profile(ref profile_env)) => {
+            debug!("Setting env for profile: {}", &profile_name);
+
+            set_env(profile_env.clone());
+        }
+        _ => (),
+    };
+}
+
+/// Returns the current active profile name
+pub(crate) fn get_profile_name() -> String {
+    get_env("CARGO_MAKE_PROFILE", DEFAULT_PROFILE)
+}
+
+/// Sets the active profile based on the provided value, the CARGO_MAKE_PROFILE env var
+/// or the default profile (in that order) and returns the profile name.
+pub(crate) fn setup_profile(profile: Option<String>) -> String {
+    let profile_name = match profile {
+        Some(value) => value,
+        None => get_profile_name(),
+    };
+
+    env::set_var("CARGO_MAKE_PROFILE", &profile_name);
+
+    profile_name
+}
+
+/// Returns the provided path resolved from the working directory (if relative).
+fn get_working_directory_path(path: &str) -> PathBuf {
+    let working_directory = get_env("CARGO_MAKE_WORKING_DIRECTORY", ".");
+
+    Path::new(&working_directory).join(expand_value(&path))
+}
+
+/// Sets the CARGO_MAKE_TOOL_ROOT env var and adds the tool root bin directory at the start of the PATH.
+fn setup_env_for_tool_root(config: &Config) {
+    match config.config.tool_root {
+        Some(ref tool_root) => {
+            let tool_root_path = get_working_directory_path(&tool_root);
+            env::set_var("CARGO_MAKE_TOOL_ROOT", &tool_root_path);
+
+            let mut paths = vec![tool_root_path.join("bin")];
+            match env::var_os("PATH") {
+                Some(current_value) => {
+                    for path in env::split_paths(&current_value) {
+                        paths.push(path);
+                    }
+                }
+                None => (),
+            };
+
+            match env::join_paths(paths) {
+                Ok(value) => env::set_var("PATH", value),
+                Err(error) => {
+                    error!("Unable to add tool root to PATH, error: {}", error);
+                    panic!("Unable to add tool root to PATH, error: {}", error);
+                }
+            };
+        }
+        None => debug!("Tool root not defined."),
+    }
+}
+
+/// Sets the CARGO_MAKE_OFFLINE_TOOL_SOURCE env var used to install crates from local source trees.
+fn setup_env_for_offline_tool_source(config: &Config) {
+    match config.config.offline_tool_source {
+        Some(ref offline_tool_source) => {
+            let source_path = get_working_directory_path(&offline_tool_source);
+            env::set_var("CARGO_MAKE_OFFLINE_TOOL_SOURCE", &source_path);
+        }
+        None => debug!("Offline tool source not defined."),
+    }
+}
+
+/// Sets the CARGO_MAKE_OFFLINE env var.<br>
+/// A value inherited from a parent cargo-make process (such as workspace members) is kept.
+fn setup_env_for_offline(offline: bool) {
+    if offline {
+        env::set_var("CARGO_MAKE_OFFLINE", "TRUE");
+    } else if env::var("CARGO_MAKE_OFFLINE").is_err() {
+        env::set_var("CARGO_MAKE_OFFLINE", "FALSE");
+    }
+}
+
+/// Sets the CARGO_MAKE_RUST_SCRIPT_RUNNER env var used as the default @rust script runner.
+fn setup_env_for_rust_script_runner(config: &Config) {
+    match config.config.rust_script_runner {
+        Some(ref runner) => env::set_var("CARGO_MAKE_RUST_SCRIPT_RUNNER", runner),
+        None => debug!("Rust script runner not defined."),
+    }
 }
 
 /// Updates the env for the current execution based on the descriptor.
 fn initialize_env(config: &Config) {
     info!("Setting Up Env.");
 
+    match config.config.secret_env_patterns {
+        Some(ref patterns) => secrets::add_patterns(patterns),
+        None => (),
+    };
+
+    setup_env_for_tool_root(config);
+    setup_env_for_offline_tool_source(config);
+    setup_env_for_rust_script_runner(config);
+
     set_env(config.env.clone());
 }
 
@@ -203,6 +530,41 @@ fn setup_env_for_git_repo() -> GitInfo {
     git_info_clone
 }
 
+fn get_rust_commit_date_from_output(output: &str) -> Option<String> {
+    for line in output.lines() {
+        if line.starts_with("commit-date:") {
+            let date = line["commit-date:".len()..].trim();
+
+            return if date.len() > 0 && date != "unknown" {
+                Some(date.to_string())
+            } else {
+                None
+            };
+        }
+    }
+
+    None
+}
+
+/// Returns the commit date (YYYY-MM-DD) of the current rust compiler.
+fn get_rust_commit_date() -> Option<String> {
+    match Command::new("rustc").arg("-vV").output() {
+        Ok(output) => {
+            let exit_code = command::get_exit_code(Ok(output.status), true);
+
+            if exit_code == 0 {
+                get_rust_commit_date_from_output(&String::from_utf8_lossy(&output.stdout))
+            } else {
+                None
+            }
+        }
+        Err(error) => {
+            debug!("Unable to get rust commit date, error: {}", error);
+            None
+        }
+    }
+}
+
 fn setup_env_for_rust() -> RustInfo {
     let rustinfo = rust_info::get();
     let rust_info_clone = rustinfo.clone();
@@ -223,6 +585,11 @@ fn setup_env_for_rust() -> RustInfo {
         env::set_var("CARGO_MAKE_RUST_CHANNEL", channel.to_string());
     }
 
+    match get_rust_commit_date() {
+        Some(date) => env::set_var("CARGO_MAKE_RUST_COMMIT_DATE", &date),
+        None => (),
+    };
+
     env::set_var(
         "CARGO_MAKE_RUST_TARGET_ARCH",
         &rustinfo.target_arch.unwrap_or("unknown".to_string()),
@@ -249,6 +616,29 @@ fn setup_env_for_rust() -> RustInfo {
     rust_info_clone
 }
 
+/// Sets the CARGO_MAKE_CI, CARGO_MAKE_PR and CI vendor/branch env vars.
+fn setup_env_for_ci() {
+    let ci_info = ci_info::get();
+
+    env::set_var("CARGO_MAKE_CI", if ci_info.ci { "TRUE" } else { "FALSE" });
+
+    let is_pr = match ci_info.vendor {
+        Some(ref vendor) => {
+            env::set_var("CARGO_MAKE_CI_VENDOR", ciinfo::get_vendor_name(&vendor));
+
+            match ciinfo::get_branch_name(&vendor) {
+                Some(branch) => env::set_var("CARGO_MAKE_CI_BRANCH_NAME", &branch),
+                None => (),
+            };
+
+            ciinfo::is_pr(&vendor)
+        }
+        None => false,
+    };
+
+    env::set_var("CARGO_MAKE_PR", if is_pr { "TRUE" } else { "FALSE" });
+}
+
 /// Sets up the env before the tasks execution.
 pub(crate) fn setup_env(cli_args: &CliArgs, config: &Config, task: &str) -> EnvInfo {
     env::set_var("CARGO_MAKE", "true");
@@ -266,6 +656,8 @@ pub(crate) fn setup_env(cli_args: &CliArgs, config: &Config, task: &str) -> EnvI
     };
     env::set_var("CARGO_MAKE_TASK_ARGS", &task_arguments);
 
+    setup_env_for_offline(cli_args.offline);
+
     // load crate info
     let crate_info = setup_env_for_crate();
 
@@ -275,6 +667,9 @@ pub(crate) fn setup_env(cli_args: &CliArgs, config: &Config, task: &str) -> EnvI
     // load rust info
     let rust_info = setup_env_for_rust();
 
+    // load ci info
+    setup_env_for_ci();
+
     // load env vars
     initialize_env(config);
 
@@ -331,6 +726,24 @@ pub(crate) fn get_env_as_bool(key: &str, default_value: bool) -> bool {
     }
 }
 
+/// Loads and parses the env file found in the provided path (using the dotenv syntax).
+pub(crate) fn load_env_file(file_path: &Path) -> Vec<String> {
+    debug!("Opening env file: {:#?}", &file_path);
+    let mut file = match File::open(&file_path) {
+        Ok(value) => value,
+        Err(error) => panic!(
+            "Unable to open env file: {} error: {}",
+            file_path.to_str().unwrap_or(""),
+            error
+        ),
+    };
+
+    let mut env_content = String::new();
+    file.read_to_string(&mut env_content).unwrap();
+
+    envfile::parse(&env_content)
+}
+
 pub(crate) fn parse_env_file(env_file: Option<String>) -> Option<Vec<String>> {
     match env_file {
         Some(file_name) => {
@@ -342,32 +755,7 @@ pub(crate) fn parse_env_file(env_file: Option<String>) -> Option<Vec<String>> {
             };
 
             if file_path.exists() {
-                debug!("Opening env file: {:#?}", &file_path);
-                let mut file = match File::open(&file_path) {
-                    Ok(value) => value,
-                    Err(error) => panic!(
-                        "Unable to open env file: {} error: {}",
-                        file_path.to_str().unwrap_or(""),
-                        error
-                    ),
-                };
-
-                let mut env_content = String::new();
-                file.read_to_string(&mut env_content).unwrap();
-
-                let mut env: Vec<String> = vec![];
-
-                let lines: Vec<&str> = env_content.split('\n').collect();
-
-                for mut line in lines {
-                    line = line.trim();
-
-                    if !line.starts_with("#") {
-                        env.push(line.to_string());
-                    }
-                }
-
-                Some(env)
+                Some(load_env_file(&file_path))
             } else {
                 None
             }
@@ -460,6 +848,14 @@ pub(crate) fn expand_env(step: &Step) -> Step {
         None => {}
     };
 
+    //update cwd by replacing any env vars
+    match config.cwd {
+        Some(value) => {
+            config.cwd = Some(expand_value(&value));
+        }
+        None => {}
+    };
+
     //update args by replacing any env vars
     expand_env_for_arguments(&mut config);
 
@@ -3,7 +3,7 @@ use super::*;
 use indexmap::IndexMap;
 use std::env;
 use std::{thread, time};
-use types::{ConfigSection, Task};
+use types::{ConfigSection, EnvValueDefinition, EnvValueUnset, Task};
 
 #[test]
 fn get_env_exists() {
@@ -101,6 +101,25 @@ fn parse_env_file_exists() {
     );
 }
 
+#[test]
+fn load_env_file_dotenv_syntax() {
+    let env = load_env_file(Path::new("./examples/env/dotenv.env"));
+
+    assert_eq!(env.len(), 6);
+    assert_eq!(env[0], "DOTENV_EXPORTED=exported value");
+    assert_eq!(env[1], "DOTENV_UNQUOTED=unquoted value");
+    assert_eq!(
+        env[2],
+        "DOTENV_DOUBLE_QUOTED=line1\nline2 with \"quotes\" and ${DOTENV_UNQUOTED}"
+    );
+    assert_eq!(
+        env[3],
+        "DOTENV_SINGLE_QUOTED=literal $${DOTENV_UNQUOTED} # not a comment"
+    );
+    assert_eq!(env[4], "DOTENV_MULTILINE=first line\nsecond line");
+    assert_eq!(env[5], "DOTENV_URL=https://example.com/?a=b&c=d");
+}
+
 #[test]
 fn evaluate_and_set_env_simple() {
     env::remove_var("EVAL_SET_SIMPLE");
@@ -129,7 +148,7 @@ fn evaluate_and_set_env_not_exists() {
     );
     assert_eq!(
         env::var("evaluate_and_set_env_not_exists").unwrap(),
-        "testing: ${eval_test_bad} works".to_string()
+        "testing:  works".to_string()
     );
 }
 
@@ -143,7 +162,7 @@ fn evaluate_and_set_env_complex() {
     );
     assert_eq!(
         env::var("evaluate_and_set_env_complex").unwrap(),
-        "checking 10 is 10 empty is ${eval_test30} and 20 is 20".to_string()
+        "checking 10 is 10 empty is  and 20 is 20".to_string()
     );
 }
 
@@ -232,6 +251,80 @@ fn setup_env_values() {
     assert_eq!(env::var("MY_ENV_KEY2").unwrap(), "MY_ENV_VALUE2");
 }
 
+#[test]
+fn set_env_with_active_profile() {
+    env::set_var("CARGO_MAKE_PROFILE", "profile_test");
+
+    let mut profile_env = IndexMap::new();
+    profile_env.insert(
+        "PROFILE_ENV_KEY1".to_string(),
+        EnvValue::Value("PROFILE".to_string()),
+    );
+
+    let mut other_profile_env = IndexMap::new();
+    other_profile_env.insert(
+        "PROFILE_ENV_KEY2".to_string(),
+        EnvValue::Value("OTHER".to_string()),
+    );
+
+    let mut env_map = IndexMap::new();
+    env_map.insert(
+        "profile_test".to_string(),
+        EnvValue::Profile(profile_env),
+    );
+    env_map.insert(
+        "other_profile".to_string(),
+        EnvValue::Profile(other_profile_env),
+    );
+    env_map.insert(
+        "PROFILE_ENV_KEY1".to_string(),
+        EnvValue::Value("BASE".to_string()),
+    );
+    env_map.insert(
+        "PROFILE_ENV_KEY2".to_string(),
+        EnvValue::Value("BASE".to_string()),
+    );
+
+    set_env(env_map);
+
+    env::remove_var("CARGO_MAKE_PROFILE");
+
+    assert_eq!(env::var("PROFILE_ENV_KEY1").unwrap(), "PROFILE");
+    assert_eq!(env::var("PROFILE_ENV_KEY2").unwrap(), "BASE");
+}
+
+#[test]
+fn setup_profile_from_value() {
+    env::set_var("CARGO_MAKE_PROFILE", "EMPTY");
+
+    let profile_name = setup_profile(Some("production".to_string()));
+
+    assert_eq!(profile_name, "production");
+    assert_eq!(env::var("CARGO_MAKE_PROFILE").unwrap(), "production");
+    env::remove_var("CARGO_MAKE_PROFILE");
+}
+
+#[test]
+fn setup_profile_from_env() {
+    env::set_var("CARGO_MAKE_PROFILE", "ci");
+
+    let profile_name = setup_profile(None);
+
+    assert_eq!(profile_name, "ci");
+    env::remove_var("CARGO_MAKE_PROFILE");
+}
+
+#[test]
+fn setup_profile_default() {
+    env::remove_var("CARGO_MAKE_PROFILE");
+
+    let profile_name = setup_profile(None);
+
+    assert_eq!(profile_name, "development");
+    assert_eq!(env::var("CARGO_MAKE_PROFILE").unwrap(), "development");
+    env::remove_var("CARGO_MAKE_PROFILE");
+}
+
 #[test]
 fn setup_env_script() {
     let cli_args = CliArgs::new();
@@ -249,6 +342,9 @@ fn setup_env_script() {
         "MY_ENV_SCRIPT_KEY2".to_string(),
         EnvValue::Info(EnvValueInfo {
             script: vec!["echo script1".to_string()],
+            secret: None,
+            cache: None,
+            cache_ttl: None,
         }),
     );
 
@@ -267,10 +363,247 @@ fn setup_env_script() {
     assert_eq!(env::var("MY_ENV_SCRIPT_KEY2").unwrap(), "script1");
 }
 
+#[test]
+fn setup_env_secret_values() {
+    let cli_args = CliArgs::new();
+
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+    config.config.secret_env_patterns = Some(vec!["SETUP_ENV_SECRET_PATTERN_*".to_string()]);
+    config.env.insert(
+        "SETUP_ENV_SECRET_DEFINITION".to_string(),
+        EnvValue::Definition(EnvValueDefinition {
+            value: "secret1".to_string(),
+            secret: Some(true),
+        }),
+    );
+    config.env.insert(
+        "SETUP_ENV_SECRET_SCRIPT".to_string(),
+        EnvValue::Info(EnvValueInfo {
+            script: vec!["echo secret2".to_string()],
+            secret: Some(true),
+            cache: None,
+            cache_ttl: None,
+        }),
+    );
+    config.env.insert(
+        "SETUP_ENV_SECRET_PATTERN_KEY".to_string(),
+        EnvValue::Value("secret3".to_string()),
+    );
+    config.env.insert(
+        "SETUP_ENV_SECRET_PUBLIC".to_string(),
+        EnvValue::Definition(EnvValueDefinition {
+            value: "public".to_string(),
+            secret: Some(false),
+        }),
+    );
+
+    setup_env(&cli_args, &config, "setup_env_secret_values");
+
+    assert_eq!(env::var("SETUP_ENV_SECRET_DEFINITION").unwrap(), "secret1");
+    assert_eq!(env::var("SETUP_ENV_SECRET_SCRIPT").unwrap(), "secret2");
+    assert_eq!(env::var("SETUP_ENV_SECRET_PATTERN_KEY").unwrap(), "secret3");
+    assert_eq!(env::var("SETUP_ENV_SECRET_PUBLIC").unwrap(), "public");
+    assert!(secrets::is_secret("SETUP_ENV_SECRET_DEFINITION"));
+    assert!(secrets::is_secret("SETUP_ENV_SECRET_SCRIPT"));
+    assert!(secrets::is_secret("SETUP_ENV_SECRET_PATTERN_KEY"));
+    assert!(!secrets::is_secret("SETUP_ENV_SECRET_PUBLIC"));
+
+    let output = secrets::redact("secret1 secret2 secret3 public");
+    assert_eq!(output, "*** *** *** public");
+}
+
+#[test]
+fn add_config_secrets_task_env() {
+    let config: ExternalConfig = toml::from_str(
+        r#"
+[config]
+secret_env_patterns = ["ADD_CONFIG_SECRETS_PATTERN_*"]
+
+[env]
+ADD_CONFIG_SECRETS_PUBLIC = "public-config-value"
+ADD_CONFIG_SECRETS_PATTERN_KEY = "pattern-config-value"
+
+[env.production]
+ADD_CONFIG_SECRETS_PROFILE = { value = "profile-config-value", secret = true }
+
+[tasks.test.env]
+ADD_CONFIG_SECRETS_TASK = { value = "task-config-value", secret = true }
+ADD_CONFIG_SECRETS_REFERENCE = { value = "${ADD_CONFIG_SECRETS_PUBLIC}", secret = true }
+
+[tasks.test.linux.env]
+ADD_CONFIG_SECRETS_PLATFORM = { value = ["platform-config-value"], secret = true }
+"#,
+    )
+    .unwrap();
+
+    add_config_secrets(&config);
+
+    assert!(secrets::is_secret("ADD_CONFIG_SECRETS_TASK"));
+    assert!(secrets::is_secret("ADD_CONFIG_SECRETS_PROFILE"));
+    assert!(secrets::is_secret("ADD_CONFIG_SECRETS_PLATFORM"));
+    assert!(!secrets::is_secret("ADD_CONFIG_SECRETS_PUBLIC"));
+
+    let output = secrets::redact(
+        "public-config-value pattern-config-value profile-config-value task-config-value platform-config-value ${ADD_CONFIG_SECRETS_PUBLIC}",
+    );
+    assert_eq!(
+        output,
+        "public-config-value *** *** *** *** ${ADD_CONFIG_SECRETS_PUBLIC}"
+    );
+}
+
+#[test]
+fn set_env_typed_values() {
+    let mut env_values = IndexMap::new();
+    env_values.insert("SET_ENV_TYPED_BOOLEAN".to_string(), EnvValue::Boolean(true));
+    env_values.insert("SET_ENV_TYPED_NUMBER".to_string(), EnvValue::Number(10));
+
+    set_env(env_values);
+
+    assert_eq!(env::var("SET_ENV_TYPED_BOOLEAN").unwrap(), "true");
+    assert_eq!(env::var("SET_ENV_TYPED_NUMBER").unwrap(), "10");
+}
+
+#[test]
+fn set_env_list() {
+    env::set_var("SET_ENV_LIST_ITEM", "expanded");
+
+    let mut env_values = IndexMap::new();
+    env_values.insert(
+        "SET_ENV_LIST_DEFAULT".to_string(),
+        EnvValue::List(EnvValueList {
+            value: vec!["1".to_string(), "${SET_ENV_LIST_ITEM}".to_string()],
+            separator: None,
+            secret: None,
+        }),
+    );
+    env_values.insert(
+        "SET_ENV_LIST_SEPARATOR".to_string(),
+        EnvValue::List(EnvValueList {
+            value: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+            separator: Some(" | ".to_string()),
+            secret: None,
+        }),
+    );
+
+    set_env(env_values);
+
+    assert_eq!(env::var("SET_ENV_LIST_DEFAULT").unwrap(), "1,expanded");
+    assert_eq!(env::var("SET_ENV_LIST_SEPARATOR").unwrap(), "1 | 2 | 3");
+}
+
+#[test]
+fn set_env_file() {
+    let mut env_values = IndexMap::new();
+    env_values.insert(
+        "SET_ENV_FILE".to_string(),
+        EnvValue::File(EnvValueFile {
+            file: "./examples/files/VERSION".to_string(),
+            secret: None,
+        }),
+    );
+
+    set_env(env_values);
+
+    assert_eq!(env::var("SET_ENV_FILE").unwrap(), "1.2.3");
+}
+
+#[test]
+#[should_panic]
+fn set_env_file_not_found() {
+    let mut env_values = IndexMap::new();
+    env_values.insert(
+        "SET_ENV_FILE_NOT_FOUND".to_string(),
+        EnvValue::File(EnvValueFile {
+            file: "./examples/files/bad_file".to_string(),
+            secret: None,
+        }),
+    );
+
+    set_env(env_values);
+}
+
+#[test]
+fn set_env_unset() {
+    env::set_var("SET_ENV_UNSET", "value");
+    env::set_var("SET_ENV_UNSET_FALSE", "value");
+
+    let mut env_values = IndexMap::new();
+    env_values.insert(
+        "SET_ENV_UNSET".to_string(),
+        EnvValue::Unset(EnvValueUnset { unset: true }),
+    );
+    env_values.insert(
+        "SET_ENV_UNSET_FALSE".to_string(),
+        EnvValue::Unset(EnvValueUnset { unset: false }),
+    );
+
+    set_env(env_values);
+
+    assert!(env::var("SET_ENV_UNSET").is_err());
+    assert_eq!(env::var("SET_ENV_UNSET_FALSE").unwrap(), "value");
+}
+
+#[test]
+fn set_env_path_list() {
+    let current_value = env::join_paths(vec!["current1", "current2"]).unwrap();
+    env::set_var("SET_ENV_PATH_LIST", &current_value);
+    env::remove_var("SET_ENV_PATH_LIST_EMPTY");
+
+    let mut env_values = IndexMap::new();
+    env_values.insert(
+        "SET_ENV_PATH_LIST".to_string(),
+        EnvValue::PathList(EnvValuePathList {
+            path_prepend: Some(vec!["first".to_string()]),
+            path_append: Some(vec!["last1".to_string(), "last2".to_string()]),
+        }),
+    );
+    env_values.insert(
+        "SET_ENV_PATH_LIST_EMPTY".to_string(),
+        EnvValue::PathList(EnvValuePathList {
+            path_prepend: None,
+            path_append: Some(vec!["last".to_string()]),
+        }),
+    );
+
+    set_env(env_values);
+
+    let expected = env::join_paths(vec!["first", "current1", "current2", "last1", "last2"]).unwrap();
+    assert_eq!(env::var_os("SET_ENV_PATH_LIST").unwrap(), expected);
+    assert_eq!(env::var("SET_ENV_PATH_LIST_EMPTY").unwrap(), "last");
+}
+
+#[test]
+fn set_env_cached_script() {
+    let env_value = EnvValue::Info(EnvValueInfo {
+        script: vec!["echo ${SET_ENV_CACHED_SCRIPT_SOURCE}".to_string()],
+        secret: None,
+        cache: Some(true),
+        cache_ttl: None,
+    });
+
+    env::set_var("SET_ENV_CACHED_SCRIPT_SOURCE", "first");
+    let mut env_values = IndexMap::new();
+    env_values.insert("SET_ENV_CACHED_SCRIPT".to_string(), env_value.clone());
+    set_env(env_values.clone());
+    assert_eq!(env::var("SET_ENV_CACHED_SCRIPT").unwrap(), "first");
+
+    env::set_var("SET_ENV_CACHED_SCRIPT_SOURCE", "second");
+    set_env(env_values);
+    assert_eq!(env::var("SET_ENV_CACHED_SCRIPT").unwrap(), "first");
+}
+
 #[test]
 fn evaluate_env_value_valid() {
     let output = evaluate_env_value(&EnvValueInfo {
         script: vec!["echo script1".to_string()],
+        secret: None,
+        cache: None,
+        cache_ttl: None,
     });
 
     assert_eq!(output, "script1".to_string());
@@ -281,6 +614,9 @@ fn evaluate_env_value_valid() {
 fn evaluate_env_value_empty() {
     let output = evaluate_env_value(&EnvValueInfo {
         script: vec!["".to_string()],
+        secret: None,
+        cache: None,
+        cache_ttl: None,
     });
 
     assert_eq!(output, "".to_string());
@@ -291,6 +627,9 @@ fn evaluate_env_value_empty() {
 fn evaluate_env_error() {
     evaluate_env_value(&EnvValueInfo {
         script: vec!["exit 1".to_string()],
+        secret: None,
+        cache: None,
+        cache_ttl: None,
     });
 }
 
@@ -504,6 +843,141 @@ fn setup_env_for_rust_simple_check() {
     assert!(env::var("CARGO_MAKE_RUST_TARGET_OS").unwrap() != "EMPTY");
     assert!(env::var("CARGO_MAKE_RUST_TARGET_POINTER_WIDTH").unwrap() != "EMPTY");
     assert!(env::var("CARGO_MAKE_RUST_TARGET_VENDOR").unwrap() != "EMPTY");
+    assert!(env::var("CARGO_MAKE_RUST_COMMIT_DATE").is_ok());
+}
+
+#[test]
+fn setup_env_for_ci_simple_check() {
+    env::set_var("CARGO_MAKE_CI", "EMPTY");
+    env::set_var("CARGO_MAKE_PR", "EMPTY");
+
+    setup_env_for_ci();
+
+    let ci = env::var("CARGO_MAKE_CI").unwrap();
+    assert!(ci == "TRUE" || ci == "FALSE");
+    let pr = env::var("CARGO_MAKE_PR").unwrap();
+    assert!(pr == "TRUE" || pr == "FALSE");
+}
+
+#[test]
+fn setup_env_for_tool_root_defined() {
+    let path = env::var_os("PATH").unwrap();
+    let directory = env::var("CARGO_MAKE_WORKING_DIRECTORY").unwrap_or("".to_string());
+    env::set_var("CARGO_MAKE_WORKING_DIRECTORY", "/project");
+
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+    config.config.tool_root = Some("target/tools".to_string());
+
+    setup_env_for_tool_root(&config);
+
+    let tool_root = env::var("CARGO_MAKE_TOOL_ROOT").unwrap();
+    let updated_path = env::var_os("PATH").unwrap();
+
+    env::set_var("PATH", &path);
+    env::set_var("CARGO_MAKE_WORKING_DIRECTORY", &directory);
+    env::remove_var("CARGO_MAKE_TOOL_ROOT");
+
+    assert_eq!(PathBuf::from(tool_root), PathBuf::from("/project/target/tools"));
+    let first_path = env::split_paths(&updated_path).next().unwrap();
+    assert_eq!(first_path, PathBuf::from("/project/target/tools/bin"));
+}
+
+#[test]
+fn setup_env_for_tool_root_not_defined() {
+    env::remove_var("CARGO_MAKE_TOOL_ROOT");
+
+    let config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+
+    setup_env_for_tool_root(&config);
+
+    assert!(env::var("CARGO_MAKE_TOOL_ROOT").is_err());
+}
+
+#[test]
+fn setup_env_for_offline_tool_source_defined() {
+    let directory = env::var("CARGO_MAKE_WORKING_DIRECTORY").unwrap_or("".to_string());
+    env::set_var("CARGO_MAKE_WORKING_DIRECTORY", "/project");
+
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+    config.config.offline_tool_source = Some("vendor".to_string());
+
+    setup_env_for_offline_tool_source(&config);
+
+    let source_path = env::var("CARGO_MAKE_OFFLINE_TOOL_SOURCE").unwrap();
+
+    env::set_var("CARGO_MAKE_WORKING_DIRECTORY", &directory);
+    env::remove_var("CARGO_MAKE_OFFLINE_TOOL_SOURCE");
+
+    assert_eq!(PathBuf::from(source_path), PathBuf::from("/project/vendor"));
+}
+
+#[test]
+fn setup_env_for_rust_script_runner_defined() {
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+    config.config.rust_script_runner = Some("rust-script".to_string());
+
+    setup_env_for_rust_script_runner(&config);
+
+    let runner = env::var("CARGO_MAKE_RUST_SCRIPT_RUNNER").unwrap();
+
+    env::remove_var("CARGO_MAKE_RUST_SCRIPT_RUNNER");
+
+    assert_eq!(runner, "rust-script");
+}
+
+#[test]
+fn setup_env_for_offline_values() {
+    env::remove_var("CARGO_MAKE_OFFLINE");
+    setup_env_for_offline(false);
+    assert_eq!(env::var("CARGO_MAKE_OFFLINE").unwrap(), "FALSE");
+
+    setup_env_for_offline(true);
+    assert_eq!(env::var("CARGO_MAKE_OFFLINE").unwrap(), "TRUE");
+
+    // inherited value is kept
+    setup_env_for_offline(false);
+    assert_eq!(env::var("CARGO_MAKE_OFFLINE").unwrap(), "TRUE");
+
+    env::set_var("CARGO_MAKE_OFFLINE", "FALSE");
+}
+
+#[test]
+fn get_rust_commit_date_from_output_found() {
+    let date = get_rust_commit_date_from_output(
+        "rustc 1.30.0-nightly (abc 2018-09-05)\nbinary: rustc\ncommit-date: 2018-09-04\nhost: x86_64-unknown-linux-gnu",
+    );
+
+    assert_eq!(date.unwrap(), "2018-09-04");
+}
+
+#[test]
+fn get_rust_commit_date_from_output_unknown() {
+    let date = get_rust_commit_date_from_output("binary: rustc\ncommit-date: unknown");
+
+    assert!(date.is_none());
+}
+
+#[test]
+fn get_rust_commit_date_from_output_missing() {
+    let date = get_rust_commit_date_from_output("binary: rustc\nrelease: 1.30.0");
+
+    assert!(date.is_none());
 }
 
 #[test]
@@ -609,6 +1083,31 @@ fn expand_env_with_env_vars() {
     assert_eq!(args[3], "arg3-ENV1-ENV2".to_string());
 }
 
+#[test]
+fn expand_env_with_cwd_and_defaults() {
+    env::set_var("TEST_ENV_EXPAND_CWD", "examples");
+    env::remove_var("TEST_ENV_EXPAND_NOT_SET");
+
+    let mut task = Task::new();
+    task.command = Some("${TEST_ENV_EXPAND_NOT_SET:-echo}".to_string());
+    task.cwd = Some("./${TEST_ENV_EXPAND_CWD}".to_string());
+    task.args = Some(vec![
+        "$${TEST_ENV_EXPAND_CWD}".to_string(),
+        "${TEST_ENV_EXPAND_NOT_SET:-${TEST_ENV_EXPAND_CWD}}".to_string(),
+    ]);
+    let step = Step {
+        name: "test".to_string(),
+        config: task,
+    };
+    let updated_step = expand_env(&step);
+
+    assert_eq!(updated_step.config.command.unwrap(), "echo".to_string());
+    assert_eq!(updated_step.config.cwd.unwrap(), "./examples".to_string());
+    let args = updated_step.config.args.unwrap();
+    assert_eq!(args[0], "${TEST_ENV_EXPAND_CWD}".to_string());
+    assert_eq!(args[1], "examples".to_string());
+}
+
 #[test]
 fn expand_env_with_env_vars_and_task_args() {
     env::set_var("TEST_ENV_EXPAND1", "ENV1");
@@ -0,0 +1,130 @@
+//! # secrets
+//!
+//! Tracks which environment variables hold secret values and redacts those values from
+//! any text printed by cargo-make.<br>
+//! The secret env names/patterns are kept in an env var so that nested cargo-make invocations
+//! (such as workspace members) redact the same values.
+//!
+
+#[cfg(test)]
+#[path = "./secrets_test.rs"]
+mod secrets_test;
+
+use glob::Pattern;
+use std::cell::RefCell;
+use std::env;
+
+static SECRET_PATTERNS_ENV: &str = "CARGO_MAKE_SECRET_ENV_PATTERNS";
+static DEFAULT_SECRET_ENV: &[&str] = &["GITHUB_API_TOKEN", "CARGO_REGISTRY_TOKEN"];
+static MASK: &str = "***";
+
+thread_local! {
+    // secret values defined in the config which are not yet set in the env
+    static SECRET_VALUES: RefCell<Vec<String>> = RefCell::new(vec![]);
+}
+
+fn get_defined_patterns() -> Vec<String> {
+    match env::var(SECRET_PATTERNS_ENV) {
+        Ok(value) => value
+            .split(';')
+            .filter(|pattern| pattern.len() > 0)
+            .map(|pattern| pattern.to_string())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn get_patterns() -> Vec<String> {
+    let mut patterns: Vec<String> = DEFAULT_SECRET_ENV
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    patterns.append(&mut get_defined_patterns());
+
+    patterns
+}
+
+fn matches_any(patterns: &Vec<String>, name: &str) -> bool {
+    for pattern in patterns {
+        if pattern == name {
+            return true;
+        }
+
+        match Pattern::new(pattern) {
+            Ok(glob) => {
+                if glob.matches(name) {
+                    return true;
+                }
+            }
+            Err(error) => debug!("Invalid secret env pattern: {} {:#?}", pattern, error),
+        };
+    }
+
+    false
+}
+
+/// Marks all env vars matching the provided names or glob patterns as secrets.
+pub(crate) fn add_patterns(patterns: &Vec<String>) {
+    let mut all_patterns = get_defined_patterns();
+
+    for pattern in patterns {
+        if !all_patterns.contains(pattern) {
+            all_patterns.push(pattern.clone());
+        }
+    }
+
+    env::set_var(SECRET_PATTERNS_ENV, all_patterns.join(";"));
+}
+
+/// Marks the provided env var as secret.
+pub(crate) fn add_secret(name: &str) {
+    add_patterns(&vec![name.to_string()]);
+}
+
+/// Marks the provided value as secret, used for secret values defined in the config which
+/// are printed before being set in the env.
+pub(crate) fn add_secret_value(value: &str) {
+    if value.len() > 0 {
+        SECRET_VALUES.with(|values| {
+            let mut values_vec = values.borrow_mut();
+
+            if !values_vec.iter().any(|existing| existing == value) {
+                values_vec.push(value.to_string());
+            }
+        });
+    }
+}
+
+/// Returns true if the provided env var holds a secret value.
+pub(crate) fn is_secret(name: &str) -> bool {
+    matches_any(&get_patterns(), name)
+}
+
+/// Replaces all secret env values found in the provided text with a mask.
+pub(crate) fn redact(text: &str) -> String {
+    let patterns = get_patterns();
+
+    let mut values = SECRET_VALUES.with(|values| values.borrow().clone());
+    for (key, value) in env::vars() {
+        if value.len() > 0 && matches_any(&patterns, &key) {
+            values.push(value);
+        }
+    }
+
+    // longer values first so that a secret containing another secret is fully masked
+    values.sort_by(|first, second| second.len().cmp(&first.len()));
+
+    let mut output = text.to_string();
+    for value in values {
+        output = output.replace(&value, MASK);
+
+        // values printed using the debug format are escaped
+        let debug_value = format!("{:?}", &value);
+        let escaped_value = &debug_value[1..debug_value.len() - 1];
+        if escaped_value != value {
+            output = output.replace(escaped_value, MASK);
+        }
+    }
+
+    output
+}
@@ -0,0 +1,89 @@
+use super::*;
+
+#[test]
+fn is_secret_default_names() {
+    assert!(is_secret("GITHUB_API_TOKEN"));
+    assert!(is_secret("CARGO_REGISTRY_TOKEN"));
+    assert!(!is_secret("SECRETS_TEST_NOT_SECRET"));
+}
+
+#[test]
+fn add_secret_name() {
+    assert!(!is_secret("SECRETS_TEST_ADD_SECRET"));
+
+    add_secret("SECRETS_TEST_ADD_SECRET");
+
+    assert!(is_secret("SECRETS_TEST_ADD_SECRET"));
+    assert!(!is_secret("SECRETS_TEST_ADD_SECRET2"));
+}
+
+#[test]
+fn add_patterns_glob() {
+    add_patterns(&vec!["SECRETS_TEST_GLOB_*_TOKEN".to_string()]);
+
+    assert!(is_secret("SECRETS_TEST_GLOB_API_TOKEN"));
+    assert!(!is_secret("SECRETS_TEST_GLOB_API_USER"));
+}
+
+#[test]
+fn add_patterns_no_duplicates() {
+    add_patterns(&vec!["SECRETS_TEST_DUPLICATE".to_string()]);
+    add_patterns(&vec!["SECRETS_TEST_DUPLICATE".to_string()]);
+
+    let patterns = get_defined_patterns();
+    let count = patterns
+        .iter()
+        .filter(|pattern| *pattern == "SECRETS_TEST_DUPLICATE")
+        .count();
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn redact_no_secrets() {
+    let output = redact("nothing to hide");
+
+    assert_eq!(output, "nothing to hide");
+}
+
+#[test]
+fn redact_secret_value() {
+    env::set_var("SECRETS_TEST_REDACT_VALUE", "my-secret-value");
+    env::set_var("SECRETS_TEST_REDACT_PUBLIC", "public-value");
+    add_secret("SECRETS_TEST_REDACT_VALUE");
+
+    let output = redact("token: my-secret-value, other: public-value, again: my-secret-value");
+
+    assert_eq!(output, "token: ***, other: public-value, again: ***");
+}
+
+#[test]
+fn redact_secret_debug_format() {
+    env::set_var("SECRETS_TEST_REDACT_DEBUG", "quoted \"secret\" value");
+    add_secret("SECRETS_TEST_REDACT_DEBUG");
+
+    let text = format!("{:?}", vec!["quoted \"secret\" value"]);
+    let output = redact(&text);
+
+    assert_eq!(output, "[\"***\"]");
+}
+
+#[test]
+fn redact_longer_secret_first() {
+    env::set_var("SECRETS_TEST_REDACT_SHORT", "abcdef");
+    env::set_var("SECRETS_TEST_REDACT_LONG", "abcdef123456");
+    add_patterns(&vec!["SECRETS_TEST_REDACT_S*".to_string(), "SECRETS_TEST_REDACT_L*".to_string()]);
+
+    let output = redact("value: abcdef123456");
+
+    assert_eq!(output, "value: ***");
+}
+
+#[test]
+fn redact_secret_value_not_in_env() {
+    add_secret_value("secrets-test-config-value");
+    add_secret_value("");
+
+    let output = redact("value: secrets-test-config-value, empty: ");
+
+    assert_eq!(output, "value: ***, empty: ");
+}
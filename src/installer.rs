@@ -3,6 +3,9 @@
 //! Installs external dependencies for tasks.<br>
 //! There are 2 types of dependencies: install_crate, install_script.<br>
 //! install_crate ensures the crate command is available and if not installs the crate based on the provided name.<br>
+//! If a version is provided, the installed binary version is checked and the crate is reinstalled if it does not match.<br>
+//! install_crate can also define a rustup component which is added via rustup if it is not installed.<br>
+//! If a tool root is defined, crates are installed in the tool root and their versions are recorded in the lock file.<br>
 //! install_script always gets executed before the task command.
 //!
 
@@ -11,8 +14,15 @@
 mod installer_test;
 
 use command;
+use environment;
+use semver::{Version, VersionReq};
+use std::path::Path;
 use std::process::Command;
-use types::Task;
+use toolchain;
+use toollock;
+use types::{InstallCrate, InstallCrateInfo, InstallRustupComponentInfo, Task};
+
+static DEFAULT_TEST_ARG: &str = "--version";
 
 fn is_crate_installed(crate_name: &str) -> bool {
     debug!("Getting list of installed cargo commands.");
@@ -26,13 +36,13 @@ fn is_crate_installed(crate_name: &str) -> bool {
             command::validate_exit_code(exit_code);
 
             let stdout = String::from_utf8_lossy(&output.stdout);
-            let lines: Vec<&str> = stdout.split(' ').collect();
-            for mut line in lines {
-                line = line.trim();
+            for line in stdout.lines() {
+                // each line starts with the command name followed by its description
+                let command_name = line.trim().split_whitespace().next().unwrap_or("");
 
-                debug!("Checking: {}", &line);
+                debug!("Checking: {}", &command_name);
 
-                if line.contains(crate_name) && crate_name.contains(line) {
+                if command_name == crate_name {
                     found = true;
                     debug!("Found installed crate.");
 
@@ -52,7 +62,38 @@ fn is_crate_installed(crate_name: &str) -> bool {
     }
 }
 
-fn get_install_crate_args(crate_name: &str, args: &Option<Vec<String>>) -> Vec<String> {
+/// Returns the local source directory of the crate.<br>
+/// The explicit path takes precedence over the crate directory in the offline tool source.
+fn get_crate_source_path(crate_name: &str, path: &Option<String>) -> Option<String> {
+    match *path {
+        Some(ref value) => Some(value.to_string()),
+        None => {
+            let offline_tool_source = environment::get_env("CARGO_MAKE_OFFLINE_TOOL_SOURCE", "");
+
+            if offline_tool_source.len() > 0 {
+                let source_path = Path::new(&offline_tool_source).join(crate_name);
+
+                if source_path.exists() {
+                    Some(source_path.to_string_lossy().into_owned())
+                } else {
+                    debug!(
+                        "Crate: {} not found in offline tool source: {}",
+                        crate_name, &offline_tool_source
+                    );
+                    None
+                }
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn get_install_crate_args(
+    crate_name: &str,
+    path: &Option<String>,
+    args: &Option<Vec<String>>,
+) -> Vec<String> {
     let mut install_args = vec!["install".to_string()];
 
     match *args {
@@ -62,29 +103,471 @@ fn get_install_crate_args(crate_name: &str, args: &Option<Vec<String>>) -> Vec<S
         None => debug!("No crate installation args defined."),
     };
 
+    if environment::get_env_as_bool("CARGO_MAKE_OFFLINE", false) {
+        install_args.push("--offline".to_string());
+    }
+
+    match get_crate_source_path(&crate_name, &path) {
+        Some(source_path) => {
+            install_args.push("--path".to_string());
+            install_args.push(source_path);
+        }
+        None => (),
+    };
+
     install_args.push(crate_name.to_string());
 
     install_args
 }
 
+/// Returns the tool root directory if the provided value is not empty.
+fn get_tool_root_from_value(tool_root: String) -> Option<String> {
+    if tool_root.len() > 0 {
+        Some(tool_root)
+    } else {
+        None
+    }
+}
+
+/// Returns the tool root directory defined via the CARGO_MAKE_TOOL_ROOT env var.
+fn get_tool_root() -> Option<String> {
+    get_tool_root_from_value(environment::get_env("CARGO_MAKE_TOOL_ROOT", ""))
+}
+
+/// Returns the crate version from the cargo install --list output.<br>
+/// Each installed crate is listed as: name vX.Y.Z (optional source):
+fn get_installed_version_from_output(output: &str, crate_name: &str) -> Option<String> {
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let mut words = line.split_whitespace();
+
+            match (words.next(), words.next()) {
+                (Some(name), Some(version)) => {
+                    if name == crate_name {
+                        let version_string = version.trim_end_matches(':').trim_start_matches('v');
+
+                        return Some(version_string.to_string());
+                    }
+                }
+                _ => (),
+            };
+        }
+    }
+
+    None
+}
+
+/// Returns the version of the crate installed in the tool root.
+fn get_installed_version(tool_root: &str, crate_name: &str) -> Option<String> {
+    match Command::new("cargo")
+        .arg("install")
+        .arg("--list")
+        .arg("--root")
+        .arg(tool_root)
+        .output()
+    {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            get_installed_version_from_output(&stdout, &crate_name)
+        }
+        Err(error) => {
+            debug!(
+                "Unable to list crates installed in tool root: {} error: {}",
+                tool_root, error
+            );
+            None
+        }
+    }
+}
+
+/// Returns true if the locked version can be used for the required version.
+fn is_locked_version_valid(locked_version: &str, version_requirement: &Option<String>) -> bool {
+    match *version_requirement {
+        Some(ref requirement) => is_version_valid(&locked_version, &requirement),
+        None => true,
+    }
+}
+
+/// Returns the cargo install args for the tool root (the version argument is pinned to the locked version if defined).
+fn get_install_crate_in_tool_root_args(
+    tool_root: &str,
+    crate_name: &str,
+    path: &Option<String>,
+    version: &Option<String>,
+    args: &Option<Vec<String>>,
+    force: bool,
+) -> Vec<String> {
+    let mut all_args = match *args {
+        Some(ref args_vec) => args_vec.clone(),
+        None => vec![],
+    };
+
+    all_args.push("--root".to_string());
+    all_args.push(tool_root.to_string());
+
+    if force {
+        all_args.push("--force".to_string());
+    }
+
+    match *version {
+        Some(ref value) => {
+            all_args.push("--version".to_string());
+            all_args.push(value.to_string());
+        }
+        None => (),
+    };
+
+    get_install_crate_args(&crate_name, &path, &Some(all_args))
+}
+
+/// Installs the crate in the tool root if it is missing or if the installed version does not
+/// match the locked/required version and records the installed version in the lock file.
+fn install_crate_in_tool_root(
+    tool_root: &str,
+    crate_name: &str,
+    path: &Option<String>,
+    version_requirement: &Option<String>,
+    args: &Option<Vec<String>>,
+    validate: bool,
+) {
+    let locked_version = match toollock::get_version(&crate_name) {
+        Some(ref version) => {
+            if is_locked_version_valid(&version, &version_requirement) {
+                Some(version.clone())
+            } else {
+                debug!("Locked version: {} does not match requirement.", &version);
+                None
+            }
+        }
+        None => None,
+    };
+
+    let installed_version = get_installed_version(&tool_root, &crate_name);
+
+    let install = match installed_version {
+        Some(ref installed) => match locked_version {
+            Some(ref locked) => installed != locked,
+            None => match *version_requirement {
+                Some(ref requirement) => !is_version_valid(&installed, &requirement),
+                None => false,
+            },
+        },
+        None => true,
+    };
+
+    if install {
+        let version = match locked_version {
+            Some(ref locked) => Some(format!("={}", locked)),
+            None => version_requirement.clone(),
+        };
+
+        info!(
+            "Installing crate: {} version: {} in tool root: {}",
+            &crate_name,
+            version.clone().unwrap_or("latest".to_string()),
+            &tool_root
+        );
+
+        let install_args = get_install_crate_in_tool_root_args(
+            &tool_root,
+            &crate_name,
+            &path,
+            &version,
+            &args,
+            installed_version.is_some(),
+        );
+
+        command::run_command("cargo", &Some(install_args), validate);
+    }
+
+    match get_installed_version(&tool_root, &crate_name) {
+        Some(version) => toollock::set_version(&crate_name, &version),
+        None => debug!("Unable to find installed version of crate: {}", &crate_name),
+    };
+}
+
+/// Returns true if the cargo command crate should be installed/validated in the tool root.<br>
+/// Built in and globally installed cargo commands are used as is, while crates already installed
+/// in the tool root are always validated against the lock file.
+fn is_tool_root_install_needed(tool_root: &str, cargo_command: &str, crate_name: &str) -> bool {
+    get_installed_version(&tool_root, &crate_name).is_some() || !is_crate_installed(cargo_command)
+}
+
 pub(crate) fn install_crate(
     cargo_command: &str,
     crate_name: &str,
     args: &Option<Vec<String>>,
     validate: bool,
 ) {
-    if !is_crate_installed(cargo_command) {
-        let install_args = get_install_crate_args(crate_name, args);
+    match get_tool_root() {
+        Some(tool_root) => {
+            if is_tool_root_install_needed(&tool_root, &cargo_command, &crate_name) {
+                install_crate_in_tool_root(&tool_root, &crate_name, &None, &None, &args, validate)
+            }
+        }
+        None => {
+            if !is_crate_installed(cargo_command) {
+                let install_args = get_install_crate_args(crate_name, &None, args);
 
-        command::run_command("cargo", &Some(install_args), validate);
+                command::run_command("cargo", &Some(install_args), validate);
+            }
+        }
+    }
+}
+
+/// Returns the first semver version found in the provided text.<br>
+/// The pre-release and build metadata are ignored as many tools use them for channel/commit info.
+fn parse_version_output(output: &str) -> Option<Version> {
+    for word in output.split_whitespace() {
+        let version_string = word.trim_start_matches('v').trim_end_matches(',');
+
+        match Version::parse(version_string) {
+            Ok(mut version) => {
+                version.pre = vec![];
+                version.build = vec![];
+
+                return Some(version);
+            }
+            _ => (),
+        };
+    }
+
+    None
+}
+
+/// Runs the binary with the test argument (via the toolchain if provided) and returns its output.<br>
+/// Returns None if the binary could not be invoked or exited with an error.
+fn get_binary_output(toolchain: &Option<String>, binary: &str, test_arg: &str) -> Option<String> {
+    let args = Some(vec![test_arg.to_string()]);
+    let (command_string, command_args) = match *toolchain {
+        Some(ref toolchain_name) => toolchain::wrap_command(&toolchain_name, &binary, &args),
+        None => (binary.to_string(), args),
+    };
+
+    match Command::new(&command_string)
+        .args(command_args.unwrap_or(vec![]))
+        .output()
+    {
+        Ok(output) => {
+            let exit_code = command::get_exit_code(Ok(output.status), true);
+
+            if exit_code == 0 {
+                let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+                text.push_str(&String::from_utf8_lossy(&output.stderr));
+
+                Some(text)
+            } else {
+                None
+            }
+        }
+        Err(error) => {
+            debug!("Unable to invoke binary: {} error: {}", binary, error);
+            None
+        }
+    }
+}
+
+fn is_version_valid(output: &str, version_requirement: &str) -> bool {
+    let requirement = match VersionReq::parse(version_requirement) {
+        Ok(value) => value,
+        Err(error) => {
+            error!(
+                "Invalid install crate version: {} error: {}",
+                version_requirement, error
+            );
+            panic!(
+                "Invalid install crate version: {} error: {}",
+                version_requirement, error
+            );
+        }
+    };
+
+    match parse_version_output(&output) {
+        Some(version) => {
+            debug!("Found installed version: {}", &version);
+
+            requirement.matches(&version)
+        }
+        None => {
+            debug!("Unable to find installed version in output: {}", &output);
+            false
+        }
+    }
+}
+
+fn get_install_crate_with_version_args(
+    info: &InstallCrateInfo,
+    args: &Option<Vec<String>>,
+    force: bool,
+) -> Vec<String> {
+    let mut all_args = match *args {
+        Some(ref args_vec) => args_vec.clone(),
+        None => vec![],
+    };
+
+    if force {
+        all_args.push("--force".to_string());
+    }
+
+    match info.version {
+        Some(ref version) => {
+            all_args.push("--version".to_string());
+            all_args.push(version.to_string());
+        }
+        None => (),
+    };
+
+    get_install_crate_args(&info.crate_name, &info.path, &Some(all_args))
+}
+
+/// Installs the crate if its binary is not found or if the installed version does not match
+/// the required version.
+pub(crate) fn install_crate_with_version(
+    info: &InstallCrateInfo,
+    args: &Option<Vec<String>>,
+    validate: bool,
+) {
+    match get_tool_root() {
+        Some(tool_root) => install_crate_in_tool_root(
+            &tool_root,
+            &info.crate_name,
+            &info.path,
+            &info.version,
+            &args,
+            validate,
+        ),
+        None => {
+            let test_arg = match info.test_arg {
+                Some(ref value) => value.as_str(),
+                None => DEFAULT_TEST_ARG,
+            };
+
+            let binary = match info.binary {
+                Some(ref value) => value,
+                None => &info.crate_name,
+            };
+
+            let (install, force) = match get_binary_output(&None, &binary, &test_arg) {
+                Some(output) => match info.version {
+                    Some(ref version) => (!is_version_valid(&output, &version), true),
+                    None => (false, false),
+                },
+                None => (true, false),
+            };
+
+            if install {
+                info!(
+                    "Installing crate: {} version: {}",
+                    &info.crate_name,
+                    info.version.clone().unwrap_or("latest".to_string())
+                );
+
+                let install_args = get_install_crate_with_version_args(&info, &args, force);
+
+                command::run_command("cargo", &Some(install_args), validate);
+            }
+        }
+    }
+}
+
+fn is_rustup_component_installed(toolchain: &Option<String>, component_name: &str) -> bool {
+    let mut args = vec![
+        "component".to_string(),
+        "list".to_string(),
+        "--installed".to_string(),
+    ];
+
+    match *toolchain {
+        Some(ref toolchain_name) => {
+            args.push("--toolchain".to_string());
+            args.push(toolchain_name.to_string());
+        }
+        None => (),
+    };
+
+    match Command::new("rustup").args(&args).output() {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            // installed components are listed with the target triple suffix (for example: clippy-x86_64-unknown-linux-gnu)
+            let prefix = format!("{}-", component_name);
+            stdout
+                .lines()
+                .map(|line| line.trim())
+                .any(|line| line == component_name || line.starts_with(&prefix))
+        }
+        Err(error) => {
+            debug!(
+                "Unable to list installed rustup components, error: {}",
+                error
+            );
+            false
+        }
+    }
+}
+
+fn get_install_rustup_component_args(
+    toolchain: &Option<String>,
+    component_name: &str,
+) -> Vec<String> {
+    let mut args = vec!["component".to_string(), "add".to_string()];
+
+    match *toolchain {
+        Some(ref toolchain_name) => {
+            args.push("--toolchain".to_string());
+            args.push(toolchain_name.to_string());
+        }
+        None => (),
+    };
+
+    args.push(component_name.to_string());
+
+    args
+}
+
+/// Adds the rustup component if its binary is not found (or if it is not listed as installed
+/// in case no binary is defined).
+pub(crate) fn install_rustup_component(
+    info: &InstallRustupComponentInfo,
+    toolchain: &Option<String>,
+    validate: bool,
+) {
+    let installed = match info.binary {
+        Some(ref binary) => {
+            let test_arg = match info.test_arg {
+                Some(ref value) => value.as_str(),
+                None => DEFAULT_TEST_ARG,
+            };
+
+            get_binary_output(&toolchain, &binary, &test_arg).is_some()
+        }
+        None => is_rustup_component_installed(&toolchain, &info.rustup_component_name),
+    };
+
+    if !installed {
+        info!(
+            "Installing rustup component: {}",
+            &info.rustup_component_name
+        );
+
+        let install_args =
+            get_install_rustup_component_args(&toolchain, &info.rustup_component_name);
+
+        command::run_command("rustup", &Some(install_args), validate);
     }
 }
 
 pub(crate) fn install(task_config: &Task) {
     let validate = !task_config.is_force();
 
+    match task_config.toolchain {
+        Some(ref toolchain_name) => toolchain::install_toolchain(&toolchain_name, validate),
+        None => (),
+    };
+
     match task_config.install_crate {
-        Some(ref crate_name) => {
+        Some(InstallCrate::Value(ref crate_name)) => {
             let cargo_command = match task_config.args {
                 Some(ref args) => &args[0],
                 None => {
@@ -100,6 +583,12 @@ pub(crate) fn install(task_config: &Task) {
                 validate,
             );
         }
+        Some(InstallCrate::Info(ref info)) => {
+            install_crate_with_version(&info, &task_config.install_crate_args, validate)
+        }
+        Some(InstallCrate::RustupComponent(ref info)) => {
+            install_rustup_component(&info, &task_config.toolchain, validate)
+        }
         None => {
             match task_config.install_script {
                 Some(ref script) => {
@@ -1,4 +1,7 @@
 use super::*;
+use std::env;
+use std::path::PathBuf;
+use toml;
 
 #[test]
 fn is_crate_installed_true() {
@@ -22,7 +25,7 @@ fn install_empty() {
 #[test]
 fn install_crate_already_installed() {
     let mut task = Task::new();
-    task.install_crate = Some("test".to_string());
+    task.install_crate = Some(InstallCrate::Value("test".to_string()));
     task.command = Some("cargo".to_string());
     task.args = Some(vec!["test".to_string()]);
 
@@ -33,7 +36,7 @@ fn install_crate_already_installed() {
 #[should_panic]
 fn install_crate_missing_cargo_command() {
     let mut task = Task::new();
-    task.install_crate = Some("test".to_string());
+    task.install_crate = Some(InstallCrate::Value("test".to_string()));
     task.command = Some("cargo".to_string());
 
     install(&task);
@@ -60,7 +63,7 @@ fn install_crate_auto_detect_unable_to_install() {
 
 #[test]
 fn get_install_crate_args_no_args() {
-    let all_args = get_install_crate_args("test123", &None);
+    let all_args = get_install_crate_args("test123", &None, &None);
 
     assert_eq!(all_args.len(), 2);
     assert_eq!(all_args[0], "install");
@@ -69,7 +72,7 @@ fn get_install_crate_args_no_args() {
 
 #[test]
 fn get_install_crate_args_empty_args() {
-    let all_args = get_install_crate_args("test123", &Some(vec![]));
+    let all_args = get_install_crate_args("test123", &None, &Some(vec![]));
 
     assert_eq!(all_args.len(), 2);
     assert_eq!(all_args[0], "install");
@@ -80,6 +83,7 @@ fn get_install_crate_args_empty_args() {
 fn get_install_crate_args_with_args() {
     let all_args = get_install_crate_args(
         "test123",
+        &None,
         &Some(vec!["arg1".to_string(), "arg2".to_string()]),
     );
 
@@ -115,3 +119,418 @@ fn install_script_error_force() {
 
     install(&task);
 }
+
+#[test]
+fn parse_version_output_found() {
+    let version = parse_version_output("cargo-make 0.14.0\n").unwrap();
+
+    assert_eq!(version, Version::parse("0.14.0").unwrap());
+}
+
+#[test]
+fn parse_version_output_prefixed() {
+    let version = parse_version_output("tool v2.1.3, built today").unwrap();
+
+    assert_eq!(version, Version::parse("2.1.3").unwrap());
+}
+
+#[test]
+fn parse_version_output_pre_release_ignored() {
+    let version = parse_version_output("rustfmt 1.4.0-stable (abc 2018-09-05)").unwrap();
+
+    assert_eq!(version, Version::parse("1.4.0").unwrap());
+}
+
+#[test]
+fn parse_version_output_not_found() {
+    let version = parse_version_output("no version here 1.0");
+
+    assert!(version.is_none());
+}
+
+#[test]
+fn is_version_valid_matching() {
+    assert!(is_version_valid("cargo-audit 0.7.2", "^0.7"));
+    assert!(is_version_valid("cargo-audit 0.7.2", ">=0.7.0, <0.8.0"));
+}
+
+#[test]
+fn is_version_valid_not_matching() {
+    assert!(!is_version_valid("cargo-audit 0.8.0", "^0.7"));
+    assert!(!is_version_valid("no version", "^0.7"));
+}
+
+#[test]
+#[should_panic]
+fn is_version_valid_invalid_requirement() {
+    is_version_valid("cargo-audit 0.7.2", "bad version");
+}
+
+#[test]
+fn get_binary_output_valid() {
+    let output = get_binary_output(&None, "cargo", "--version");
+
+    assert!(output.unwrap().starts_with("cargo "));
+}
+
+#[test]
+fn get_binary_output_not_found() {
+    let output = get_binary_output(&None, "badbadbad", "--version");
+
+    assert!(output.is_none());
+}
+
+#[test]
+fn get_install_crate_with_version_args_no_force() {
+    let info = InstallCrateInfo {
+        crate_name: "test123".to_string(),
+        binary: Some("test".to_string()),
+        version: Some("^0.7".to_string()),
+        test_arg: None,
+        path: None,
+    };
+
+    let all_args = get_install_crate_with_version_args(&info, &None, false);
+
+    assert_eq!(all_args, vec!["install", "--version", "^0.7", "test123"]);
+}
+
+#[test]
+fn get_install_crate_with_version_args_force_with_args() {
+    let info = InstallCrateInfo {
+        crate_name: "test123".to_string(),
+        binary: Some("test".to_string()),
+        version: Some("^0.7".to_string()),
+        test_arg: None,
+        path: None,
+    };
+
+    let all_args =
+        get_install_crate_with_version_args(&info, &Some(vec!["--locked".to_string()]), true);
+
+    assert_eq!(
+        all_args,
+        vec![
+            "install",
+            "--locked",
+            "--force",
+            "--version",
+            "^0.7",
+            "test123"
+        ]
+    );
+}
+
+#[test]
+fn install_crate_with_version_already_installed() {
+    let mut task = Task::new();
+    task.install_crate = Some(InstallCrate::Info(InstallCrateInfo {
+        crate_name: "badbadbad".to_string(),
+        binary: Some("cargo".to_string()),
+        version: Some(">=0.1".to_string()),
+        test_arg: Some("--version".to_string()),
+        path: None,
+    }));
+
+    install(&task);
+}
+
+#[test]
+fn install_crate_with_version_no_version_check() {
+    let mut task = Task::new();
+    task.install_crate = Some(InstallCrate::Info(InstallCrateInfo {
+        crate_name: "badbadbad".to_string(),
+        binary: Some("cargo".to_string()),
+        version: None,
+        test_arg: None,
+        path: None,
+    }));
+
+    install(&task);
+}
+
+#[test]
+fn install_crate_info_deserialize() {
+    let task: Task = toml::from_str(
+        r#"install_crate = { crate_name = "cargo-audit", binary = "cargo-audit", version = "^0.7", test_arg = "-V" }"#,
+    ).unwrap();
+
+    assert_eq!(
+        task.install_crate.unwrap(),
+        InstallCrate::Info(InstallCrateInfo {
+            crate_name: "cargo-audit".to_string(),
+            binary: Some("cargo-audit".to_string()),
+            version: Some("^0.7".to_string()),
+            test_arg: Some("-V".to_string()),
+            path: None,
+        })
+    );
+}
+
+#[test]
+fn get_binary_output_with_toolchain_not_found() {
+    let output = get_binary_output(&Some("badbadbad".to_string()), "cargo", "--version");
+
+    assert!(output.is_none());
+}
+
+#[test]
+fn is_rustup_component_installed_true() {
+    let installed = is_rustup_component_installed(&None, "rustc");
+    assert!(installed);
+}
+
+#[test]
+fn is_rustup_component_installed_false() {
+    let installed = is_rustup_component_installed(&None, "badbadbad");
+    assert!(!installed);
+}
+
+#[test]
+fn get_install_rustup_component_args_no_toolchain() {
+    let args = get_install_rustup_component_args(&None, "clippy");
+
+    assert_eq!(args, vec!["component", "add", "clippy"]);
+}
+
+#[test]
+fn get_install_rustup_component_args_with_toolchain() {
+    let args = get_install_rustup_component_args(&Some("nightly".to_string()), "clippy");
+
+    assert_eq!(
+        args,
+        vec!["component", "add", "--toolchain", "nightly", "clippy"]
+    );
+}
+
+#[test]
+fn install_rustup_component_binary_found() {
+    let mut task = Task::new();
+    task.install_crate = Some(InstallCrate::RustupComponent(InstallRustupComponentInfo {
+        rustup_component_name: "badbadbad".to_string(),
+        binary: Some("cargo".to_string()),
+        test_arg: None,
+    }));
+
+    install(&task);
+}
+
+#[test]
+fn install_rustup_component_already_installed() {
+    let mut task = Task::new();
+    task.install_crate = Some(InstallCrate::RustupComponent(InstallRustupComponentInfo {
+        rustup_component_name: "rustc".to_string(),
+        binary: None,
+        test_arg: None,
+    }));
+
+    install(&task);
+}
+
+#[test]
+fn install_rustup_component_info_deserialize() {
+    let task: Task = toml::from_str(
+        r#"install_crate = { rustup_component_name = "clippy", binary = "cargo-clippy" }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        task.install_crate.unwrap(),
+        InstallCrate::RustupComponent(InstallRustupComponentInfo {
+            rustup_component_name: "clippy".to_string(),
+            binary: Some("cargo-clippy".to_string()),
+            test_arg: None,
+        })
+    );
+}
+
+#[test]
+fn get_tool_root_from_value_empty() {
+    assert!(get_tool_root_from_value("".to_string()).is_none());
+}
+
+#[test]
+fn get_tool_root_from_value_defined() {
+    let tool_root = get_tool_root_from_value("/project/tools".to_string());
+
+    assert_eq!(tool_root.unwrap(), "/project/tools");
+}
+
+#[test]
+fn get_installed_version_from_output_found() {
+    let output = "cargo-audit v0.7.2:\n    cargo-audit\ncargo-outdated v0.8.0 (/some/path):\n    cargo-outdated\n";
+
+    assert_eq!(
+        get_installed_version_from_output(&output, "cargo-audit").unwrap(),
+        "0.7.2"
+    );
+    assert_eq!(
+        get_installed_version_from_output(&output, "cargo-outdated").unwrap(),
+        "0.8.0"
+    );
+}
+
+#[test]
+fn get_installed_version_from_output_not_found() {
+    let output = "cargo-audit v0.7.2:\n    cargo-outdated\n";
+
+    assert!(get_installed_version_from_output(&output, "cargo-outdated").is_none());
+}
+
+#[test]
+fn get_installed_version_empty_tool_root() {
+    let version = get_installed_version("./target/_cargo_make_temp/empty_tool_root", "cargo-audit");
+
+    assert!(version.is_none());
+}
+
+#[test]
+fn is_tool_root_install_needed_built_in_command() {
+    let needed = is_tool_root_install_needed(
+        "./target/_cargo_make_temp/empty_tool_root",
+        "build",
+        "cargo-build",
+    );
+
+    assert!(!needed);
+}
+
+#[test]
+fn is_tool_root_install_needed_missing_command() {
+    let needed = is_tool_root_install_needed(
+        "./target/_cargo_make_temp/empty_tool_root",
+        "badbadbad",
+        "cargo-badbadbad",
+    );
+
+    assert!(needed);
+}
+
+#[test]
+fn is_locked_version_valid_no_requirement() {
+    assert!(is_locked_version_valid("0.7.2", &None));
+}
+
+#[test]
+fn is_locked_version_valid_with_requirement() {
+    assert!(is_locked_version_valid("0.7.2", &Some("^0.7".to_string())));
+    assert!(!is_locked_version_valid("0.7.2", &Some("^0.8".to_string())));
+}
+
+#[test]
+fn get_install_crate_in_tool_root_args_no_version() {
+    let all_args = get_install_crate_in_tool_root_args(
+        "/project/tools",
+        "cargo-audit",
+        &None,
+        &None,
+        &None,
+        false,
+    );
+
+    assert_eq!(
+        all_args,
+        vec!["install", "--root", "/project/tools", "cargo-audit"]
+    );
+}
+
+#[test]
+fn get_install_crate_in_tool_root_args_locked_force() {
+    let all_args = get_install_crate_in_tool_root_args(
+        "/project/tools",
+        "cargo-audit",
+        &None,
+        &Some("=0.7.2".to_string()),
+        &Some(vec!["--locked".to_string()]),
+        true,
+    );
+
+    assert_eq!(
+        all_args,
+        vec![
+            "install",
+            "--locked",
+            "--root",
+            "/project/tools",
+            "--force",
+            "--version",
+            "=0.7.2",
+            "cargo-audit"
+        ]
+    );
+}
+
+#[test]
+fn get_crate_source_path_explicit() {
+    let source_path = get_crate_source_path("test123", &Some("tools/test123".to_string()));
+
+    assert_eq!(source_path.unwrap(), "tools/test123");
+}
+
+#[test]
+fn get_crate_source_path_offline_tool_source_found() {
+    env::set_var("CARGO_MAKE_OFFLINE_TOOL_SOURCE", "examples");
+    let source_path = get_crate_source_path("crate_targets", &None);
+    env::remove_var("CARGO_MAKE_OFFLINE_TOOL_SOURCE");
+
+    assert_eq!(
+        PathBuf::from(source_path.unwrap()),
+        PathBuf::from("examples/crate_targets")
+    );
+}
+
+#[test]
+fn get_crate_source_path_offline_tool_source_not_found() {
+    env::set_var("CARGO_MAKE_OFFLINE_TOOL_SOURCE", "examples");
+    let source_path = get_crate_source_path("badbadbad", &None);
+    env::remove_var("CARGO_MAKE_OFFLINE_TOOL_SOURCE");
+
+    assert!(source_path.is_none());
+}
+
+#[test]
+fn get_crate_source_path_not_defined() {
+    env::remove_var("CARGO_MAKE_OFFLINE_TOOL_SOURCE");
+
+    assert!(get_crate_source_path("crate_targets", &None).is_none());
+}
+
+#[test]
+fn get_install_crate_args_offline_with_path() {
+    env::set_var("CARGO_MAKE_OFFLINE", "TRUE");
+    let all_args = get_install_crate_args(
+        "test123",
+        &Some("tools/test123".to_string()),
+        &Some(vec!["--locked".to_string()]),
+    );
+    env::remove_var("CARGO_MAKE_OFFLINE");
+
+    assert_eq!(
+        all_args,
+        vec![
+            "install",
+            "--locked",
+            "--offline",
+            "--path",
+            "tools/test123",
+            "test123"
+        ]
+    );
+}
+
+#[test]
+fn install_crate_info_with_path_deserialize() {
+    let task: Task =
+        toml::from_str(r#"install_crate = { crate_name = "foo", path = "tools/foo" }"#).unwrap();
+
+    assert_eq!(
+        task.install_crate.unwrap(),
+        InstallCrate::Info(InstallCrateInfo {
+            crate_name: "foo".to_string(),
+            binary: None,
+            version: None,
+            test_arg: None,
+            path: Some("tools/foo".to_string()),
+        })
+    );
+}
@@ -7,6 +7,7 @@
 #[path = "./logger_test.rs"]
 mod logger_test;
 
+use environment::secrets;
 use fern;
 use log::{Level, LevelFilter};
 use std::env;
@@ -74,7 +75,8 @@ pub(crate) fn init(level_name: &str) {
         .format(|out, message, record| {
             let name = env!("CARGO_PKG_NAME");
             let record_level = record.level();
-            out.finish(format_args!("[{}] {} - {}", &name, record_level, message));
+            let text = secrets::redact(&message.to_string());
+            out.finish(format_args!("[{}] {} - {}", &name, record_level, &text));
 
             if record_level == Level::Error {
                 warn!("Build Failed.");
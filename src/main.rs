@@ -128,6 +128,7 @@ extern crate indexmap;
 #[macro_use]
 extern crate log;
 extern crate rand;
+extern crate regex;
 extern crate run_script;
 extern crate rust_info;
 extern crate semver;
@@ -152,6 +153,8 @@ mod logger;
 mod runner;
 mod scriptengine;
 mod storage;
+mod toolchain;
+mod toollock;
 mod version;
 
 #[cfg(test)]
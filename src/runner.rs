@@ -5,6 +5,7 @@
 //!
 //! * Load env variables
 //! * Create an execution plan based on the requested task and its dependencies
+//! * Install the dependencies of all tasks if requested
 //! * Run all tasks defined in the execution plan
 //!
 
@@ -23,10 +24,19 @@ use std::collections::HashSet;
 use std::env;
 use std::path;
 use std::time::SystemTime;
-use types::{CliArgs, Config, CrateInfo, EnvInfo, EnvValue, ExecutionPlan, FlowInfo, Step, Task};
+use types::{
+    CliArgs, Config, CrateInfo, EnvInfo, EnvValue, ExecutionPlan, FlowInfo, InstallCrate, Step,
+    Task,
+};
 
 fn validate_condition(flow_info: &FlowInfo, step: &Step) -> bool {
-    condition::validate_condition(&flow_info, &step)
+    match condition::validate_condition(&flow_info, &step) {
+        Ok(_) => true,
+        Err(reason) => {
+            info!("Skipping task {}: {}", &step.name, reason);
+            false
+        }
+    }
 }
 
 fn run_sub_task(flow_info: &FlowInfo, sub_task: &str) {
@@ -55,12 +65,17 @@ fn run_task(flow_info: &FlowInfo, step: &Step) {
 
         let updated_step = environment::expand_env(&step);
 
-        installer::install(&updated_step.config);
+        match get_install_key(&updated_step.config) {
+            Some(ref install_key) if flow_info.installed.contains(install_key) => {
+                debug!("Dependencies of task: {} already installed.", &step.name)
+            }
+            _ => installer::install(&updated_step.config),
+        };
 
         match step.config.run_task {
             Some(ref sub_task) => run_sub_task(&flow_info, sub_task),
             None => {
-                let revert_directory = match step.config.cwd {
+                let revert_directory = match updated_step.config.cwd {
                     Some(ref cwd) => {
                         if cwd.len() > 0 {
                             let directory =
@@ -91,14 +106,12 @@ fn run_task(flow_info: &FlowInfo, step: &Step) {
                 };
 
                 // revert to original cwd
-                match step.config.cwd {
+                match updated_step.config.cwd {
                     Some(_) => environment::setup_cwd(Some(&revert_directory)),
                     _ => (),
                 };
             }
         };
-    } else {
-        debug!("Task: {} disabled", &step.name);
     }
 }
 
@@ -208,7 +221,9 @@ fn update_member_path(member: &str) -> String {
     member_path
 }
 
-fn create_workspace_task(crate_info: CrateInfo, task: &str) -> Task {
+/// Creates the task which invokes the requested task in all workspace members.<br>
+/// In install only mode the members are invoked with --install-only as the task installation script.
+fn create_workspace_task(crate_info: CrateInfo, task: &str, install_only: bool) -> Task {
     let workspace = crate_info.workspace.unwrap();
     let members = workspace.members.unwrap_or(vec![]);
 
@@ -236,6 +251,9 @@ fn create_workspace_task(crate_info: CrateInfo, task: &str) -> Task {
             let mut make_line =
                 "cargo make --disable-check-for-updates --no-on-error --loglevel=".to_string();
             make_line.push_str(&log_level);
+            if install_only {
+                make_line.push_str(" --install-only");
+            }
             make_line.push_str(" ");
             make_line.push_str(&task);
             script_lines.push(make_line);
@@ -270,13 +288,17 @@ fn create_workspace_task(crate_info: CrateInfo, task: &str) -> Task {
     };
 
     let mut workspace_task = Task::new();
-    workspace_task.script = Some(script_lines);
+    if install_only {
+        workspace_task.install_script = Some(script_lines);
+    } else {
+        workspace_task.script = Some(script_lines);
+    }
     workspace_task.env = task_env;
 
     workspace_task
 }
 
-fn create_proxy_task(task: &str) -> Task {
+fn create_proxy_task(task: &str, install_first: bool) -> Task {
     //get log level name
     let log_level = logger::get_log_level();
 
@@ -304,6 +326,10 @@ fn create_proxy_task(task: &str) -> Task {
         _ => {}
     };
 
+    if install_first {
+        args.push("--install-first".to_string());
+    }
+
     args.push(task.to_string());
 
     let mut proxy_task = Task::new();
@@ -345,6 +371,7 @@ fn create_execution_plan(
     task: &str,
     disable_workspace: bool,
     allow_private: bool,
+    install_only: bool,
 ) -> ExecutionPlan {
     let mut task_names = HashSet::new();
     let mut steps = Vec::new();
@@ -374,7 +401,7 @@ fn create_execution_plan(
     let workspace_flow = is_workspace_flow(&config, &task, disable_workspace, &crate_info);
 
     if workspace_flow {
-        let workspace_task = create_workspace_task(crate_info, task);
+        let workspace_task = create_workspace_task(crate_info, task, install_only);
 
         steps.push(Step {
             name: "workspace".to_string(),
@@ -420,14 +447,15 @@ fn run_flow(flow_info: &FlowInfo, allow_private: bool) {
         &flow_info.task,
         flow_info.disable_workspace,
         allow_private,
+        false,
     );
     debug!("Created execution plan: {:#?}", &execution_plan);
 
     run_task_flow(&flow_info, &execution_plan);
 }
 
-fn run_protected_flow(flow_info: &FlowInfo) {
-    let proxy_task = create_proxy_task(&flow_info.task);
+fn run_protected_flow(flow_info: &FlowInfo, install_first: bool) {
+    let proxy_task = create_proxy_task(&flow_info.task, install_first);
 
     let exit_code = command::run_command(&proxy_task.command.unwrap(), &proxy_task.args, false);
 
@@ -447,27 +475,217 @@ fn run_protected_flow(flow_info: &FlowInfo) {
     }
 }
 
+/// Returns the cargo command used to detect if the crate is installed (same logic as the installer).
+fn get_install_cargo_command(task: &Task) -> Option<String> {
+    let cargo_command = match task.args {
+        Some(ref args) => args.first().cloned(),
+        None => None,
+    };
+
+    match task.install_crate {
+        Some(InstallCrate::Value(_)) => cargo_command,
+        Some(_) => None,
+        None => match (&task.install_script, &task.command) {
+            (&None, &Some(ref command)) if command == "cargo" => cargo_command,
+            _ => None,
+        },
+    }
+}
+
+/// Returns the key which identifies the installation requirement of the task or None if
+/// the task has nothing to install.
+fn get_install_key(task: &Task) -> Option<String> {
+    let cargo_command = get_install_cargo_command(&task);
+
+    if task.install_crate.is_none()
+        && task.install_script.is_none()
+        && task.toolchain.is_none()
+        && cargo_command.is_none()
+    {
+        None
+    } else {
+        Some(format!(
+            "{:?}",
+            (
+                &task.install_crate,
+                &task.install_crate_args,
+                &task.install_script,
+                &task.script_runner,
+                &task.toolchain,
+                &cargo_command,
+            )
+        ))
+    }
+}
+
+/// Returns a task which only holds the installation attributes of the provided task.
+fn get_install_task(task: &Task) -> Task {
+    let mut install_task = Task::new();
+
+    install_task.env = task.env.clone();
+    install_task.force = task.force.clone();
+    install_task.install_crate = task.install_crate.clone();
+    install_task.install_crate_args = task.install_crate_args.clone();
+    install_task.install_script = task.install_script.clone();
+    install_task.toolchain = task.toolchain.clone();
+    install_task.script_runner = task.script_runner.clone();
+    install_task.command = task.command.clone();
+    install_task.args = match get_install_cargo_command(&task) {
+        Some(cargo_command) => Some(vec![cargo_command]),
+        None => None,
+    };
+
+    install_task
+}
+
+/// Collects the installation steps of all tasks in the execution plan (including sub tasks).<br>
+/// The task env is set before the task is expanded, tasks which do not meet their condition criteria
+/// or have nothing to install are skipped and duplicate installations are only added once.
+fn collect_install_steps(
+    flow_info: &FlowInfo,
+    execution_plan: &ExecutionPlan,
+    install_steps: &mut Vec<Step>,
+    installations: &mut HashSet<String>,
+    sub_tasks: &mut HashSet<String>,
+) {
+    for step in &execution_plan.steps {
+        if condition::validate_criteria(&flow_info, &step).is_ok() {
+            match step.config.run_task {
+                Some(ref sub_task) => {
+                    if sub_tasks.insert(sub_task.to_string()) {
+                        let sub_execution_plan = create_execution_plan(
+                            &flow_info.config,
+                            &sub_task,
+                            flow_info.disable_workspace,
+                            true,
+                            true,
+                        );
+
+                        collect_install_steps(
+                            &flow_info,
+                            &sub_execution_plan,
+                            install_steps,
+                            installations,
+                            sub_tasks,
+                        );
+                    }
+                }
+                None => {
+                    let env = match step.config.env {
+                        Some(ref env) => env.clone(),
+                        None => IndexMap::new(),
+                    };
+                    environment::set_env(env);
+
+                    let updated_step = environment::expand_env(&step);
+
+                    match get_install_key(&updated_step.config) {
+                        Some(install_key) => {
+                            if installations.insert(install_key) {
+                                install_steps.push(Step {
+                                    name: step.name.clone(),
+                                    config: get_install_task(&updated_step.config),
+                                });
+                            }
+                        }
+                        None => debug!("Task: {} has nothing to install.", &step.name),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Restores the env to the provided variables (variables which were added later are removed).
+fn restore_env(variables: &Vec<(String, String)>) {
+    let keys: HashSet<&String> = variables.iter().map(|variable| &variable.0).collect();
+
+    for (key, _) in env::vars() {
+        if !keys.contains(&key) {
+            env::remove_var(&key);
+        }
+    }
+
+    for &(ref key, ref value) in variables {
+        env::set_var(&key, &value);
+    }
+}
+
+/// Installs the dependencies of all tasks in the execution plan before any task is invoked.<br>
+/// The env of each task is set during the installation and reverted once all dependencies are installed.<br>
+/// Returns the installation keys of the installed dependencies.
+fn install_dependencies(flow_info: &FlowInfo, execution_plan: &ExecutionPlan) -> HashSet<String> {
+    let original_env: Vec<(String, String)> = env::vars().collect();
+
+    let mut install_steps = vec![];
+    let mut installations = HashSet::new();
+    collect_install_steps(
+        &flow_info,
+        &execution_plan,
+        &mut install_steps,
+        &mut installations,
+        &mut HashSet::new(),
+    );
+
+    for step in &install_steps {
+        info!("Installing Dependencies For Task: {}", &step.name);
+
+        match step.config.env {
+            Some(ref env) => environment::set_env(env.clone()),
+            None => (),
+        };
+
+        installer::install(&step.config);
+    }
+
+    restore_env(&original_env);
+
+    installations
+}
+
 /// Runs the requested tasks.<br>
 /// The flow is as follows:
 ///
 /// * Create an execution plan based on the requested task and its dependencies
+/// * Install the dependencies of all tasks if requested (--install-first/--install-only)
 /// * Run all tasks defined in the execution plan
 pub(crate) fn run(config: Config, task: &str, env_info: EnvInfo, cli_args: &CliArgs) {
     let start_time = SystemTime::now();
 
-    let flow_info = FlowInfo {
+    let mut flow_info = FlowInfo {
         config,
         task: task.to_string(),
         env_info,
         disable_workspace: cli_args.disable_workspace,
         disable_on_error: cli_args.disable_on_error,
         cli_arguments: cli_args.arguments.clone(),
+        installed: HashSet::new(),
     };
 
-    if flow_info.disable_on_error || flow_info.config.config.on_error_task.is_none() {
-        run_flow(&flow_info, false);
+    let protected_flow =
+        !flow_info.disable_on_error && flow_info.config.config.on_error_task.is_some();
+
+    // the protected flow installs the dependencies in its own process
+    if cli_args.install_only || (cli_args.install_first && !protected_flow) {
+        // workspace members only install their dependencies so all members are installed before any is invoked
+        let execution_plan = create_execution_plan(
+            &flow_info.config,
+            &flow_info.task,
+            flow_info.disable_workspace,
+            false,
+            true,
+        );
+        debug!("Created execution plan: {:#?}", &execution_plan);
+
+        flow_info.installed = install_dependencies(&flow_info, &execution_plan);
+    }
+
+    if cli_args.install_only {
+        info!("Dependencies installed, tasks will not be invoked.");
+    } else if protected_flow {
+        run_protected_flow(&flow_info, cli_args.install_first);
     } else {
-        run_protected_flow(&flow_info);
+        run_flow(&flow_info, false);
     }
 
     let time_string = match start_time.elapsed() {
@@ -484,10 +702,48 @@ pub(crate) fn run(config: Config, task: &str, env_info: EnvInfo, cli_args: &CliA
     info!("Build Done {}.", &time_string);
 }
 
+/// Returns the skip prediction of all steps which can be evaluated without running
+/// the condition scripts.
+fn get_predicted_skips(flow_info: &FlowInfo, execution_plan: &ExecutionPlan) -> Vec<String> {
+    let mut skips = vec![];
+
+    for step in &execution_plan.steps {
+        match condition::validate_criteria(&flow_info, &step) {
+            Err(reason) => skips.push(format!("Skipping task {}: {}", &step.name, reason)),
+            _ => (),
+        };
+    }
+
+    skips
+}
+
 /// Only prints the execution plan
-pub(crate) fn print(config: &Config, task: &str, disable_workspace: bool) {
-    let execution_plan = create_execution_plan(&config, &task, disable_workspace, false);
+pub(crate) fn print(config: Config, task: &str, env_info: EnvInfo, cli_args: &CliArgs) {
+    let execution_plan = create_execution_plan(
+        &config,
+        &task,
+        cli_args.disable_workspace,
+        false,
+        cli_args.install_only,
+    );
     debug!("Created execution plan: {:#?}", &execution_plan);
 
-    println!("{:#?}", &execution_plan);
+    let plan_text = format!("{:#?}", &execution_plan);
+    println!("{}", environment::secrets::redact(&plan_text));
+
+    let flow_info = FlowInfo {
+        config,
+        task: task.to_string(),
+        env_info,
+        disable_workspace: cli_args.disable_workspace,
+        disable_on_error: cli_args.disable_on_error,
+        cli_arguments: cli_args.arguments.clone(),
+        installed: HashSet::new(),
+    };
+
+    let skips = get_predicted_skips(&flow_info, &execution_plan);
+    if !skips.is_empty() {
+        let skips_text = format!("Predicted skips:\n{}", skips.join("\n"));
+        println!("{}", environment::secrets::redact(&skips_text));
+    }
 }
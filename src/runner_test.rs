@@ -2,6 +2,7 @@ use super::*;
 use indexmap::IndexMap;
 use rust_info::types::RustInfo;
 use std::env;
+use toml;
 use types::{
     ConfigSection, CrateInfo, EnvInfo, EnvValue, FlowInfo, GitInfo, PlatformOverrideTask, Step,
     Task, Workspace,
@@ -97,7 +98,7 @@ fn create_execution_plan_single() {
 
     config.tasks.insert("test".to_string(), task);
 
-    let execution_plan = create_execution_plan(&config, "test", false, true);
+    let execution_plan = create_execution_plan(&config, "test", false, true, false);
     assert_eq!(execution_plan.steps.len(), 3);
     assert_eq!(execution_plan.steps[0].name, "init");
     assert_eq!(execution_plan.steps[1].name, "test");
@@ -123,7 +124,7 @@ fn create_execution_plan_single_disabled() {
 
     config.tasks.insert("test".to_string(), task);
 
-    let execution_plan = create_execution_plan(&config, "test", false, true);
+    let execution_plan = create_execution_plan(&config, "test", false, true, false);
     assert_eq!(execution_plan.steps.len(), 2);
     assert_eq!(execution_plan.steps[0].name, "init");
     assert_eq!(execution_plan.steps[1].name, "end");
@@ -149,7 +150,7 @@ fn create_execution_plan_single_private() {
 
     config.tasks.insert("test-private".to_string(), task);
 
-    create_execution_plan(&config, "test-private", false, false);
+    create_execution_plan(&config, "test-private", false, false, false);
 }
 
 #[test]
@@ -171,7 +172,7 @@ fn create_execution_plan_single_allow_private() {
 
     config.tasks.insert("test-private".to_string(), task);
 
-    let execution_plan = create_execution_plan(&config, "test-private", false, true);
+    let execution_plan = create_execution_plan(&config, "test-private", false, true, false);
     assert_eq!(execution_plan.steps.len(), 3);
     assert_eq!(execution_plan.steps[0].name, "init");
     assert_eq!(execution_plan.steps[1].name, "test-private");
@@ -202,7 +203,7 @@ fn create_execution_plan_with_dependencies() {
         .tasks
         .insert("task_dependency".to_string(), task_dependency);
 
-    let execution_plan = create_execution_plan(&config, "test", false, true);
+    let execution_plan = create_execution_plan(&config, "test", false, true, false);
     assert_eq!(execution_plan.steps.len(), 4);
     assert_eq!(execution_plan.steps[0].name, "init");
     assert_eq!(execution_plan.steps[1].name, "task_dependency");
@@ -235,7 +236,7 @@ fn create_execution_plan_disabled_task_with_dependencies() {
         .tasks
         .insert("task_dependency".to_string(), task_dependency);
 
-    let execution_plan = create_execution_plan(&config, "test", false, true);
+    let execution_plan = create_execution_plan(&config, "test", false, true, false);
     assert_eq!(execution_plan.steps.len(), 2);
     assert_eq!(execution_plan.steps[0].name, "init");
     assert_eq!(execution_plan.steps[1].name, "end");
@@ -266,7 +267,7 @@ fn create_execution_plan_with_dependencies_disabled() {
         .tasks
         .insert("task_dependency".to_string(), task_dependency);
 
-    let execution_plan = create_execution_plan(&config, "test", false, true);
+    let execution_plan = create_execution_plan(&config, "test", false, true, false);
     assert_eq!(execution_plan.steps.len(), 3);
     assert_eq!(execution_plan.steps[0].name, "init");
     assert_eq!(execution_plan.steps[1].name, "test");
@@ -295,10 +296,14 @@ fn create_execution_plan_platform_disabled() {
         env: None,
         cwd: None,
         install_script: None,
+        toolchain: None,
         args: None,
         script: None,
         script_runner: None,
+        script_runner_args: None,
         script_extension: None,
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: None,
         dependencies: None,
     });
@@ -315,10 +320,14 @@ fn create_execution_plan_platform_disabled() {
         env: None,
         cwd: None,
         install_script: None,
+        toolchain: None,
         args: None,
         script: None,
         script_runner: None,
+        script_runner_args: None,
         script_extension: None,
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: None,
         dependencies: None,
     });
@@ -335,17 +344,21 @@ fn create_execution_plan_platform_disabled() {
         env: None,
         cwd: None,
         install_script: None,
+        toolchain: None,
         args: None,
         script: None,
         script_runner: None,
+        script_runner_args: None,
         script_extension: None,
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: None,
         dependencies: None,
     });
 
     config.tasks.insert("test".to_string(), task);
 
-    let execution_plan = create_execution_plan(&config, "test", false, true);
+    let execution_plan = create_execution_plan(&config, "test", false, true, false);
     assert_eq!(execution_plan.steps.len(), 0);
 }
 
@@ -362,7 +375,7 @@ fn create_execution_plan_workspace() {
     config.tasks.insert("test".to_string(), task);
 
     env::set_current_dir("./examples/workspace").unwrap();
-    let execution_plan = create_execution_plan(&config, "test", false, true);
+    let execution_plan = create_execution_plan(&config, "test", false, true, false);
     env::set_current_dir("../../").unwrap();
     assert_eq!(execution_plan.steps.len(), 1);
     assert_eq!(execution_plan.steps[0].name, "workspace");
@@ -381,7 +394,7 @@ fn create_execution_plan_noworkspace() {
     config.tasks.insert("test".to_string(), task);
 
     env::set_current_dir("./examples/workspace").unwrap();
-    let execution_plan = create_execution_plan(&config, "test", true, true);
+    let execution_plan = create_execution_plan(&config, "test", true, true, false);
     env::set_current_dir("../../").unwrap();
     assert_eq!(execution_plan.steps.len(), 1);
     assert_eq!(execution_plan.steps[0].name, "test");
@@ -414,9 +427,10 @@ fn create_workspace_task_no_members() {
     crate_info.workspace = Some(Workspace {
         members: Some(members),
         exclude: None,
+        metadata: None,
     });
 
-    let task = create_workspace_task(crate_info, "some_task");
+    let task = create_workspace_task(crate_info, "some_task", false);
 
     assert!(task.script.is_some());
     let script = task.script.unwrap();
@@ -436,9 +450,10 @@ fn create_workspace_task_with_members() {
     crate_info.workspace = Some(Workspace {
         members: Some(members),
         exclude: None,
+        metadata: None,
     });
 
-    let task = create_workspace_task(crate_info, "some_task");
+    let task = create_workspace_task(crate_info, "some_task", false);
 
     let mut expected_script = r#"cd ./member1
 cargo make --disable-check-for-updates --no-on-error --loglevel=LEVEL_NAME some_task
@@ -460,6 +475,28 @@ cd -"#
     assert!(task.env.is_none());
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn create_workspace_task_install_only() {
+    let mut crate_info = CrateInfo::new();
+    crate_info.workspace = Some(Workspace {
+        members: Some(vec!["member1".to_string()]),
+        exclude: None,
+        metadata: None,
+    });
+
+    let task = create_workspace_task(crate_info, "some_task", true);
+
+    let log_level = logger::get_log_level();
+    let expected_script = format!(
+        "cd ./member1\ncargo make --disable-check-for-updates --no-on-error --loglevel={} --install-only some_task\ncd -",
+        &log_level
+    );
+
+    assert!(task.script.is_none());
+    assert_eq!(task.install_script.unwrap().join("\n"), expected_script);
+}
+
 #[test]
 fn create_workspace_task_extend_workspace_makefile() {
     let mut crate_info = CrateInfo::new();
@@ -467,10 +504,11 @@ fn create_workspace_task_extend_workspace_makefile() {
     crate_info.workspace = Some(Workspace {
         members: Some(members),
         exclude: None,
+        metadata: None,
     });
 
     env::set_var("CARGO_MAKE_EXTEND_WORKSPACE_MAKEFILE", "true");
-    let task = create_workspace_task(crate_info, "some_task");
+    let task = create_workspace_task(crate_info, "some_task", false);
     env::set_var("CARGO_MAKE_EXTEND_WORKSPACE_MAKEFILE", "false");
 
     assert!(task.script.is_some());
@@ -490,7 +528,7 @@ fn create_workspace_task_extend_workspace_makefile() {
 fn create_proxy_task_no_makefile() {
     let makefile = env::var("CARGO_MAKE_MAKEFILE_PATH").unwrap_or("EMPTY".to_string());
     env::remove_var("CARGO_MAKE_MAKEFILE_PATH");
-    let task = create_proxy_task("some_task");
+    let task = create_proxy_task("some_task", false);
     env::set_var("CARGO_MAKE_MAKEFILE_PATH", &makefile);
 
     assert_eq!(task.command.unwrap(), "cargo".to_string());
@@ -513,7 +551,7 @@ fn create_proxy_task_no_makefile() {
 fn create_proxy_task_with_makefile() {
     let makefile = env::var("CARGO_MAKE_MAKEFILE_PATH").unwrap_or("EMPTY".to_string());
     env::set_var("CARGO_MAKE_MAKEFILE_PATH", &makefile);
-    let task = create_proxy_task("some_task");
+    let task = create_proxy_task("some_task", false);
 
     assert_eq!(task.command.unwrap(), "cargo".to_string());
 
@@ -534,6 +572,20 @@ fn create_proxy_task_with_makefile() {
     assert_eq!(args[5], "some_task".to_string());
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn create_proxy_task_install_first() {
+    let makefile = env::var("CARGO_MAKE_MAKEFILE_PATH").unwrap_or("EMPTY".to_string());
+    env::remove_var("CARGO_MAKE_MAKEFILE_PATH");
+    let task = create_proxy_task("some_task", true);
+    env::set_var("CARGO_MAKE_MAKEFILE_PATH", &makefile);
+
+    let args = task.args.unwrap();
+    assert_eq!(args.len(), 6);
+    assert_eq!(args[4], "--install-first".to_string());
+    assert_eq!(args[5], "some_task".to_string());
+}
+
 #[test]
 #[should_panic]
 fn run_task_bad_script() {
@@ -553,6 +605,7 @@ fn run_task_bad_script() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -585,6 +638,7 @@ fn run_task_script_with_args_error() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: Some(vec!["1".to_string()]),
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -616,6 +670,7 @@ fn run_task_script_with_args_valid() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: Some(vec!["0".to_string()]),
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -646,6 +701,7 @@ fn run_task_command() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -678,6 +734,7 @@ fn run_task_bad_command_valid_script() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -709,6 +766,7 @@ fn run_task_no_command_valid_script() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -746,6 +804,7 @@ fn run_task_bad_run_task_valid_command() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -784,6 +843,7 @@ fn run_task_valid_run_task() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -815,6 +875,7 @@ fn run_task_invalid_task() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -828,6 +889,83 @@ fn run_task_invalid_task() {
     run_task(&flow_info, &step);
 }
 
+#[test]
+fn run_task_installed() {
+    let config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+
+    let mut task = Task::new();
+    task.install_script = Some(vec!["exit 1".to_string()]);
+    task.command = Some("echo".to_string());
+
+    let mut installed = HashSet::new();
+    installed.insert(get_install_key(&task).unwrap());
+
+    let flow_info = FlowInfo {
+        config,
+        task: "test".to_string(),
+        env_info: EnvInfo {
+            rust_info: RustInfo::new(),
+            crate_info: CrateInfo::new(),
+            git_info: GitInfo::new(),
+        },
+        disable_workspace: false,
+        disable_on_error: false,
+        cli_arguments: None,
+        installed,
+    };
+
+    let step = Step {
+        name: "test".to_string(),
+        config: task,
+    };
+
+    run_task(&flow_info, &step);
+}
+
+#[test]
+#[should_panic]
+fn run_task_installed_other_dependencies() {
+    let config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+
+    let mut installed_task = Task::new();
+    installed_task.install_script = Some(vec!["exit 0".to_string()]);
+
+    let mut installed = HashSet::new();
+    installed.insert(get_install_key(&installed_task).unwrap());
+
+    let flow_info = FlowInfo {
+        config,
+        task: "test".to_string(),
+        env_info: EnvInfo {
+            rust_info: RustInfo::new(),
+            crate_info: CrateInfo::new(),
+            git_info: GitInfo::new(),
+        },
+        disable_workspace: false,
+        disable_on_error: false,
+        cli_arguments: None,
+        installed,
+    };
+
+    let mut task = Task::new();
+    task.install_script = Some(vec!["exit 1".to_string()]);
+    task.command = Some("echo".to_string());
+    let step = Step {
+        name: "test".to_string(),
+        config: task,
+    };
+
+    run_task(&flow_info, &step);
+}
+
 #[test]
 fn run_task_set_env() {
     let config = Config {
@@ -846,6 +984,7 @@ fn run_task_set_env() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut env = IndexMap::new();
@@ -889,6 +1028,7 @@ fn run_task_cwd_no_such_dir() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -920,6 +1060,7 @@ fn run_task_cwd_dir_exists() {
         disable_workspace: false,
         disable_on_error: false,
         cli_arguments: None,
+        installed: HashSet::new(),
     };
 
     let mut task = Task::new();
@@ -1021,6 +1162,7 @@ fn is_workspace_flow_disabled_via_cli() {
     crate_info.workspace = Some(Workspace {
         members: Some(members),
         exclude: None,
+        metadata: None,
     });
 
     let mut task = Task::new();
@@ -1045,6 +1187,7 @@ fn is_workspace_flow_disabled_via_task() {
     crate_info.workspace = Some(Workspace {
         members: Some(members),
         exclude: None,
+        metadata: None,
     });
 
     let mut task = Task::new();
@@ -1061,3 +1204,324 @@ fn is_workspace_flow_disabled_via_task() {
 
     assert!(!workspace_flow);
 }
+
+#[test]
+fn get_predicted_skips_static_criteria() {
+    env::remove_var("RUNNER_TEST_PREDICTED_SKIP_NOT_SET");
+
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+
+    let mut skipped_task = Task::new();
+    skipped_task.condition =
+        Some(toml::from_str(r#"env_set = ["RUNNER_TEST_PREDICTED_SKIP_NOT_SET"]"#).unwrap());
+    config.tasks.insert("skipped".to_string(), skipped_task);
+
+    // condition scripts are not evaluated
+    let mut script_task = Task::new();
+    script_task.condition_script = Some(vec!["exit 1".to_string()]);
+    config.tasks.insert("script".to_string(), script_task);
+
+    let mut task = Task::new();
+    task.dependencies = Some(vec!["skipped".to_string(), "script".to_string()]);
+    config.tasks.insert("test".to_string(), task);
+
+    let execution_plan = create_execution_plan(&config, "test", false, true, false);
+
+    let flow_info = FlowInfo {
+        config,
+        task: "test".to_string(),
+        env_info: EnvInfo {
+            rust_info: RustInfo::new(),
+            crate_info: CrateInfo::new(),
+            git_info: GitInfo::new(),
+        },
+        disable_workspace: false,
+        disable_on_error: false,
+        cli_arguments: None,
+        installed: HashSet::new(),
+    };
+
+    let skips = get_predicted_skips(&flow_info, &execution_plan);
+
+    assert_eq!(
+        skips,
+        vec![
+            "Skipping task skipped: env_set condition not met, env RUNNER_TEST_PREDICTED_SKIP_NOT_SET is not set"
+        ]
+    );
+}
+
+#[test]
+fn get_install_task_only_install_attributes() {
+    let mut task = Task::new();
+    task.description = Some("description".to_string());
+    task.install_script = Some(vec!["echo 1".to_string()]);
+    task.toolchain = Some("nightly".to_string());
+    task.command = Some("cargo".to_string());
+    task.args = Some(vec!["test".to_string(), "--verbose".to_string()]);
+    task.dependencies = Some(vec!["A".to_string()]);
+
+    let install_task = get_install_task(&task);
+
+    assert!(install_task.description.is_none());
+    assert!(install_task.dependencies.is_none());
+    assert_eq!(install_task.install_script.unwrap().len(), 1);
+    assert_eq!(install_task.toolchain.unwrap(), "nightly");
+    assert_eq!(install_task.command.unwrap(), "cargo");
+    assert!(install_task.args.is_none());
+}
+
+#[test]
+fn get_install_task_cargo_command() {
+    let mut task = Task::new();
+    task.command = Some("cargo".to_string());
+    task.args = Some(vec!["audit".to_string(), "--verbose".to_string()]);
+
+    let install_task = get_install_task(&task);
+
+    assert_eq!(install_task.command.unwrap(), "cargo");
+    assert_eq!(install_task.args.unwrap(), vec!["audit"]);
+}
+
+#[test]
+fn get_install_key_nothing_to_install() {
+    let mut task = Task::new();
+    task.command = Some("echo".to_string());
+    task.args = Some(vec!["test".to_string()]);
+
+    assert!(get_install_key(&task).is_none());
+}
+
+#[test]
+fn get_install_key_cargo_command() {
+    let mut build_task = Task::new();
+    build_task.command = Some("cargo".to_string());
+    build_task.args = Some(vec!["build".to_string()]);
+
+    let mut build_verbose_task = build_task.clone();
+    build_verbose_task.args = Some(vec!["build".to_string(), "--verbose".to_string()]);
+
+    let mut test_task = build_task.clone();
+    test_task.args = Some(vec!["test".to_string()]);
+
+    let build_key = get_install_key(&build_task);
+
+    assert!(build_key.is_some());
+    assert_eq!(build_key, get_install_key(&build_verbose_task));
+    assert!(build_key != get_install_key(&test_task));
+}
+
+#[test]
+fn get_install_key_cargo_command_with_install_script() {
+    let mut task = Task::new();
+    task.command = Some("cargo".to_string());
+    task.args = Some(vec!["build".to_string()]);
+    task.install_script = Some(vec!["echo 1".to_string()]);
+
+    let mut other_task = task.clone();
+    other_task.args = Some(vec!["test".to_string()]);
+
+    assert!(get_install_key(&task).is_some());
+    assert_eq!(get_install_key(&task), get_install_key(&other_task));
+}
+
+#[test]
+fn collect_install_steps_unique_with_sub_tasks() {
+    env::remove_var("RUNNER_TEST_INSTALL_SKIP_NOT_SET");
+
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+
+    let mut install_task = Task::new();
+    install_task.install_script = Some(vec!["echo 1".to_string()]);
+    config
+        .tasks
+        .insert("install1".to_string(), install_task.clone());
+    config.tasks.insert("install2".to_string(), install_task);
+
+    let mut skipped_task = Task::new();
+    skipped_task.install_script = Some(vec!["echo 2".to_string()]);
+    skipped_task.condition =
+        Some(toml::from_str(r#"env_set = ["RUNNER_TEST_INSTALL_SKIP_NOT_SET"]"#).unwrap());
+    config.tasks.insert("skipped".to_string(), skipped_task);
+
+    let mut sub_task = Task::new();
+    sub_task.install_script = Some(vec!["echo 3".to_string()]);
+    config.tasks.insert("sub".to_string(), sub_task);
+
+    let mut run_task = Task::new();
+    run_task.run_task = Some("sub".to_string());
+    config.tasks.insert("run".to_string(), run_task);
+
+    let mut build_task = Task::new();
+    build_task.command = Some("cargo".to_string());
+    build_task.args = Some(vec!["build".to_string()]);
+    config
+        .tasks
+        .insert("build1".to_string(), build_task.clone());
+    build_task.args = Some(vec!["build".to_string(), "--release".to_string()]);
+    config.tasks.insert("build2".to_string(), build_task);
+
+    let mut task = Task::new();
+    task.dependencies = Some(vec![
+        "install1".to_string(),
+        "install2".to_string(),
+        "skipped".to_string(),
+        "run".to_string(),
+        "build1".to_string(),
+        "build2".to_string(),
+    ]);
+    config.tasks.insert("test".to_string(), task);
+
+    let execution_plan = create_execution_plan(&config, "test", false, true, false);
+
+    let flow_info = FlowInfo {
+        config,
+        task: "test".to_string(),
+        env_info: EnvInfo {
+            rust_info: RustInfo::new(),
+            crate_info: CrateInfo::new(),
+            git_info: GitInfo::new(),
+        },
+        disable_workspace: false,
+        disable_on_error: false,
+        cli_arguments: None,
+        installed: HashSet::new(),
+    };
+
+    let mut install_steps = vec![];
+    collect_install_steps(
+        &flow_info,
+        &execution_plan,
+        &mut install_steps,
+        &mut HashSet::new(),
+        &mut HashSet::new(),
+    );
+
+    let names: Vec<String> = install_steps.iter().map(|step| step.name.clone()).collect();
+    assert_eq!(names, vec!["install1", "sub", "build1"]);
+    assert_eq!(
+        install_steps[1].config.install_script.clone().unwrap(),
+        vec!["echo 3"]
+    );
+}
+
+#[test]
+fn collect_install_steps_task_env() {
+    env::remove_var("RUNNER_TEST_INSTALL_TASK_ENV");
+
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+
+    let mut env = IndexMap::new();
+    env.insert(
+        "RUNNER_TEST_INSTALL_TASK_ENV".to_string(),
+        EnvValue::Value("audit".to_string()),
+    );
+
+    let mut task = Task::new();
+    task.env = Some(env);
+    task.install_crate = Some(InstallCrate::Value("cargo-audit".to_string()));
+    task.command = Some("cargo".to_string());
+    task.args = Some(vec!["${RUNNER_TEST_INSTALL_TASK_ENV:?missing}".to_string()]);
+    config.tasks.insert("test".to_string(), task);
+
+    let execution_plan = create_execution_plan(&config, "test", false, true, false);
+
+    let flow_info = FlowInfo {
+        config,
+        task: "test".to_string(),
+        env_info: EnvInfo {
+            rust_info: RustInfo::new(),
+            crate_info: CrateInfo::new(),
+            git_info: GitInfo::new(),
+        },
+        disable_workspace: false,
+        disable_on_error: false,
+        cli_arguments: None,
+        installed: HashSet::new(),
+    };
+
+    let mut install_steps = vec![];
+    collect_install_steps(
+        &flow_info,
+        &execution_plan,
+        &mut install_steps,
+        &mut HashSet::new(),
+        &mut HashSet::new(),
+    );
+    env::remove_var("RUNNER_TEST_INSTALL_TASK_ENV");
+
+    assert_eq!(install_steps.len(), 1);
+    assert_eq!(install_steps[0].config.args.clone().unwrap(), vec!["audit"]);
+    assert!(install_steps[0].config.env.is_some());
+}
+
+#[test]
+fn install_dependencies_revert_env() {
+    env::remove_var("RUNNER_TEST_INSTALL_DEPENDENCIES_ENV");
+
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env: IndexMap::new(),
+        tasks: IndexMap::new(),
+    };
+
+    let mut env = IndexMap::new();
+    env.insert(
+        "RUNNER_TEST_INSTALL_DEPENDENCIES_ENV".to_string(),
+        EnvValue::Value("1".to_string()),
+    );
+
+    let mut task = Task::new();
+    task.env = Some(env);
+    task.install_script = Some(vec!["exit 0".to_string()]);
+    config.tasks.insert("test".to_string(), task);
+
+    let execution_plan = create_execution_plan(&config, "test", false, true, true);
+
+    let flow_info = FlowInfo {
+        config,
+        task: "test".to_string(),
+        env_info: EnvInfo {
+            rust_info: RustInfo::new(),
+            crate_info: CrateInfo::new(),
+            git_info: GitInfo::new(),
+        },
+        disable_workspace: false,
+        disable_on_error: false,
+        cli_arguments: None,
+        installed: HashSet::new(),
+    };
+
+    let installed = install_dependencies(&flow_info, &execution_plan);
+
+    assert_eq!(installed.len(), 1);
+    assert!(env::var("RUNNER_TEST_INSTALL_DEPENDENCIES_ENV").is_err());
+}
+
+#[test]
+fn restore_env_added_and_updated() {
+    env::set_var("RUNNER_TEST_RESTORE_ENV_UPDATED", "1");
+    env::remove_var("RUNNER_TEST_RESTORE_ENV_ADDED");
+
+    let variables: Vec<(String, String)> = env::vars().collect();
+
+    env::set_var("RUNNER_TEST_RESTORE_ENV_UPDATED", "2");
+    env::set_var("RUNNER_TEST_RESTORE_ENV_ADDED", "1");
+
+    restore_env(&variables);
+
+    assert_eq!(env::var("RUNNER_TEST_RESTORE_ENV_UPDATED").unwrap(), "1");
+    assert!(env::var("RUNNER_TEST_RESTORE_ENV_ADDED").is_err());
+}
@@ -0,0 +1,627 @@
+//! # cmscript
+//!
+//! Runs scripts written in a minimal shell like language.<br>
+//! All commands are built in and implemented in rust so scripts behave the same on all platforms.
+//!
+
+#[cfg(test)]
+#[path = "./cmscript_test.rs"]
+mod cmscript_test;
+
+use environment;
+use glob::glob;
+use indexmap::IndexMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A single script line token
+struct Token {
+    /// The token text (without the quotes)
+    value: String,
+    /// True if the token contains quoted text
+    quoted: bool,
+    /// True if the token contains single quoted text and should not be expanded
+    literal: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A parsed script statement
+enum Statement {
+    /// A single command invocation
+    Command(Vec<Token>),
+    /// Conditional block
+    If {
+        condition: Vec<Token>,
+        then_block: Vec<Statement>,
+        else_block: Vec<Statement>,
+    },
+    /// Loop over a list of values
+    For {
+        name: String,
+        values: Vec<Token>,
+        block: Vec<Statement>,
+    },
+}
+
+/// Holds the script runtime state
+struct Context {
+    /// The script variables
+    variables: IndexMap<String, Vec<String>>,
+    /// The script cli arguments
+    arguments: Vec<String>,
+}
+
+fn create_token(value: &str, quoted: bool, literal: bool) -> Token {
+    Token {
+        value: value.to_string(),
+        quoted,
+        literal,
+    }
+}
+
+fn tokenize(line: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut value = String::new();
+    let mut in_token = false;
+    let mut quoted = false;
+    let mut literal = false;
+    let mut quote: Option<char> = None;
+
+    for character in line.chars() {
+        match quote {
+            Some(quote_character) => {
+                if character == quote_character {
+                    quote = None;
+                } else {
+                    value.push(character);
+                }
+            }
+            None => {
+                if character.is_whitespace() {
+                    if in_token {
+                        tokens.push(create_token(&value, quoted, literal));
+
+                        value.clear();
+                        in_token = false;
+                        quoted = false;
+                        literal = false;
+                    }
+                } else if character == '"' || character == '\'' {
+                    quote = Some(character);
+                    in_token = true;
+                    quoted = true;
+
+                    if character == '\'' {
+                        literal = true;
+                    }
+                } else {
+                    value.push(character);
+                    in_token = true;
+                }
+            }
+        }
+    }
+
+    if quote.is_some() {
+        Err(format!("Missing closing quote in line: {}", line))
+    } else {
+        if in_token {
+            tokens.push(create_token(&value, quoted, literal));
+        }
+
+        Ok(tokens)
+    }
+}
+
+fn get_lines(script: &Vec<String>) -> Result<Vec<Vec<Token>>, String> {
+    let mut lines = vec![];
+
+    let text = script.join("\n");
+    for line in text.lines() {
+        let trimmed_line = line.trim();
+
+        if trimmed_line.len() > 0 && !trimmed_line.starts_with("#") {
+            let tokens = tokenize(trimmed_line)?;
+            lines.push(tokens);
+        }
+    }
+
+    Ok(lines)
+}
+
+fn get_keyword(tokens: &Vec<Token>) -> Option<String> {
+    if tokens[0].quoted {
+        None
+    } else {
+        Some(tokens[0].value.clone())
+    }
+}
+
+/// Parses the lines until an else/end keyword or the end of the script and returns the parsed
+/// statements and the keyword which ended the block (if any).
+fn parse_block(
+    lines: &Vec<Vec<Token>>,
+    index: &mut usize,
+) -> Result<(Vec<Statement>, Option<String>), String> {
+    let mut statements = vec![];
+
+    while *index < lines.len() {
+        let tokens = lines[*index].clone();
+        *index = *index + 1;
+
+        let keyword = get_keyword(&tokens).unwrap_or("".to_string());
+
+        if keyword == "else" || keyword == "end" {
+            return Ok((statements, Some(keyword)));
+        } else if keyword == "if" {
+            let condition = tokens[1..].to_vec();
+
+            let (then_block, terminator) = parse_block(lines, index)?;
+            let else_block = match terminator {
+                Some(ref value) if value == "else" => {
+                    let (else_block, else_terminator) = parse_block(lines, index)?;
+
+                    match else_terminator {
+                        Some(ref value) if value == "end" => else_block,
+                        _ => return Err("Missing end for if block.".to_string()),
+                    }
+                }
+                Some(_) => vec![],
+                None => return Err("Missing end for if block.".to_string()),
+            };
+
+            statements.push(Statement::If {
+                condition,
+                then_block,
+                else_block,
+            });
+        } else if keyword == "for" {
+            if tokens.len() < 3 || tokens[2].value != "in" {
+                return Err("Invalid for loop, expected: for <name> in <values>".to_string());
+            }
+
+            let name = tokens[1].value.clone();
+            let values = tokens[3..].to_vec();
+
+            let (block, terminator) = parse_block(lines, index)?;
+            match terminator {
+                Some(ref value) if value == "end" => statements.push(Statement::For {
+                    name,
+                    values,
+                    block,
+                }),
+                _ => return Err("Missing end for for loop.".to_string()),
+            };
+        } else {
+            statements.push(Statement::Command(tokens));
+        }
+    }
+
+    Ok((statements, None))
+}
+
+fn parse(script: &Vec<String>) -> Result<Vec<Statement>, String> {
+    let lines = get_lines(script)?;
+
+    let mut index = 0;
+    let (statements, terminator) = parse_block(&lines, &mut index)?;
+
+    match terminator {
+        Some(keyword) => Err(format!("Unexpected {} found.", keyword)),
+        None => Ok(statements),
+    }
+}
+
+/// Returns the variable name if the provided value is a single variable reference.
+fn get_variable_name(value: &str) -> Option<String> {
+    if value.starts_with("${") && value.ends_with("}") && value.len() > 3 {
+        let name = &value[2..value.len() - 1];
+
+        if name.contains("$") || name.contains("{") || name.contains(":") {
+            None
+        } else {
+            Some(name.to_string())
+        }
+    } else {
+        None
+    }
+}
+
+/// Returns the value of the script variable or argument (${@} or ${1}, ${2}, ...).
+fn get_context_value(name: &str, context: &Context) -> Option<String> {
+    if name == "@" {
+        Some(context.arguments.join(" "))
+    } else {
+        match context.variables.get(name) {
+            Some(values) => Some(values.join(" ")),
+            None => match name.parse::<usize>() {
+                Ok(index) if index > 0 => context.arguments.get(index - 1).cloned(),
+                _ => None,
+            },
+        }
+    }
+}
+
+fn expand_text(text: &str, context: &Context) -> String {
+    environment::expand_value_with_variables(&text, &|name: &str| get_context_value(name, context))
+}
+
+/// Expands all variables in the provided tokens.<br>
+/// Unquoted tokens which only reference a list variable are expanded to all list values.
+fn expand_tokens(tokens: &[Token], context: &Context) -> Vec<String> {
+    let mut values = vec![];
+
+    for token in tokens.iter() {
+        if token.literal {
+            values.push(token.value.clone());
+        } else {
+            let variable_name = if token.quoted {
+                None
+            } else {
+                get_variable_name(&token.value)
+            };
+
+            match variable_name {
+                Some(ref name) if name == "@" => values.extend(context.arguments.iter().cloned()),
+                Some(ref name) if context.variables.contains_key(name) => {
+                    values.extend(context.variables[name].iter().cloned())
+                }
+                _ => values.push(expand_text(&token.value, context)),
+            }
+        }
+    }
+
+    values
+}
+
+/// Splits the leading flags (for example -rf) from the rest of the arguments.
+fn split_flags(args: &[String]) -> (String, Vec<String>) {
+    let mut flags = String::new();
+    let mut values = vec![];
+
+    for arg in args.iter() {
+        if values.is_empty() && arg.starts_with("-") && arg.len() > 1 {
+            flags.push_str(&arg[1..]);
+        } else {
+            values.push(arg.clone());
+        }
+    }
+
+    (flags, values)
+}
+
+fn evaluate_condition(args: &[String]) -> Result<bool, String> {
+    let (negate, condition_args) = if args.len() > 0 && args[0] == "not" {
+        (true, &args[1..])
+    } else {
+        (false, args)
+    };
+
+    if condition_args.is_empty() {
+        return Err("Missing if condition.".to_string());
+    }
+
+    let values = &condition_args[1..];
+    let result = match condition_args[0].as_str() {
+        "exists" | "is_file" | "is_dir" => {
+            if values.len() != 1 {
+                return Err(format!("{} expects a single path.", &condition_args[0]));
+            }
+
+            let path = Path::new(&values[0]);
+            match condition_args[0].as_str() {
+                "exists" => path.exists(),
+                "is_file" => path.is_file(),
+                _ => path.is_dir(),
+            }
+        }
+        "eq" => {
+            if values.len() != 2 {
+                return Err("eq expects 2 values.".to_string());
+            }
+
+            values[0] == values[1]
+        }
+        "empty" => values.iter().all(|value| value.len() == 0),
+        _ => return Err(format!("Unknown if condition: {}", &condition_args[0])),
+    };
+
+    Ok(result != negate)
+}
+
+/// Returns the target path for copying/moving the source into the destination.
+fn get_target_path(source: &str, destination: &str, multiple: bool) -> Result<PathBuf, String> {
+    let destination_path = Path::new(destination);
+
+    if destination_path.is_dir() {
+        match Path::new(source).file_name() {
+            Some(file_name) => Ok(destination_path.join(file_name)),
+            None => Err(format!("Invalid source path: {}", source)),
+        }
+    } else if multiple {
+        Err(format!("Target: {} is not a directory.", destination))
+    } else {
+        Ok(destination_path.to_path_buf())
+    }
+}
+
+fn copy_path(source: &Path, target: &Path) -> Result<(), String> {
+    if source.is_dir() {
+        fs::create_dir_all(target)
+            .map_err(|error| format!("Unable to create directory: {:?}, {}", target, error))?;
+
+        let entries = fs::read_dir(source)
+            .map_err(|error| format!("Unable to read directory: {:?}, {}", source, error))?;
+        for entry in entries {
+            let entry =
+                entry.map_err(|error| format!("Unable to read directory entry, {}", error))?;
+            copy_path(&entry.path(), &target.join(entry.file_name()))?;
+        }
+
+        Ok(())
+    } else {
+        match fs::copy(source, target) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(format!(
+                "Unable to copy: {:?} to: {:?}, {}",
+                source, target, error
+            )),
+        }
+    }
+}
+
+fn remove_path(path: &Path, recursive: bool) -> Result<(), String> {
+    let result = if path.is_dir() {
+        if recursive {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_dir(path)
+        }
+    } else {
+        fs::remove_file(path)
+    };
+
+    result.map_err(|error| format!("Unable to remove: {:?}, {}", path, error))
+}
+
+fn run_copy(args: &[String]) -> Result<(), String> {
+    let (_, paths) = split_flags(args);
+
+    if paths.len() < 2 {
+        return Err("cp expects at least a source and a target.".to_string());
+    }
+
+    let destination = &paths[paths.len() - 1];
+    let sources = &paths[..paths.len() - 1];
+    for source in sources.iter() {
+        let target = get_target_path(source, destination, sources.len() > 1)?;
+        copy_path(Path::new(source), &target)?;
+    }
+
+    Ok(())
+}
+
+fn run_move(args: &[String]) -> Result<(), String> {
+    let (_, paths) = split_flags(args);
+
+    if paths.len() < 2 {
+        return Err("mv expects at least a source and a target.".to_string());
+    }
+
+    let destination = &paths[paths.len() - 1];
+    let sources = &paths[..paths.len() - 1];
+    for source in sources.iter() {
+        let target = get_target_path(source, destination, sources.len() > 1)?;
+        let source_path = Path::new(source);
+
+        match fs::rename(source_path, &target) {
+            Ok(_) => (),
+            Err(error) => {
+                // rename fails between file systems, so fallback to copy and delete
+                debug!("Unable to rename: {}, {}", source, error);
+                copy_path(source_path, &target)?;
+                remove_path(source_path, true)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_remove(args: &[String]) -> Result<(), String> {
+    let (flags, paths) = split_flags(args);
+    let recursive = flags.contains("r") || flags.contains("R");
+    let force = flags.contains("f");
+
+    for path_value in paths.iter() {
+        let path = Path::new(path_value);
+
+        if path.exists() {
+            remove_path(path, recursive)?;
+        } else if !force {
+            return Err(format!(
+                "Unable to remove: {}, path does not exist.",
+                path_value
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn run_mkdir(args: &[String]) -> Result<(), String> {
+    let (_, paths) = split_flags(args);
+
+    for path in paths.iter() {
+        fs::create_dir_all(path)
+            .map_err(|error| format!("Unable to create directory: {}, {}", path, error))?;
+    }
+
+    Ok(())
+}
+
+fn run_glob(args: &[String], context: &mut Context) -> Result<(), String> {
+    if args.len() < 2 {
+        return Err("glob expects a variable name and at least one pattern.".to_string());
+    }
+
+    let mut paths = vec![];
+    for pattern in args[1..].iter() {
+        let entries =
+            glob(pattern).map_err(|error| format!("Invalid glob: {}, {}", pattern, error))?;
+
+        for entry in entries {
+            match entry {
+                Ok(path) => paths.push(path.to_string_lossy().into_owned()),
+                Err(error) => debug!("Unable to read glob entry, {}", error),
+            }
+        }
+    }
+
+    context.variables.insert(args[0].clone(), paths);
+
+    Ok(())
+}
+
+fn run_exec(args: &[String], context: &Context) -> Result<(), String> {
+    if args.is_empty() {
+        return Err("exec expects a command.".to_string());
+    }
+
+    let mut command = Command::new(&args[0]);
+    command.args(&args[1..]);
+
+    for (name, values) in context.variables.iter() {
+        command.env(name, values.join(" "));
+    }
+
+    match command.status() {
+        Ok(status) => match status.code() {
+            Some(0) => Ok(()),
+            Some(code) => Err(format!("Command: {} exited with code: {}", &args[0], code)),
+            None => Err(format!("Command: {} was terminated.", &args[0])),
+        },
+        Err(error) => Err(format!("Unable to run command: {}, {}", &args[0], error)),
+    }
+}
+
+/// Runs a single command and returns the exit code if the script should exit.
+fn run_command(args: Vec<String>, context: &mut Context) -> Result<Option<i32>, String> {
+    if args.is_empty() {
+        return Ok(None);
+    }
+
+    let command_args = &args[1..];
+    match args[0].as_str() {
+        "echo" => println!("{}", command_args.join(" ")),
+        "set" => {
+            if command_args.is_empty() {
+                return Err("set expects a variable name.".to_string());
+            }
+
+            context
+                .variables
+                .insert(command_args[0].clone(), command_args[1..].to_vec());
+        }
+        "cp" => run_copy(command_args)?,
+        "mv" => run_move(command_args)?,
+        "rm" => run_remove(command_args)?,
+        "mkdir" => run_mkdir(command_args)?,
+        "glob" => run_glob(command_args, context)?,
+        "exec" => run_exec(command_args, context)?,
+        "exit" => {
+            let code = match command_args.first() {
+                Some(value) => value
+                    .parse::<i32>()
+                    .map_err(|_| format!("Invalid exit code: {}", value))?,
+                None => 0,
+            };
+
+            return Ok(Some(code));
+        }
+        _ => return Err(format!("Unknown command: {}", &args[0])),
+    };
+
+    Ok(None)
+}
+
+/// Runs the statements and returns the exit code if the script should exit.
+fn run_statements(
+    statements: &Vec<Statement>,
+    context: &mut Context,
+) -> Result<Option<i32>, String> {
+    for statement in statements.iter() {
+        let exit_code = match *statement {
+            Statement::Command(ref tokens) => {
+                let args = expand_tokens(tokens, context);
+                run_command(args, context)?
+            }
+            Statement::If {
+                ref condition,
+                ref then_block,
+                ref else_block,
+            } => {
+                let args = expand_tokens(condition, context);
+
+                if evaluate_condition(&args)? {
+                    run_statements(then_block, context)?
+                } else {
+                    run_statements(else_block, context)?
+                }
+            }
+            Statement::For {
+                ref name,
+                ref values,
+                ref block,
+            } => {
+                let mut exit_code = None;
+
+                for value in expand_tokens(values, context) {
+                    context.variables.insert(name.clone(), vec![value]);
+
+                    exit_code = run_statements(block, context)?;
+                    if exit_code.is_some() {
+                        break;
+                    }
+                }
+
+                exit_code
+            }
+        };
+
+        if exit_code.is_some() {
+            return Ok(exit_code);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Runs the script and returns its exit code.
+fn run(script: &Vec<String>, cli_arguments: &Vec<String>) -> Result<i32, String> {
+    let statements = parse(script)?;
+
+    let mut context = Context {
+        variables: IndexMap::new(),
+        arguments: cli_arguments.clone(),
+    };
+
+    let exit_code = run_statements(&statements, &mut context)?;
+
+    Ok(exit_code.unwrap_or(0))
+}
+
+pub(crate) fn execute(script: &Vec<String>, cli_arguments: &Vec<String>) {
+    match run(script, cli_arguments) {
+        Ok(exit_code) => {
+            debug!("Executed cm script, exit code: {}", exit_code);
+
+            if exit_code != 0 {
+                error!("Script exited with code: {}", exit_code);
+            }
+        }
+        Err(error) => error!("Unable to execute cm script, {}", error),
+    }
+}
@@ -0,0 +1,450 @@
+use super::*;
+use std::env;
+use std::fs::File;
+use std::io::Write;
+
+fn create_context(arguments: Vec<&str>) -> Context {
+    Context {
+        variables: IndexMap::new(),
+        arguments: arguments.iter().map(|value| value.to_string()).collect(),
+    }
+}
+
+fn to_script(lines: Vec<&str>) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+fn create_file(path: &str) {
+    let mut file = File::create(path).unwrap();
+    file.write_all(b"test").unwrap();
+}
+
+fn get_temp_directory(name: &str) -> String {
+    let directory = format!("target/_cargo_make_temp/cmscript/{}", name);
+    fs::remove_dir_all(&directory).unwrap_or(());
+    fs::create_dir_all(&directory).unwrap();
+
+    directory
+}
+
+#[test]
+fn tokenize_empty() {
+    let output = tokenize("").unwrap();
+
+    assert!(output.is_empty());
+}
+
+#[test]
+fn tokenize_simple() {
+    let output = tokenize("echo  1 \t2").unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            create_token("echo", false, false),
+            create_token("1", false, false),
+            create_token("2", false, false),
+        ]
+    );
+}
+
+#[test]
+fn tokenize_quotes() {
+    let output = tokenize("echo \"a b\" 'c ${d}' e\"f\" \"\"").unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            create_token("echo", false, false),
+            create_token("a b", true, false),
+            create_token("c ${d}", true, true),
+            create_token("ef", true, false),
+            create_token("", true, false),
+        ]
+    );
+}
+
+#[test]
+fn tokenize_missing_quote() {
+    let output = tokenize("echo \"test");
+
+    assert!(output.is_err());
+}
+
+#[test]
+fn parse_comments_and_empty_lines() {
+    let output = parse(&to_script(vec!["# comment\n\n  echo test\n", "  #another"])).unwrap();
+
+    assert_eq!(
+        output,
+        vec![Statement::Command(vec![
+            create_token("echo", false, false),
+            create_token("test", false, false),
+        ])]
+    );
+}
+
+#[test]
+fn parse_if_else() {
+    let output = parse(&to_script(vec![
+        "if exists ./Cargo.toml",
+        "echo 1",
+        "else",
+        "echo 2",
+        "end",
+    ]))
+    .unwrap();
+
+    assert_eq!(
+        output,
+        vec![Statement::If {
+            condition: vec![
+                create_token("exists", false, false),
+                create_token("./Cargo.toml", false, false),
+            ],
+            then_block: vec![Statement::Command(vec![
+                create_token("echo", false, false),
+                create_token("1", false, false),
+            ])],
+            else_block: vec![Statement::Command(vec![
+                create_token("echo", false, false),
+                create_token("2", false, false),
+            ])],
+        }]
+    );
+}
+
+#[test]
+fn parse_for() {
+    let output = parse(&to_script(vec!["for value in 1 2", "echo ${value}", "end"])).unwrap();
+
+    assert_eq!(
+        output,
+        vec![Statement::For {
+            name: "value".to_string(),
+            values: vec![
+                create_token("1", false, false),
+                create_token("2", false, false),
+            ],
+            block: vec![Statement::Command(vec![
+                create_token("echo", false, false),
+                create_token("${value}", false, false),
+            ])],
+        }]
+    );
+}
+
+#[test]
+fn parse_invalid_for() {
+    let output = parse(&to_script(vec!["for value 1 2", "end"]));
+
+    assert!(output.is_err());
+}
+
+#[test]
+fn parse_missing_end() {
+    let output = parse(&to_script(vec!["if exists ./Cargo.toml", "echo 1"]));
+
+    assert!(output.is_err());
+}
+
+#[test]
+fn parse_unexpected_end() {
+    let output = parse(&to_script(vec!["echo 1", "end"]));
+
+    assert!(output.is_err());
+}
+
+#[test]
+fn expand_tokens_variables() {
+    env::set_var("CARGO_MAKE_CMSCRIPT_TEST_ENV", "env value");
+
+    let mut context = create_context(vec!["arg1", "arg2"]);
+    context
+        .variables
+        .insert("list".to_string(), vec!["a".to_string(), "b".to_string()]);
+    context.variables.insert("empty".to_string(), vec![]);
+
+    let output = expand_tokens(
+        &tokenize("${list} \"${list}\" '${list}' ${empty} ${1}-${2} ${@} ${CARGO_MAKE_CMSCRIPT_TEST_ENV} ${CARGO_MAKE_CMSCRIPT_TEST_BAD:-default}").unwrap(),
+        &context,
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            "a",
+            "b",
+            "a b",
+            "${list}",
+            "arg1-arg2",
+            "arg1",
+            "arg2",
+            "env value",
+            "default"
+        ]
+    );
+}
+
+#[test]
+fn expand_tokens_escaped_and_nested_variables() {
+    env::set_var("CARGO_MAKE_CMSCRIPT_TEST_NESTED_ENV", "env value");
+
+    let mut context = create_context(vec!["arg1"]);
+    context.variables.insert(
+        "name".to_string(),
+        vec!["${CARGO_MAKE_CMSCRIPT_TEST_NESTED_ENV}".to_string()],
+    );
+    context.variables.insert("empty".to_string(), vec![]);
+
+    let output = expand_tokens(
+        &tokenize("$${name} \"$${1}\" \"value: ${name}\" ${empty:-default} ${1:?missing}").unwrap(),
+        &context,
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            "${name}",
+            "${1}",
+            "value: ${CARGO_MAKE_CMSCRIPT_TEST_NESTED_ENV}",
+            "default",
+            "arg1"
+        ]
+    );
+}
+
+#[test]
+fn evaluate_condition_paths() {
+    assert!(evaluate_condition(&to_script(vec!["exists", "./Cargo.toml"])).unwrap());
+    assert!(evaluate_condition(&to_script(vec!["is_file", "./Cargo.toml"])).unwrap());
+    assert!(!evaluate_condition(&to_script(vec!["is_dir", "./Cargo.toml"])).unwrap());
+    assert!(evaluate_condition(&to_script(vec!["is_dir", "./src"])).unwrap());
+    assert!(evaluate_condition(&to_script(vec!["not", "exists", "./bad"])).unwrap());
+}
+
+#[test]
+fn evaluate_condition_values() {
+    assert!(evaluate_condition(&to_script(vec!["eq", "a", "a"])).unwrap());
+    assert!(!evaluate_condition(&to_script(vec!["eq", "a", "b"])).unwrap());
+    assert!(evaluate_condition(&to_script(vec!["not", "eq", "a", "b"])).unwrap());
+    assert!(evaluate_condition(&to_script(vec!["empty"])).unwrap());
+    assert!(evaluate_condition(&to_script(vec!["empty", ""])).unwrap());
+    assert!(!evaluate_condition(&to_script(vec!["empty", "a"])).unwrap());
+}
+
+#[test]
+fn evaluate_condition_invalid() {
+    assert!(evaluate_condition(&to_script(vec![])).is_err());
+    assert!(evaluate_condition(&to_script(vec!["not"])).is_err());
+    assert!(evaluate_condition(&to_script(vec!["bad"])).is_err());
+    assert!(evaluate_condition(&to_script(vec!["eq", "a"])).is_err());
+    assert!(evaluate_condition(&to_script(vec!["exists"])).is_err());
+}
+
+#[test]
+fn run_empty() {
+    let output = run(&vec![], &vec![]).unwrap();
+
+    assert_eq!(output, 0);
+}
+
+#[test]
+fn run_exit_code() {
+    let output = run(&to_script(vec!["echo start", "exit 3", "exit 4"]), &vec![]).unwrap();
+
+    assert_eq!(output, 3);
+}
+
+#[test]
+fn run_exit_in_loop() {
+    let output = run(
+        &to_script(vec![
+            "for value in 1 2 3",
+            "if eq ${value} 2",
+            "exit ${value}",
+            "end",
+            "end",
+            "exit 1",
+        ]),
+        &vec![],
+    )
+    .unwrap();
+
+    assert_eq!(output, 2);
+}
+
+#[test]
+fn run_if_else() {
+    let output = run(
+        &to_script(vec!["if eq ${1} a", "exit 1", "else", "exit 2", "end"]),
+        &vec!["b".to_string()],
+    )
+    .unwrap();
+
+    assert_eq!(output, 2);
+}
+
+#[test]
+fn run_invalid_exit_code() {
+    let output = run(&to_script(vec!["exit bad"]), &vec![]);
+
+    assert!(output.is_err());
+}
+
+#[test]
+fn run_unknown_command() {
+    let output = run(&to_script(vec!["badcommand"]), &vec![]);
+
+    assert!(output.is_err());
+}
+
+#[test]
+fn run_file_commands() {
+    let directory = get_temp_directory("file_commands");
+
+    let output = run(
+        &to_script(vec![
+            "mkdir -p ${1}/dir1/sub ${1}/dir2",
+            "cp ${1}/missing.txt ${1}/dir1/file.txt",
+        ]),
+        &vec![directory.clone()],
+    );
+    assert!(output.is_err());
+
+    create_file(&format!("{}/dir1/sub/file.txt", &directory));
+
+    let output = run(
+        &to_script(vec![
+            "cp ${1}/dir1/sub/file.txt ${1}/dir1/copy.txt",
+            "cp -r ${1}/dir1 ${1}/dir2",
+            "mv ${1}/dir1/copy.txt ${1}/dir1/moved.txt",
+            "rm ${1}/dir2/dir1/copy.txt",
+            "rm -f ${1}/dir2/dir1/missing.txt",
+        ]),
+        &vec![directory.clone()],
+    )
+    .unwrap();
+    assert_eq!(output, 0);
+
+    assert!(Path::new(&format!("{}/dir1/sub/file.txt", &directory)).is_file());
+    assert!(Path::new(&format!("{}/dir1/moved.txt", &directory)).is_file());
+    assert!(!Path::new(&format!("{}/dir1/copy.txt", &directory)).exists());
+    assert!(Path::new(&format!("{}/dir2/dir1/sub/file.txt", &directory)).is_file());
+    assert!(!Path::new(&format!("{}/dir2/dir1/copy.txt", &directory)).exists());
+
+    let output = run(&to_script(vec!["rm ${1}/dir2"]), &vec![directory.clone()]);
+    assert!(output.is_err());
+
+    let output = run(
+        &to_script(vec!["rm ${1}/missing"]),
+        &vec![directory.clone()],
+    );
+    assert!(output.is_err());
+
+    let output = run(
+        &to_script(vec!["rm -rf ${1}/dir2"]),
+        &vec![directory.clone()],
+    )
+    .unwrap();
+    assert_eq!(output, 0);
+    assert!(!Path::new(&format!("{}/dir2", &directory)).exists());
+}
+
+#[test]
+fn run_glob_and_move() {
+    let directory = get_temp_directory("glob");
+
+    fs::create_dir_all(format!("{}/source", &directory)).unwrap();
+    fs::create_dir_all(format!("{}/target", &directory)).unwrap();
+    create_file(&format!("{}/source/1.txt", &directory));
+    create_file(&format!("{}/source/2.txt", &directory));
+    create_file(&format!("{}/source/3.md", &directory));
+
+    let output = run(
+        &to_script(vec![
+            "glob files ${1}/source/*.txt",
+            "for file in ${files}",
+            "mv ${file} ${1}/target",
+            "end",
+            "glob remaining ${1}/source/*",
+            "if not eq ${remaining} ${1}/source/3.md",
+            "exit 1",
+            "end",
+        ]),
+        &vec![directory.clone()],
+    )
+    .unwrap();
+    assert_eq!(output, 0);
+
+    assert!(Path::new(&format!("{}/target/1.txt", &directory)).is_file());
+    assert!(Path::new(&format!("{}/target/2.txt", &directory)).is_file());
+    assert!(!Path::new(&format!("{}/target/3.md", &directory)).exists());
+}
+
+#[test]
+fn run_move_multiple_to_file() {
+    let directory = get_temp_directory("move_multiple");
+
+    create_file(&format!("{}/1.txt", &directory));
+    create_file(&format!("{}/2.txt", &directory));
+
+    let output = run(
+        &to_script(vec!["mv ${1}/1.txt ${1}/2.txt ${1}/3.txt"]),
+        &vec![directory.clone()],
+    );
+
+    assert!(output.is_err());
+}
+
+#[test]
+fn run_move_with_flags() {
+    let directory = get_temp_directory("move_flags");
+
+    create_file(&format!("{}/1.txt", &directory));
+
+    let output = run(
+        &to_script(vec!["mv -f ${1}/1.txt ${1}/2.txt"]),
+        &vec![directory.clone()],
+    )
+    .unwrap();
+    assert_eq!(output, 0);
+
+    assert!(!Path::new(&format!("{}/1.txt", &directory)).exists());
+    assert!(Path::new(&format!("{}/2.txt", &directory)).is_file());
+}
+
+#[test]
+fn run_exec() {
+    let output = run(
+        &to_script(vec![
+            "set CARGO_MAKE_CMSCRIPT_TEST value",
+            "exec cargo --version",
+        ]),
+        &vec![],
+    )
+    .unwrap();
+
+    assert_eq!(output, 0);
+}
+
+#[test]
+fn run_exec_error() {
+    let output = run(&to_script(vec!["exec cargo badcommand"]), &vec![]);
+
+    assert!(output.is_err());
+}
+
+#[test]
+fn execute_valid() {
+    execute(
+        &to_script(vec!["set value test", "echo ${value} ${1}"]),
+        &vec!["arg".to_string()],
+    );
+}
+
+#[test]
+#[should_panic]
+fn execute_error() {
+    execute(&to_script(vec!["exit 1"]), &vec![]);
+}
@@ -10,17 +10,49 @@ mod generic_script_test;
 use command;
 use scriptengine::script_utils::{create_script_file, delete_file};
 
-fn run_file(file: &str, runner: &String) -> bool {
-    let exit_code = command::run_command(runner, &Some(vec![file.to_string()]), false);
+/// Returns the runner args in the following order: runner args, script file and cli arguments.
+fn get_runner_args(
+    file: &str,
+    runner_args: &Option<Vec<String>>,
+    cli_arguments: &Vec<String>,
+) -> Vec<String> {
+    let mut args = match *runner_args {
+        Some(ref args_vec) => args_vec.clone(),
+        None => vec![],
+    };
+
+    args.push(file.to_string());
+
+    let mut cli_args = cli_arguments.clone();
+    args.append(&mut cli_args);
+
+    args
+}
+
+fn run_file(
+    file: &str,
+    runner: &String,
+    runner_args: &Option<Vec<String>>,
+    cli_arguments: &Vec<String>,
+) -> bool {
+    let args = get_runner_args(file, runner_args, cli_arguments);
+
+    let exit_code = command::run_command(runner, &Some(args), false);
     debug!("Executed generic script, exit code: {}", exit_code);
 
     exit_code == 0
 }
 
-pub(crate) fn execute(script_text: &Vec<String>, runner: String, extension: String) {
+pub(crate) fn execute(
+    script_text: &Vec<String>,
+    runner: String,
+    runner_args: &Option<Vec<String>>,
+    extension: String,
+    cli_arguments: &Vec<String>,
+) {
     let file = create_script_file(script_text, &extension);
 
-    let valid = run_file(&file, &runner);
+    let valid = run_file(&file, &runner, runner_args, cli_arguments);
 
     delete_file(&file);
 
@@ -1,21 +1,56 @@
 use super::*;
 use test;
 
+#[test]
+fn get_runner_args_file_only() {
+    let args = get_runner_args("script.py", &None, &vec![]);
+
+    assert_eq!(args, vec!["script.py"]);
+}
+
+#[test]
+fn get_runner_args_all() {
+    let args = get_runner_args(
+        "script.py",
+        &Some(vec!["-u".to_string(), "-B".to_string()]),
+        &vec!["arg1".to_string(), "arg2".to_string()],
+    );
+
+    assert_eq!(args, vec!["-u", "-B", "script.py", "arg1", "arg2"]);
+}
+
 #[test]
 fn execute_shell() {
     execute(
         &vec!["exit 0".to_string()],
         test::get_os_runner(),
+        &None,
         test::get_os_extension(),
+        &vec![],
     );
 }
 
+#[test]
+fn execute_shell_with_args() {
+    if !cfg!(windows) {
+        execute(
+            &vec!["if [ \"$1\" != \"arg1\" ]; then exit 1; fi".to_string()],
+            test::get_os_runner(),
+            &Some(vec!["-e".to_string()]),
+            test::get_os_extension(),
+            &vec!["arg1".to_string()],
+        );
+    }
+}
+
 #[test]
 #[should_panic]
 fn execute_shell_error() {
     execute(
         &vec!["exit 1".to_string()],
         test::get_os_runner(),
+        &None,
         test::get_os_extension(),
+        &vec![],
     );
 }
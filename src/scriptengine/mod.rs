@@ -3,6 +3,7 @@
 //! Facade for all different non OS scripts.
 //!
 
+mod cmscript;
 mod generic_script;
 mod rsscript;
 pub(crate) mod script_utils;
@@ -21,6 +22,8 @@ enum EngineType {
     Rust,
     /// shell to windows batch conversion
     Shell2Batch,
+    /// Built in cross platform mini shell
+    CmScript,
     /// Generic script runner
     Generic,
     /// Unsupported type
@@ -40,6 +43,9 @@ fn get_engine_type(task: &Task) -> EngineType {
                 } else if script_runner == "@shell" {
                     debug!("Shell to batch detected.");
                     EngineType::Shell2Batch
+                } else if script_runner == "@cm" {
+                    debug!("cm script detected.");
+                    EngineType::CmScript
                 } else if task.script_extension.is_some() {
                     // if both script runner and extension is defined, we use generic script runner
                     debug!("Generic script detected.");
@@ -59,7 +65,13 @@ pub(crate) fn invoke(task: &Task, cli_arguments: &Vec<String>) -> bool {
     match engine_type {
         EngineType::Rust => {
             let script = task.script.as_ref().unwrap();
-            rsscript::execute(script, cli_arguments);
+            rsscript::execute(
+                script,
+                &task.rust_script_runner,
+                &task.script_dependencies,
+                &task.toolchain,
+                cli_arguments,
+            );
 
             true
         }
@@ -69,11 +81,23 @@ pub(crate) fn invoke(task: &Task, cli_arguments: &Vec<String>) -> bool {
 
             true
         }
+        EngineType::CmScript => {
+            let script = task.script.as_ref().unwrap();
+            cmscript::execute(script, cli_arguments);
+
+            true
+        }
         EngineType::Generic => {
             let script = task.script.as_ref().unwrap();
             let runner = task.script_runner.clone().unwrap();
             let extension = task.script_extension.clone().unwrap();
-            generic_script::execute(script, runner, extension);
+            generic_script::execute(
+                script,
+                runner,
+                &task.script_runner_args,
+                extension,
+                cli_arguments,
+            );
 
             true
         }
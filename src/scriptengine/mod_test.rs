@@ -54,6 +54,17 @@ fn get_engine_type_shell_to_batch() {
     assert_eq!(output, EngineType::Shell2Batch);
 }
 
+#[test]
+fn get_engine_type_cm() {
+    let mut task = Task::new();
+    task.script_runner = Some("@cm".to_string());
+    task.script = Some(vec!["echo test".to_string()]);
+
+    let output = get_engine_type(&task);
+
+    assert_eq!(output, EngineType::CmScript);
+}
+
 #[test]
 fn get_engine_type_generic() {
     let mut task = Task::new();
@@ -124,6 +135,17 @@ fn invoke_rust_runner_error() {
     }
 }
 
+#[test]
+fn invoke_cm_runner() {
+    let mut task = Task::new();
+    task.script_runner = Some("@cm".to_string());
+    task.script = Some(vec!["echo test".to_string()]);
+
+    let output = invoke(&task, &vec![]);
+
+    assert!(output);
+}
+
 #[test]
 fn invoke_shell_to_batch_runner() {
     let mut task = Task::new();
@@ -159,6 +181,23 @@ fn invoke_generic_runner() {
     assert!(output);
 }
 
+#[test]
+fn invoke_generic_runner_with_args() {
+    if !cfg!(windows) {
+        let mut task = Task::new();
+        task.script_runner = Some(test::get_os_runner());
+        task.script_runner_args = Some(vec!["-e".to_string()]);
+        task.script_extension = Some(test::get_os_extension());
+        task.script = Some(vec![
+            "if [ \"$1\" != \"arg1\" ]; then exit 1; fi".to_string()
+        ]);
+
+        let output = invoke(&task, &vec!["arg1".to_string()]);
+
+        assert!(output);
+    }
+}
+
 #[test]
 #[should_panic]
 fn invoke_generic_runner_error() {
@@ -1,46 +1,403 @@
 //! # rsscript
 //!
-//! Compiles and runs rust code.
+//! Compiles and runs rust code using one of the supported rust script runners.<br>
+//! Binaries compiled by cargo-script are cached (keyed by the script text and toolchain) and reused
+//! as long as the script is not modified.
 //!
 
 #[cfg(test)]
 #[path = "./rsscript_test.rs"]
 mod rsscript_test;
 
+use cache;
 use command;
+use environment;
+use indexmap::IndexMap;
 use installer;
 use scriptengine::script_utils::{create_script_file, delete_file};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use storage;
+use toml;
+use toolchain;
+use types::InstallCrateInfo;
 
-fn install_crate() {
+#[derive(Debug, Clone, PartialEq)]
+/// The supported rust script runners
+enum ScriptRunner {
+    /// cargo-script
+    CargoScript,
+    /// rust-script
+    RustScript,
+    /// cargo-play
+    CargoPlay,
+}
+
+/// Returns the task runner name, or the default runner (config level) if not defined.
+fn get_script_runner_name(runner: &Option<String>) -> String {
+    match *runner {
+        Some(ref value) => value.clone(),
+        None => environment::get_env("CARGO_MAKE_RUST_SCRIPT_RUNNER", "cargo-script"),
+    }
+}
+
+fn get_script_runner(runner_name: &str) -> Option<ScriptRunner> {
+    match runner_name {
+        "cargo-script" => Some(ScriptRunner::CargoScript),
+        "rust-script" => Some(ScriptRunner::RustScript),
+        "cargo-play" => Some(ScriptRunner::CargoPlay),
+        _ => None,
+    }
+}
+
+fn install_crate(runner: &ScriptRunner) {
     // install dependencies
-    installer::install_crate("script", "cargo-script", &None, true);
+    match *runner {
+        ScriptRunner::CargoScript => {
+            installer::install_crate("script", "cargo-script", &None, true)
+        }
+        ScriptRunner::CargoPlay => installer::install_crate("play", "cargo-play", &None, true),
+        ScriptRunner::RustScript => {
+            let info = InstallCrateInfo {
+                crate_name: "rust-script".to_string(),
+                binary: None,
+                version: None,
+                test_arg: None,
+                path: None,
+            };
+
+            installer::install_crate_with_version(&info, &None, true)
+        }
+    }
+}
+
+/// Returns the value in the toml inline format.
+fn format_toml_value(value: &toml::Value) -> String {
+    match *value {
+        toml::Value::String(ref text) => format!("{:?}", text),
+        toml::Value::Integer(number) => number.to_string(),
+        toml::Value::Float(number) => number.to_string(),
+        toml::Value::Boolean(flag) => flag.to_string(),
+        toml::Value::Datetime(ref datetime) => datetime.to_string(),
+        toml::Value::Array(ref values) => {
+            let items: Vec<String> = values.iter().map(format_toml_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+        toml::Value::Table(ref table) => {
+            let items: Vec<String> = table
+                .iter()
+                .map(|(key, item)| format!("{} = {}", key, format_toml_value(item)))
+                .collect();
+            format!("{{ {} }}", items.join(", "))
+        }
+    }
+}
+
+/// Returns the dependencies in the embedded manifest format of the runner.
+fn get_dependencies_lines(
+    runner: &ScriptRunner,
+    dependencies: &IndexMap<String, toml::Value>,
+) -> Vec<String> {
+    let dependency_lines = dependencies
+        .iter()
+        .map(|(name, value)| format!("{} = {}", name, format_toml_value(value)));
+
+    match *runner {
+        ScriptRunner::CargoPlay => dependency_lines
+            .map(|line| format!("//# {}", line))
+            .collect(),
+        _ => {
+            let mut lines = vec!["//! ```cargo".to_string(), "//! [dependencies]".to_string()];
+            lines.extend(dependency_lines.map(|line| format!("//! {}", line)));
+            lines.push("//! ```".to_string());
+
+            lines
+        }
+    }
+}
+
+/// Returns the script with the dependencies injected at its start.
+fn add_dependencies(
+    rust_script: &Vec<String>,
+    runner: &ScriptRunner,
+    dependencies: &Option<IndexMap<String, toml::Value>>,
+) -> Vec<String> {
+    match *dependencies {
+        Some(ref dependencies_map) if !dependencies_map.is_empty() => {
+            let mut script = get_dependencies_lines(runner, dependencies_map);
+            script.extend(rust_script.iter().cloned());
+
+            script
+        }
+        _ => rust_script.clone(),
+    }
 }
 
 fn create_rust_file(rust_script: &Vec<String>) -> String {
     create_script_file(rust_script, "rs")
 }
 
-fn run_file(file: &str, cli_arguments: &Vec<String>) -> bool {
-    let mut args = vec!["script".to_string(), file.to_string()];
+/// Returns a hash of the script text and toolchain used as the cached binary name.
+fn get_script_hash(rust_script: &Vec<String>, toolchain: &Option<String>) -> String {
+    let toolchain_name = match *toolchain {
+        Some(ref value) => value.as_str(),
+        None => "",
+    };
+
+    storage::get_stable_hash(&[&rust_script.join("\n"), toolchain_name])
+}
+
+fn get_binary_cache_directory() -> Option<PathBuf> {
+    match cache::get_cache_directory(false) {
+        Some(directory) => Some(directory.join("rsscript")),
+        None => None,
+    }
+}
+
+fn get_binary_path(directory: &Path, hash: &str) -> PathBuf {
+    directory.join(format!("{}{}", hash, env::consts::EXE_SUFFIX))
+}
+
+fn run_with_toolchain(toolchain: &Option<String>, command_string: &str, args: Vec<String>) -> i32 {
+    let command_args = Some(args);
+
+    match *toolchain {
+        Some(ref toolchain_name) => {
+            let (rustup_command, rustup_args) =
+                toolchain::wrap_command(toolchain_name, command_string, &command_args);
+            command::run_command(&rustup_command, &rustup_args, false)
+        }
+        None => command::run_command(command_string, &command_args, false),
+    }
+}
+
+/// Returns the command and args used to compile and run the script file.
+fn get_run_command(
+    runner: &ScriptRunner,
+    file: &str,
+    cli_arguments: &Vec<String>,
+) -> (String, Vec<String>) {
     let mut cli_args = cli_arguments.clone();
-    args.append(&mut cli_args);
 
-    let exit_code = command::run_command("cargo", &Some(args), false);
+    match *runner {
+        ScriptRunner::CargoScript => {
+            let mut args = vec!["script".to_string(), file.to_string()];
+            args.append(&mut cli_args);
+
+            ("cargo".to_string(), args)
+        }
+        ScriptRunner::RustScript => {
+            let mut args = vec![file.to_string()];
+            args.append(&mut cli_args);
+
+            ("rust-script".to_string(), args)
+        }
+        ScriptRunner::CargoPlay => {
+            let mut args = vec!["play".to_string(), file.to_string()];
+            if !cli_args.is_empty() {
+                args.push("--".to_string());
+                args.append(&mut cli_args);
+            }
+
+            ("cargo".to_string(), args)
+        }
+    }
+}
+
+fn run_file(
+    runner: &ScriptRunner,
+    file: &str,
+    toolchain: &Option<String>,
+    cli_arguments: &Vec<String>,
+) -> bool {
+    let (command_string, args) = get_run_command(runner, file, cli_arguments);
+
+    let exit_code = run_with_toolchain(toolchain, &command_string, args);
     debug!("Executed rust code, exit code: {}", exit_code);
 
     exit_code == 0
 }
 
-pub(crate) fn execute(rust_script: &Vec<String>, cli_arguments: &Vec<String>) {
-    install_crate();
+fn run_binary(binary: &Path, cli_arguments: &Vec<String>) -> bool {
+    let binary_path = binary.to_string_lossy().into_owned();
+
+    let exit_code = command::run_command(&binary_path, &Some(cli_arguments.clone()), false);
+    debug!("Executed cached rust binary, exit code: {}", exit_code);
+
+    exit_code == 0
+}
+
+/// Decodes the executable path written by cargo-script (UTF-16 on windows).
+fn decode_exe_path(bytes: &[u8], wide: bool) -> Option<PathBuf> {
+    let value = if wide {
+        let words: Vec<u16> = bytes
+            .chunks(2)
+            .map(|chunk| chunk[0] as u16 | ((*chunk.get(1).unwrap_or(&0) as u16) << 8))
+            .collect();
+
+        String::from_utf16(&words).ok()
+    } else {
+        String::from_utf8(bytes.to_vec()).ok()
+    };
+
+    match value {
+        Some(path) => {
+            if path.len() > 0 {
+                Some(PathBuf::from(path))
+            } else {
+                None
+            }
+        }
+        None => None,
+    }
+}
+
+/// Returns the compiled executable path which cargo-script stores in the package directory.
+fn read_exe_path(package_directory: &Path) -> Option<PathBuf> {
+    match fs::read(package_directory.join("target.exe_path")) {
+        Ok(bytes) => decode_exe_path(&bytes, cfg!(windows)),
+        Err(error) => {
+            debug!("Unable to read compiled rust binary path, {}", error);
+            None
+        }
+    }
+}
+
+fn cache_binary(executable: &Path, binary: &Path) -> bool {
+    match binary.parent() {
+        Some(directory) => match fs::create_dir_all(directory) {
+            Ok(_) => {
+                // copy to a temporary file first to prevent using partially copied binaries
+                let temp_binary = binary.with_extension("tmp");
+
+                match fs::copy(executable, &temp_binary)
+                    .and_then(|_| fs::rename(&temp_binary, binary))
+                {
+                    Ok(_) => true,
+                    Err(error) => {
+                        info!("Unable to cache rust binary: {:?}, {}", binary, error);
+                        false
+                    }
+                }
+            }
+            Err(error) => {
+                info!("Unable to create directory: {:?}, {}", directory, error);
+                false
+            }
+        },
+        None => false,
+    }
+}
+
+fn compile_and_run(
+    file: &str,
+    toolchain: &Option<String>,
+    binary: &Path,
+    cli_arguments: &Vec<String>,
+) -> bool {
+    let package_directory = binary.with_extension("pkg");
+    let package_directory_str = package_directory.to_string_lossy().into_owned();
+
+    let args = vec![
+        "script".to_string(),
+        "--build-only".to_string(),
+        "--pkg-path".to_string(),
+        package_directory_str,
+        file.to_string(),
+    ];
+
+    let exit_code = run_with_toolchain(toolchain, "cargo", args);
+    debug!("Compiled rust code, exit code: {}", exit_code);
+
+    let valid = if exit_code == 0 {
+        let cached = match read_exe_path(&package_directory) {
+            Some(executable) => cache_binary(&executable, binary),
+            None => false,
+        };
+
+        if cached {
+            run_binary(binary, cli_arguments)
+        } else {
+            run_file(&ScriptRunner::CargoScript, file, toolchain, cli_arguments)
+        }
+    } else {
+        false
+    };
+
+    fs::remove_dir_all(&package_directory).unwrap_or(());
+
+    valid
+}
+
+/// Runs the script via cargo-script using the cached binary (compiled and cached if needed).
+fn execute_cached(
+    rust_script: &Vec<String>,
+    toolchain: &Option<String>,
+    cli_arguments: &Vec<String>,
+) -> bool {
+    let hash = get_script_hash(rust_script, toolchain);
+    let binary = match get_binary_cache_directory() {
+        Some(directory) => Some(get_binary_path(&directory, &hash)),
+        None => None,
+    };
+
+    match binary {
+        Some(ref binary_path) if binary_path.exists() => {
+            debug!("Using cached rust binary: {:?}", binary_path);
+            run_binary(binary_path, cli_arguments)
+        }
+        _ => {
+            install_crate(&ScriptRunner::CargoScript);
+
+            let file = create_rust_file(rust_script);
+
+            let valid = match binary {
+                Some(ref binary_path) => {
+                    compile_and_run(&file, toolchain, binary_path, cli_arguments)
+                }
+                None => run_file(&ScriptRunner::CargoScript, &file, toolchain, cli_arguments),
+            };
+
+            delete_file(&file);
+
+            valid
+        }
+    }
+}
+
+pub(crate) fn execute(
+    rust_script: &Vec<String>,
+    runner: &Option<String>,
+    dependencies: &Option<IndexMap<String, toml::Value>>,
+    toolchain: &Option<String>,
+    cli_arguments: &Vec<String>,
+) {
+    let runner_name = get_script_runner_name(runner);
+
+    match get_script_runner(&runner_name) {
+        Some(script_runner) => {
+            let script = add_dependencies(rust_script, &script_runner, dependencies);
+
+            let valid = match script_runner {
+                ScriptRunner::CargoScript => execute_cached(&script, toolchain, cli_arguments),
+                _ => {
+                    install_crate(&script_runner);
+
+                    let file = create_rust_file(&script);
 
-    let file = create_rust_file(rust_script);
+                    let valid = run_file(&script_runner, &file, toolchain, cli_arguments);
 
-    let valid = run_file(&file, &cli_arguments);
+                    delete_file(&file);
 
-    delete_file(&file);
+                    valid
+                }
+            };
 
-    if !valid {
-        error!("Unable to execute rust code.");
+            if !valid {
+                error!("Unable to execute rust code.");
+            }
+        }
+        None => error!("Unsupported rust script runner: {}", &runner_name),
     }
 }
@@ -1,11 +1,345 @@
 use super::*;
+use std::fs::File;
+use std::io::Write;
 use test;
 
+fn get_dependencies() -> IndexMap<String, toml::Value> {
+    let mut dependencies = IndexMap::new();
+    dependencies.insert("time".to_string(), toml::Value::String("*".to_string()));
+
+    let mut serde = toml::value::Table::new();
+    serde.insert("version".to_string(), toml::Value::String("1".to_string()));
+    serde.insert(
+        "features".to_string(),
+        toml::Value::Array(vec![toml::Value::String("derive".to_string())]),
+    );
+    dependencies.insert("serde".to_string(), toml::Value::Table(serde));
+
+    dependencies
+}
+
+#[test]
+fn get_script_runner_name_task_value() {
+    env::set_var("CARGO_MAKE_RUST_SCRIPT_RUNNER", "cargo-play");
+
+    let runner_name = get_script_runner_name(&Some("rust-script".to_string()));
+
+    env::remove_var("CARGO_MAKE_RUST_SCRIPT_RUNNER");
+
+    assert_eq!(runner_name, "rust-script");
+}
+
+#[test]
+fn get_script_runner_name_config_value() {
+    env::set_var("CARGO_MAKE_RUST_SCRIPT_RUNNER", "cargo-play");
+
+    let runner_name = get_script_runner_name(&None);
+
+    env::remove_var("CARGO_MAKE_RUST_SCRIPT_RUNNER");
+
+    assert_eq!(runner_name, "cargo-play");
+}
+
+#[test]
+fn get_script_runner_name_default() {
+    env::remove_var("CARGO_MAKE_RUST_SCRIPT_RUNNER");
+
+    let runner_name = get_script_runner_name(&None);
+
+    assert_eq!(runner_name, "cargo-script");
+}
+
+#[test]
+fn get_script_runner_all() {
+    assert_eq!(
+        get_script_runner("cargo-script").unwrap(),
+        ScriptRunner::CargoScript
+    );
+    assert_eq!(
+        get_script_runner("rust-script").unwrap(),
+        ScriptRunner::RustScript
+    );
+    assert_eq!(
+        get_script_runner("cargo-play").unwrap(),
+        ScriptRunner::CargoPlay
+    );
+    assert!(get_script_runner("bad").is_none());
+}
+
+#[test]
+fn format_toml_value_all_types() {
+    assert_eq!(
+        format_toml_value(&toml::Value::String("1.0".to_string())),
+        "\"1.0\""
+    );
+    assert_eq!(format_toml_value(&toml::Value::Integer(5)), "5");
+    assert_eq!(format_toml_value(&toml::Value::Boolean(false)), "false");
+    assert_eq!(
+        format_toml_value(&toml::Value::Array(vec![
+            toml::Value::String("a".to_string()),
+            toml::Value::String("b".to_string()),
+        ])),
+        "[\"a\", \"b\"]"
+    );
+    assert_eq!(
+        format_toml_value(get_dependencies().get("serde").unwrap()),
+        "{ features = [\"derive\"], version = \"1\" }"
+    );
+}
+
+#[test]
+fn get_dependencies_lines_cargo_script() {
+    let lines = get_dependencies_lines(&ScriptRunner::CargoScript, &get_dependencies());
+
+    assert_eq!(
+        lines,
+        vec![
+            "//! ```cargo",
+            "//! [dependencies]",
+            "//! time = \"*\"",
+            "//! serde = { features = [\"derive\"], version = \"1\" }",
+            "//! ```",
+        ]
+    );
+}
+
+#[test]
+fn get_dependencies_lines_rust_script() {
+    let lines = get_dependencies_lines(&ScriptRunner::RustScript, &get_dependencies());
+
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "//! ```cargo");
+}
+
+#[test]
+fn get_dependencies_lines_cargo_play() {
+    let lines = get_dependencies_lines(&ScriptRunner::CargoPlay, &get_dependencies());
+
+    assert_eq!(
+        lines,
+        vec![
+            "//# time = \"*\"",
+            "//# serde = { features = [\"derive\"], version = \"1\" }",
+        ]
+    );
+}
+
+#[test]
+fn add_dependencies_none() {
+    let script = vec!["fn main() {}".to_string()];
+
+    let output = add_dependencies(&script, &ScriptRunner::CargoScript, &None);
+
+    assert_eq!(output, script);
+}
+
+#[test]
+fn add_dependencies_empty() {
+    let script = vec!["fn main() {}".to_string()];
+
+    let output = add_dependencies(&script, &ScriptRunner::CargoScript, &Some(IndexMap::new()));
+
+    assert_eq!(output, script);
+}
+
+#[test]
+fn add_dependencies_defined() {
+    let script = vec!["fn main() {}".to_string()];
+
+    let output = add_dependencies(&script, &ScriptRunner::CargoPlay, &Some(get_dependencies()));
+
+    assert_eq!(output.len(), 3);
+    assert_eq!(output[0], "//# time = \"*\"");
+    assert_eq!(output[2], "fn main() {}");
+}
+
+#[test]
+fn get_run_command_cargo_script() {
+    let (command_string, args) = get_run_command(
+        &ScriptRunner::CargoScript,
+        "file.rs",
+        &vec!["arg".to_string()],
+    );
+
+    assert_eq!(command_string, "cargo");
+    assert_eq!(args, vec!["script", "file.rs", "arg"]);
+}
+
+#[test]
+fn get_run_command_rust_script() {
+    let (command_string, args) = get_run_command(
+        &ScriptRunner::RustScript,
+        "file.rs",
+        &vec!["arg".to_string()],
+    );
+
+    assert_eq!(command_string, "rust-script");
+    assert_eq!(args, vec!["file.rs", "arg"]);
+}
+
+#[test]
+fn get_run_command_cargo_play_no_args() {
+    let (command_string, args) = get_run_command(&ScriptRunner::CargoPlay, "file.rs", &vec![]);
+
+    assert_eq!(command_string, "cargo");
+    assert_eq!(args, vec!["play", "file.rs"]);
+}
+
+#[test]
+fn get_run_command_cargo_play_with_args() {
+    let (command_string, args) = get_run_command(
+        &ScriptRunner::CargoPlay,
+        "file.rs",
+        &vec!["arg".to_string()],
+    );
+
+    assert_eq!(command_string, "cargo");
+    assert_eq!(args, vec!["play", "file.rs", "--", "arg"]);
+}
+
+#[test]
+fn get_script_hash_same_script() {
+    let script = vec!["fn main() {}".to_string()];
+
+    let hash1 = get_script_hash(&script, &None);
+    let hash2 = get_script_hash(&script, &None);
+
+    assert_eq!(hash1, hash2);
+    assert_eq!(hash1.len(), 16);
+}
+
+#[test]
+fn get_script_hash_different_script() {
+    let hash1 = get_script_hash(&vec!["fn main() {}".to_string()], &None);
+    let hash2 = get_script_hash(&vec!["fn main() { }".to_string()], &None);
+
+    assert!(hash1 != hash2);
+}
+
+#[test]
+fn get_script_hash_different_toolchain() {
+    let script = vec!["fn main() {}".to_string()];
+
+    let hash1 = get_script_hash(&script, &None);
+    let hash2 = get_script_hash(&script, &Some("nightly".to_string()));
+    let hash3 = get_script_hash(&script, &Some("beta".to_string()));
+
+    assert!(hash1 != hash2);
+    assert!(hash2 != hash3);
+}
+
+#[test]
+fn get_binary_path_valid() {
+    let path = get_binary_path(Path::new("./cache"), "abc");
+
+    assert_eq!(
+        path,
+        Path::new("./cache").join(format!("abc{}", env::consts::EXE_SUFFIX))
+    );
+}
+
+#[test]
+fn decode_exe_path_narrow() {
+    let path = decode_exe_path(b"/home/test/bin", false).unwrap();
+
+    assert_eq!(path, PathBuf::from("/home/test/bin"));
+}
+
+#[test]
+fn decode_exe_path_wide() {
+    let path = decode_exe_path(&[b'c', 0, b':', 0, b'\\', 0, b'a', 0], true).unwrap();
+
+    assert_eq!(path, PathBuf::from("c:\\a"));
+}
+
+#[test]
+fn decode_exe_path_empty() {
+    assert!(decode_exe_path(b"", false).is_none());
+}
+
+#[test]
+fn read_exe_path_missing() {
+    let directory = test::get_temp_test_directory();
+
+    assert!(read_exe_path(&directory).is_none());
+}
+
+#[test]
+fn read_exe_path_valid() {
+    let directory = test::get_temp_test_directory();
+    let executable = directory.join("script");
+
+    let mut file = File::create(directory.join("target.exe_path")).unwrap();
+    if cfg!(windows) {
+        let bytes: Vec<u8> = executable
+            .to_str()
+            .unwrap()
+            .encode_utf16()
+            .flat_map(|word| vec![(word & 0xff) as u8, (word >> 8) as u8])
+            .collect();
+        file.write_all(&bytes).unwrap();
+    } else {
+        file.write_all(executable.to_str().unwrap().as_bytes())
+            .unwrap();
+    }
+
+    let path = read_exe_path(&directory).unwrap();
+
+    assert_eq!(path, executable);
+}
+
+#[test]
+fn cache_binary_valid() {
+    let directory = test::get_temp_test_directory();
+    let executable = directory.join("script");
+    let binary = directory.join("cache").join("binary");
+
+    let mut file = File::create(&executable).unwrap();
+    file.write_all(b"test").unwrap();
+
+    let cached = cache_binary(&executable, &binary);
+
+    assert!(cached);
+    assert_eq!(fs::read(&binary).unwrap(), b"test");
+    assert!(!binary.with_extension("tmp").exists());
+}
+
+#[test]
+fn cache_binary_missing_executable() {
+    let directory = test::get_temp_test_directory();
+
+    let cached = cache_binary(&directory.join("script"), &directory.join("binary"));
+
+    assert!(!cached);
+}
+
+#[test]
+fn execute_cached_binary() {
+    // cached binaries are invoked directly without compilation
+    if Path::new("/bin/true").exists() {
+        let directory = test::get_temp_test_directory();
+        env::set_var("CARGO_MAKE_HOME", directory.to_str().unwrap());
+
+        let script = vec!["fn main() {donotcompile();}".to_string()];
+        let hash = get_script_hash(&script, &None);
+        let binary = get_binary_path(&get_binary_cache_directory().unwrap(), &hash);
+        fs::create_dir_all(binary.parent().unwrap()).unwrap();
+        fs::copy("/bin/true", &binary).unwrap();
+
+        execute(&script, &None, &None, &None, &vec![]);
+
+        env::remove_var("CARGO_MAKE_HOME");
+    }
+}
+
 #[test]
 fn execute_valid() {
     if test::should_test(false) {
         execute(
             &vec!["fn main() {println!(\"test\");}".to_string()],
+            &None,
+            &None,
+            &None,
             &vec![],
         );
     }
@@ -15,7 +349,13 @@ fn execute_valid() {
 #[should_panic]
 fn execute_not_compile() {
     if test::should_test(true) {
-        execute(&vec!["fn main() {donotcompile();}".to_string()], &vec![]);
+        execute(
+            &vec!["fn main() {donotcompile();}".to_string()],
+            &None,
+            &None,
+            &None,
+            &vec![],
+        );
     }
 }
 
@@ -23,6 +363,24 @@ fn execute_not_compile() {
 #[should_panic]
 fn execute_runtime_panic() {
     if test::should_test(true) {
-        execute(&vec!["fn main() {panic!(\"error\");}".to_string()], &vec![]);
+        execute(
+            &vec!["fn main() {panic!(\"error\");}".to_string()],
+            &None,
+            &None,
+            &None,
+            &vec![],
+        );
     }
 }
+
+#[test]
+#[should_panic]
+fn execute_unsupported_runner() {
+    execute(
+        &vec!["fn main() {}".to_string()],
+        &Some("bad".to_string()),
+        &None,
+        &None,
+        &vec![],
+    );
+}
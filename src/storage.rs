@@ -42,3 +42,38 @@ pub(crate) fn get_storage_directory(
         }
     }
 }
+
+/// Returns the project level directory used to persist cargo-make state.<br>
+/// The directory is located under the cargo target directory.
+pub(crate) fn get_project_state_directory() -> PathBuf {
+    let target_directory = match env::var("CARGO_TARGET_DIR") {
+        Ok(directory) => PathBuf::from(directory),
+        _ => {
+            let working_directory = match env::var("CARGO_MAKE_WORKING_DIRECTORY") {
+                Ok(directory) => directory,
+                _ => ".".to_string(),
+            };
+
+            Path::new(&working_directory).join("target")
+        }
+    };
+
+    target_directory.join("cargo-make")
+}
+
+/// Returns a hex digest (FNV-1a 64 bit) of the provided values.<br>
+/// Unlike the std hashers, the output does not change between rust releases so it can be used
+/// as a persisted cache key.
+pub(crate) fn get_stable_hash(values: &[&str]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for value in values {
+        // 0xff is never part of a utf8 string so it is used to separate the values
+        for byte in value.as_bytes().iter().chain([0xff].iter()) {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    format!("{:016x}", hash)
+}
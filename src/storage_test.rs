@@ -33,3 +33,40 @@ fn get_storage_directory_no_env_with_os_dir_file_exists() {
     os_directory = os_directory.join("cargo-make");
     assert_eq!(directory, os_directory);
 }
+
+#[test]
+fn get_project_state_directory_with_target_dir_env() {
+    env::set_var("CARGO_TARGET_DIR", "./test_target");
+
+    let directory = get_project_state_directory();
+
+    env::remove_var("CARGO_TARGET_DIR");
+
+    assert_eq!(directory, Path::new("./test_target").join("cargo-make"));
+}
+
+#[test]
+fn get_project_state_directory_no_target_dir_env() {
+    env::remove_var("CARGO_TARGET_DIR");
+    let working_directory = env::var("CARGO_MAKE_WORKING_DIRECTORY").unwrap_or(".".to_string());
+
+    let directory = get_project_state_directory();
+
+    assert_eq!(
+        directory,
+        Path::new(&working_directory).join("target").join("cargo-make")
+    );
+}
+
+#[test]
+fn get_stable_hash_known_value() {
+    assert_eq!(get_stable_hash(&[]), "cbf29ce484222325");
+    assert_eq!(get_stable_hash(&["echo 1"]), get_stable_hash(&["echo 1"]));
+    assert_eq!(get_stable_hash(&["echo 1"]).len(), 16);
+}
+
+#[test]
+fn get_stable_hash_value_boundaries() {
+    assert_ne!(get_stable_hash(&["ab", "c"]), get_stable_hash(&["a", "bc"]));
+    assert_ne!(get_stable_hash(&["abc"]), get_stable_hash(&["abc", ""]));
+}
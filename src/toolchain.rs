@@ -0,0 +1,61 @@
+//! # toolchain
+//!
+//! Toolchain related utility functions.<br>
+//! Commands of tasks with a defined toolchain are invoked via rustup run and missing toolchains
+//! are installed via rustup.
+//!
+
+#[cfg(test)]
+#[path = "./toolchain_test.rs"]
+mod toolchain_test;
+
+use command;
+
+/// Returns the command and args needed to invoke the given command via the provided toolchain.
+pub(crate) fn wrap_command(
+    toolchain: &str,
+    command: &str,
+    args: &Option<Vec<String>>,
+) -> (String, Option<Vec<String>>) {
+    let mut rustup_args = vec![
+        "run".to_string(),
+        toolchain.to_string(),
+        command.to_string(),
+    ];
+
+    match *args {
+        Some(ref args_vec) => {
+            for arg in args_vec.iter() {
+                rustup_args.push(arg.to_string());
+            }
+        }
+        None => debug!("No command args defined."),
+    };
+
+    ("rustup".to_string(), Some(rustup_args))
+}
+
+/// Returns true if the toolchain is installed.
+pub(crate) fn has_toolchain(toolchain: &str) -> bool {
+    let args = Some(vec!["--version".to_string()]);
+    let (command_string, rustup_args) = wrap_command(toolchain, "rustc", &args);
+
+    let output = command::run_command_get_output(&command_string, &rustup_args, true);
+
+    command::get_exit_code_from_output(&output, true) == 0
+}
+
+/// Installs the toolchain via rustup if it is not installed.
+pub(crate) fn install_toolchain(toolchain: &str, validate: bool) {
+    if !has_toolchain(toolchain) {
+        info!("Installing toolchain: {}", &toolchain);
+
+        let args = vec![
+            "toolchain".to_string(),
+            "install".to_string(),
+            toolchain.to_string(),
+        ];
+
+        command::run_command("rustup", &Some(args), validate);
+    }
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn wrap_command_no_args() {
+    let (command_string, args) = wrap_command("nightly", "cargo", &None);
+
+    assert_eq!(command_string, "rustup");
+    assert_eq!(args.unwrap(), vec!["run", "nightly", "cargo"]);
+}
+
+#[test]
+fn wrap_command_with_args() {
+    let (command_string, args) = wrap_command(
+        "nightly-2018-10-01",
+        "cargo",
+        &Some(vec!["clippy".to_string(), "--all".to_string()]),
+    );
+
+    assert_eq!(command_string, "rustup");
+    assert_eq!(
+        args.unwrap(),
+        vec!["run", "nightly-2018-10-01", "cargo", "clippy", "--all"]
+    );
+}
+
+#[test]
+fn has_toolchain_not_found() {
+    let found = has_toolchain("badbadbad");
+
+    assert!(!found);
+}
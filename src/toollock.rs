@@ -0,0 +1,115 @@
+//! # toollock
+//!
+//! Manages the cargo-make.lock file which holds the versions of the crates installed
+//! in the project tool root.<br>
+//! Locked versions are used when the crates are installed again.
+//!
+
+#[cfg(test)]
+#[path = "./toollock_test.rs"]
+mod toollock_test;
+
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use toml;
+use types::ToolLock;
+
+static LOCK_FILE: &'static str = "cargo-make.lock";
+
+fn get_lock_file() -> PathBuf {
+    let working_directory = match env::var("CARGO_MAKE_WORKING_DIRECTORY") {
+        Ok(directory) => directory,
+        _ => ".".to_string(),
+    };
+
+    Path::new(&working_directory).join(LOCK_FILE)
+}
+
+fn load_from_file(file_path: &Path) -> ToolLock {
+    if file_path.exists() {
+        match File::open(&file_path) {
+            Ok(mut file) => {
+                let mut lock_str = String::new();
+                file.read_to_string(&mut lock_str).unwrap();
+
+                match toml::from_str(&lock_str) {
+                    Ok(value) => value,
+                    Err(error) => {
+                        info!("Unable to parse lock file, {}", error);
+                        ToolLock::new()
+                    }
+                }
+            }
+            Err(error) => {
+                info!(
+                    "Unable to open lock file: {:#?} error: {}",
+                    &file_path, error
+                );
+                ToolLock::new()
+            }
+        }
+    } else {
+        ToolLock::new()
+    }
+}
+
+fn store_to_file(file_path: &Path, tool_lock: &ToolLock) {
+    match toml::to_string_pretty(tool_lock) {
+        Ok(toml_str) => match File::create(&file_path) {
+            Ok(mut file) => match file.write_all(toml_str.as_bytes()) {
+                Err(error) => info!(
+                    "Error while writing to lock file: {:#?}, error: {:#?}",
+                    &file_path, error
+                ),
+                _ => (),
+            },
+            Err(error) => info!(
+                "Error while creating lock file: {:#?}, error: {:#?}",
+                &file_path, error
+            ),
+        },
+        Err(error) => info!(
+            "Error during serialization of lock file: {:#?}, error: {:#?}",
+            &file_path, error
+        ),
+    };
+}
+
+fn get_version_from_file(file_path: &Path, crate_name: &str) -> Option<String> {
+    let tool_lock = load_from_file(&file_path);
+
+    match tool_lock.tools.get(crate_name) {
+        Some(version) => Some(version.clone()),
+        None => None,
+    }
+}
+
+fn set_version_in_file(file_path: &Path, crate_name: &str, version: &str) {
+    let mut tool_lock = load_from_file(&file_path);
+
+    let updated = match tool_lock.tools.get(crate_name) {
+        Some(current_version) => current_version != version,
+        None => true,
+    };
+
+    if updated {
+        tool_lock
+            .tools
+            .insert(crate_name.to_string(), version.to_string());
+
+        store_to_file(&file_path, &tool_lock);
+    }
+}
+
+/// Returns the locked version of the provided crate.
+pub(crate) fn get_version(crate_name: &str) -> Option<String> {
+    get_version_from_file(&get_lock_file(), &crate_name)
+}
+
+/// Records the installed version of the provided crate.
+pub(crate) fn set_version(crate_name: &str, version: &str) {
+    set_version_in_file(&get_lock_file(), &crate_name, &version);
+}
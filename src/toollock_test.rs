@@ -0,0 +1,76 @@
+use super::*;
+use std::fs::{create_dir_all, remove_file};
+
+fn get_temp_lock_file(name: &str) -> PathBuf {
+    let directory = env::current_dir()
+        .unwrap()
+        .join("target/_cargo_make_temp/toollock");
+    create_dir_all(&directory).unwrap();
+
+    let file_path = directory.join(name);
+    if file_path.exists() {
+        remove_file(&file_path).unwrap();
+    }
+
+    file_path
+}
+
+#[test]
+fn get_lock_file_working_directory() {
+    let directory = env::var("CARGO_MAKE_WORKING_DIRECTORY").unwrap_or("".to_string());
+    env::set_var("CARGO_MAKE_WORKING_DIRECTORY", "/some/project");
+
+    let file_path = get_lock_file();
+
+    env::set_var("CARGO_MAKE_WORKING_DIRECTORY", &directory);
+
+    assert_eq!(file_path, PathBuf::from("/some/project/cargo-make.lock"));
+}
+
+#[test]
+fn load_from_file_not_exists() {
+    let tool_lock = load_from_file(&PathBuf::from("examples2/cargo-make.lock"));
+
+    assert!(tool_lock.tools.is_empty());
+}
+
+#[test]
+fn load_from_file_exists() {
+    let tool_lock = load_from_file(&PathBuf::from("examples/tool_lock/cargo-make.lock"));
+
+    assert_eq!(tool_lock.tools.len(), 2);
+    assert_eq!(tool_lock.tools.get("cargo-audit").unwrap(), "0.7.2");
+    assert_eq!(tool_lock.tools.get("cargo-outdated").unwrap(), "0.8.0");
+}
+
+#[test]
+fn get_version_from_file_not_locked() {
+    let version = get_version_from_file(
+        &PathBuf::from("examples/tool_lock/cargo-make.lock"),
+        "cargo-bloat",
+    );
+
+    assert!(version.is_none());
+}
+
+#[test]
+fn set_version_in_file_new_and_updated() {
+    let file_path = get_temp_lock_file("set_version.lock");
+
+    set_version_in_file(&file_path, "cargo-audit", "0.7.2");
+    set_version_in_file(&file_path, "cargo-outdated", "0.8.0");
+    assert_eq!(
+        get_version_from_file(&file_path, "cargo-audit").unwrap(),
+        "0.7.2"
+    );
+
+    set_version_in_file(&file_path, "cargo-audit", "0.8.0");
+    assert_eq!(
+        get_version_from_file(&file_path, "cargo-audit").unwrap(),
+        "0.8.0"
+    );
+    assert_eq!(
+        get_version_from_file(&file_path, "cargo-outdated").unwrap(),
+        "0.8.0"
+    );
+}
@@ -9,6 +9,9 @@ mod types_test;
 
 use indexmap::IndexMap;
 use rust_info::types::RustInfo;
+use std::collections::HashSet;
+use std::fmt;
+use toml;
 
 /// Returns the platform name
 pub fn get_platform_name() -> String {
@@ -36,12 +39,20 @@ pub struct CliArgs {
     pub env: Option<Vec<String>>,
     /// Environment variables file
     pub env_file: Option<String>,
+    /// Environment profile name
+    pub profile: Option<String>,
     /// Prevent workspace support
     pub disable_workspace: bool,
     /// Prevent on error flow even if defined in config section
     pub disable_on_error: bool,
     /// Only print the execution plan
     pub print_only: bool,
+    /// Install the dependencies of all tasks in the execution plan before running the tasks
+    pub install_first: bool,
+    /// Only install the dependencies of all tasks in the execution plan without running the tasks
+    pub install_only: bool,
+    /// Install crates without accessing the network
+    pub offline: bool,
     /// List all known steps
     pub list_all_steps: bool,
     /// Disables the update check during startup
@@ -62,9 +73,13 @@ impl CliArgs {
             cwd: None,
             env: None,
             env_file: None,
+            profile: None,
             disable_workspace: false,
             disable_on_error: false,
             print_only: false,
+            install_first: false,
+            install_only: false,
+            offline: false,
             list_all_steps: false,
             disable_check_for_updates: false,
             experimental: false,
@@ -93,6 +108,47 @@ impl Cache {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Holds a cached env script output
+pub struct EnvCacheEntry {
+    /// The script output
+    pub value: String,
+    /// The time (in seconds since epoch) in which the value was cached
+    pub created: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Holds the cached env script outputs of the project
+pub struct EnvCache {
+    /// Cached outputs mapped by the script hash
+    pub values: IndexMap<String, EnvCacheEntry>,
+}
+
+impl EnvCache {
+    /// Returns new instance
+    pub fn new() -> EnvCache {
+        EnvCache {
+            values: IndexMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Holds the versions of the crates installed in the project tool root
+pub struct ToolLock {
+    /// The installed crate versions mapped by the crate name
+    pub tools: IndexMap<String, String>,
+}
+
+impl ToolLock {
+    /// Returns new instance
+    pub fn new() -> ToolLock {
+        ToolLock {
+            tools: IndexMap::new(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 /// Holds configuration info for cargo-make
 pub struct GlobalConfig {
@@ -144,6 +200,21 @@ impl GitInfo {
     }
 }
 
+#[derive(Deserialize, Debug, Clone)]
+/// Holds the cargo-make specific metadata defined in the Cargo.toml file
+pub struct CrateMetadata {
+    /// The cargo-make configuration (config, env and tasks)
+    #[serde(rename = "cargo-make")]
+    pub cargo_make: Option<ExternalConfig>,
+}
+
+impl CrateMetadata {
+    /// Creates and returns a new instance.
+    pub fn new() -> CrateMetadata {
+        CrateMetadata { cargo_make: None }
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 /// Holds crate workspace info, see http://doc.crates.io/manifest.html#the-workspace-section
 pub struct Workspace {
@@ -151,6 +222,8 @@ pub struct Workspace {
     pub members: Option<Vec<String>>,
     /// exclude paths
     pub exclude: Option<Vec<String>>,
+    /// workspace metadata
+    pub metadata: Option<CrateMetadata>,
 }
 
 impl Workspace {
@@ -159,6 +232,7 @@ impl Workspace {
         Workspace {
             members: None,
             exclude: None,
+            metadata: None,
         }
     }
 }
@@ -180,6 +254,8 @@ pub struct PackageInfo {
     pub homepage: Option<String>,
     /// repository link
     pub repository: Option<String>,
+    /// package metadata
+    pub metadata: Option<CrateMetadata>,
 }
 
 impl PackageInfo {
@@ -193,6 +269,7 @@ impl PackageInfo {
             documentation: None,
             homepage: None,
             repository: None,
+            metadata: None,
         }
     }
 }
@@ -202,6 +279,8 @@ impl PackageInfo {
 pub struct CrateDependencyInfo {
     /// Holds the dependency path
     pub path: Option<String>,
+    /// True if the dependency is optional (optional dependencies also define a feature)
+    pub optional: Option<bool>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -214,6 +293,15 @@ pub enum CrateDependency {
     Info(CrateDependencyInfo),
 }
 
+#[derive(Deserialize, Debug, Clone)]
+/// Holds crate target (bin, example or bench) info.
+pub struct CrateTarget {
+    /// The target name
+    pub name: Option<String>,
+    /// The target source file path
+    pub path: Option<String>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 /// Holds crate information loaded from the Cargo.toml file.
 pub struct CrateInfo {
@@ -223,6 +311,20 @@ pub struct CrateInfo {
     pub workspace: Option<Workspace>,
     /// crate dependencies
     pub dependencies: Option<IndexMap<String, CrateDependency>>,
+    /// crate dev dependencies
+    #[serde(rename = "dev-dependencies")]
+    pub dev_dependencies: Option<IndexMap<String, CrateDependency>>,
+    /// crate build dependencies
+    #[serde(rename = "build-dependencies")]
+    pub build_dependencies: Option<IndexMap<String, CrateDependency>>,
+    /// crate features
+    pub features: Option<IndexMap<String, Vec<String>>>,
+    /// binary targets (including the auto discovered targets)
+    pub bin: Option<Vec<CrateTarget>>,
+    /// example targets (including the auto discovered targets)
+    pub example: Option<Vec<CrateTarget>>,
+    /// bench targets (including the auto discovered targets)
+    pub bench: Option<Vec<CrateTarget>>,
 }
 
 impl CrateInfo {
@@ -232,6 +334,12 @@ impl CrateInfo {
             package: None,
             workspace: None,
             dependencies: None,
+            dev_dependencies: None,
+            build_dependencies: None,
+            features: None,
+            bin: None,
+            example: None,
+            bench: None,
         }
     }
 }
@@ -262,6 +370,8 @@ pub struct FlowInfo {
     pub disable_on_error: bool,
     /// additional command line arguments
     pub cli_arguments: Option<Vec<String>>,
+    /// The installation keys of the dependencies which were installed before running the flow
+    pub installed: HashSet<String>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -275,6 +385,34 @@ pub struct RustVersionCondition {
     pub equal: Option<String>,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+/// Rust version condition value
+pub enum RustVersion {
+    /// Semver requirement expression, for example: >=1.30, <1.40
+    Requirement(String),
+    /// Min, max and/or specific version
+    Range(RustVersionCondition),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+/// Nightly date condition structure (dates are in the YYYY-MM-DD format)
+pub struct NightlyDateCondition {
+    /// min nightly date
+    pub min: Option<String>,
+    /// max nightly date
+    pub max: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+/// Files modified condition structure
+pub struct FilesModifiedCondition {
+    /// Input file globs
+    pub input: Vec<String>,
+    /// Output file globs
+    pub output: Vec<String>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 /// Holds condition attributes
 pub struct TaskCondition {
@@ -288,8 +426,50 @@ pub struct TaskCondition {
     pub env_not_set: Option<Vec<String>>,
     /// Environment variables and their values
     pub env: Option<IndexMap<String, String>>,
+    /// Environment variables and values they must contain
+    pub env_contains: Option<IndexMap<String, String>>,
+    /// Environment variables and regular expressions their values must match
+    pub env_regex: Option<IndexMap<String, String>>,
+    /// Environment variables which must be set to a true value (true/yes/1, case insensitive)
+    pub env_true: Option<Vec<String>>,
     /// Rust version condition
-    pub rust_version: Option<RustVersionCondition>,
+    pub rust_version: Option<RustVersion>,
+    /// Rust nightly date condition
+    pub nightly_date: Option<NightlyDateCondition>,
+    /// True to run only in CI builds, false to run only outside CI builds
+    pub ci: Option<bool>,
+    /// CI vendor names (for example travis, gitlab, appveyor)
+    pub ci_vendors: Option<Vec<String>>,
+    /// Features which must be defined by the crate
+    pub crate_has_feature: Option<Vec<String>>,
+    /// Dependencies (including dev and build dependencies) which must be defined by the crate
+    pub crate_has_dependency: Option<Vec<String>>,
+    /// True if the crate must have binary targets, false if it must not
+    pub crate_has_bin: Option<bool>,
+    /// True if the crate must have examples, false if it must not
+    pub crate_has_examples: Option<bool>,
+    /// True if the crate must have benches, false if it must not
+    pub crate_has_benches: Option<bool>,
+    /// Environment profile names
+    pub profiles: Option<Vec<String>>,
+    /// Valid if any of the nested conditions is valid
+    pub any: Option<Vec<TaskCondition>>,
+    /// Valid if all of the nested conditions are valid
+    pub all: Option<Vec<TaskCondition>>,
+    /// Valid if the nested condition is not valid
+    pub not: Option<Box<TaskCondition>>,
+    /// File paths/globs which must exist
+    pub files_exist: Option<Vec<String>>,
+    /// File paths/globs which must not exist
+    pub files_not_exist: Option<Vec<String>>,
+    /// Valid if any of the input files is newer than all the output files
+    pub files_modified: Option<FilesModifiedCondition>,
+    /// Git branch names/globs
+    pub git_branches: Option<Vec<String>>,
+    /// If true, the git working tree must be clean, if false it must contain uncommitted changes
+    pub git_clean: Option<bool>,
+    /// File globs of which at least one must be changed compared to the git base ref
+    pub git_changed_files: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -297,6 +477,58 @@ pub struct TaskCondition {
 pub struct EnvValueInfo {
     /// The script to execute to get the env value
     pub script: Vec<String>,
+    /// If true, the value is treated as a secret and redacted from the output
+    pub secret: Option<bool>,
+    /// If true, the script output is cached in the project state directory
+    pub cache: Option<bool>,
+    /// The amount of seconds the cached script output is valid for (enables the cache)
+    pub cache_ttl: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+/// Holds an env value and its attributes
+pub struct EnvValueDefinition {
+    /// The env value
+    pub value: String,
+    /// If true, the value is treated as a secret and redacted from the output
+    pub secret: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+/// Holds a list env value which is joined into a single value
+pub struct EnvValueList {
+    /// The list of values
+    pub value: Vec<String>,
+    /// The separator used to join the values (defaults to ',')
+    pub separator: Option<String>,
+    /// If true, the value is treated as a secret and redacted from the output
+    pub secret: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+/// Holds an env value which is read from a file
+pub struct EnvValueFile {
+    /// The file path (the file content is trimmed)
+    pub file: String,
+    /// If true, the value is treated as a secret and redacted from the output
+    pub secret: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+/// Holds an env value which removes the env var
+pub struct EnvValueUnset {
+    /// If true, the env var is removed
+    pub unset: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+/// Holds paths which are added to the current env value using the platform path separator
+pub struct EnvValuePathList {
+    /// Paths added before the current value
+    pub path_prepend: Option<Vec<String>>,
+    /// Paths added after the current value
+    pub path_append: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -305,8 +537,62 @@ pub struct EnvValueInfo {
 pub enum EnvValue {
     /// The value as string
     Value(String),
+    /// The value as boolean
+    Boolean(bool),
+    /// The value as integer number
+    Number(i64),
     /// Script which will return the value
     Info(EnvValueInfo),
+    /// The value with additional attributes
+    Definition(EnvValueDefinition),
+    /// List of values joined with a separator
+    List(EnvValueList),
+    /// The value is read from a file
+    File(EnvValueFile),
+    /// Removes the env var
+    Unset(EnvValueUnset),
+    /// Paths added to the current env value
+    PathList(EnvValuePathList),
+    /// Profile env which overrides the base env when the profile is active
+    Profile(IndexMap<String, EnvValue>),
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+/// Holds crate installation info with an installed version check
+pub struct InstallCrateInfo {
+    /// The crate name
+    pub crate_name: String,
+    /// The binary file name used to check the installed version (defaults to the crate name)
+    pub binary: Option<String>,
+    /// The required semver version (for example ^0.7)
+    pub version: Option<String>,
+    /// The binary argument which prints its version (defaults to --version)
+    pub test_arg: Option<String>,
+    /// The local crate source directory to install from instead of the registry
+    pub path: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+/// Holds rustup component installation info
+pub struct InstallRustupComponentInfo {
+    /// The rustup component name
+    pub rustup_component_name: String,
+    /// The binary file name used to check if the component is installed
+    pub binary: Option<String>,
+    /// The binary argument used to check if the component is installed (defaults to --version)
+    pub test_arg: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+/// Install crate value
+pub enum InstallCrate {
+    /// The crate name (the crate is installed if its cargo command is not found)
+    Value(String),
+    /// Crate installation info with an installed version check
+    Info(InstallCrateInfo),
+    /// Rustup component installation info
+    RustupComponent(InstallRustupComponentInfo),
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -343,11 +629,13 @@ pub struct Task {
     /// acts like alias if runtime OS is Mac (takes precedence over alias)
     pub mac_alias: Option<String>,
     /// if defined, the provided crate will be installed (if needed) before running the task
-    pub install_crate: Option<String>,
+    pub install_crate: Option<InstallCrate>,
     /// additional cargo install arguments
     pub install_crate_args: Option<Vec<String>>,
     /// if defined, the provided script will be executed before running the task
     pub install_script: Option<Vec<String>>,
+    /// The rust toolchain used to invoke the command (installed via rustup if missing)
+    pub toolchain: Option<String>,
     /// The command to execute
     pub command: Option<String>,
     /// The command args
@@ -356,8 +644,14 @@ pub struct Task {
     pub script: Option<Vec<String>>,
     /// The script runner (defaults to cmd in windows and sh for other platforms)
     pub script_runner: Option<String>,
+    /// The script runner arguments provided before the script file (generic script runners only)
+    pub script_runner_args: Option<Vec<String>>,
     /// The script file extension
     pub script_extension: Option<String>,
+    /// The runner used to compile and run @rust scripts (cargo-script, rust-script or cargo-play)
+    pub rust_script_runner: Option<String>,
+    /// The dependencies of @rust scripts, injected as the script runner embedded manifest
+    pub script_dependencies: Option<IndexMap<String, toml::Value>>,
     /// The task name to execute
     pub run_task: Option<String>,
     /// A list of tasks to execute before this task
@@ -392,11 +686,15 @@ impl Task {
             install_crate: None,
             install_crate_args: None,
             install_script: None,
+            toolchain: None,
             command: None,
             args: None,
             script: None,
             script_runner: None,
+            script_runner_args: None,
             script_extension: None,
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: None,
             dependencies: None,
             linux: None,
@@ -522,6 +820,12 @@ impl Task {
             self.install_script = None;
         }
 
+        if task.toolchain.is_some() {
+            self.toolchain = task.toolchain.clone();
+        } else if override_values {
+            self.toolchain = None;
+        }
+
         if task.command.is_some() {
             self.command = task.command.clone();
         } else if override_values {
@@ -546,12 +850,30 @@ impl Task {
             self.script_runner = None;
         }
 
+        if task.script_runner_args.is_some() {
+            self.script_runner_args = task.script_runner_args.clone();
+        } else if override_values {
+            self.script_runner_args = None;
+        }
+
         if task.script_extension.is_some() {
             self.script_extension = task.script_extension.clone();
         } else if override_values {
             self.script_extension = None;
         }
 
+        if task.rust_script_runner.is_some() {
+            self.rust_script_runner = task.rust_script_runner.clone();
+        } else if override_values {
+            self.rust_script_runner = None;
+        }
+
+        if task.script_dependencies.is_some() {
+            self.script_dependencies = task.script_dependencies.clone();
+        } else if override_values {
+            self.script_dependencies = None;
+        }
+
         if task.run_task.is_some() {
             self.run_task = task.run_task.clone();
         } else if override_values {
@@ -634,11 +956,15 @@ impl Task {
                     install_crate: override_task.install_crate.clone(),
                     install_crate_args: override_task.install_crate_args.clone(),
                     install_script: override_task.install_script.clone(),
+                    toolchain: override_task.toolchain.clone(),
                     command: override_task.command.clone(),
                     args: override_task.args.clone(),
                     script: override_task.script.clone(),
                     script_runner: override_task.script_runner.clone(),
+                    script_runner_args: override_task.script_runner_args.clone(),
                     script_extension: override_task.script_extension.clone(),
+                    rust_script_runner: override_task.rust_script_runner.clone(),
+                    script_dependencies: override_task.script_dependencies.clone(),
                     run_task: override_task.run_task.clone(),
                     dependencies: override_task.dependencies.clone(),
                     linux: None,
@@ -720,11 +1046,13 @@ pub struct PlatformOverrideTask {
     /// The working directory for the task to execute its command/script
     pub cwd: Option<String>,
     /// if defined, the provided crate will be installed (if needed) before running the task
-    pub install_crate: Option<String>,
+    pub install_crate: Option<InstallCrate>,
     /// additional cargo install arguments
     pub install_crate_args: Option<Vec<String>>,
     /// if defined, the provided script will be executed before running the task
     pub install_script: Option<Vec<String>>,
+    /// The rust toolchain used to invoke the command (installed via rustup if missing)
+    pub toolchain: Option<String>,
     /// The command to execute
     pub command: Option<String>,
     /// The command args
@@ -733,8 +1061,14 @@ pub struct PlatformOverrideTask {
     pub script: Option<Vec<String>>,
     /// The script runner (defaults to cmd in windows and sh for other platforms)
     pub script_runner: Option<String>,
+    /// The script runner arguments provided before the script file (generic script runners only)
+    pub script_runner_args: Option<Vec<String>>,
     /// The script file extension
     pub script_extension: Option<String>,
+    /// The runner used to compile and run @rust scripts (cargo-script, rust-script or cargo-play)
+    pub rust_script_runner: Option<String>,
+    /// The dependencies of @rust scripts, injected as the script runner embedded manifest
+    pub script_dependencies: Option<IndexMap<String, toml::Value>>,
     /// The task name to execute
     pub run_task: Option<String>,
     /// A list of tasks to execute before this task
@@ -794,6 +1128,10 @@ impl PlatformOverrideTask {
                 self.install_script = task.install_script.clone();
             }
 
+            if self.toolchain.is_none() && task.toolchain.is_some() {
+                self.toolchain = task.toolchain.clone();
+            }
+
             if self.command.is_none() && task.command.is_some() {
                 self.command = task.command.clone();
             }
@@ -810,10 +1148,22 @@ impl PlatformOverrideTask {
                 self.script_runner = task.script_runner.clone();
             }
 
+            if self.script_runner_args.is_none() && task.script_runner_args.is_some() {
+                self.script_runner_args = task.script_runner_args.clone();
+            }
+
             if self.script_extension.is_none() && task.script_extension.is_some() {
                 self.script_extension = task.script_extension.clone();
             }
 
+            if self.rust_script_runner.is_none() && task.rust_script_runner.is_some() {
+                self.rust_script_runner = task.rust_script_runner.clone();
+            }
+
+            if self.script_dependencies.is_none() && task.script_dependencies.is_some() {
+                self.script_dependencies = task.script_dependencies.clone();
+            }
+
             if self.run_task.is_none() && task.run_task.is_some() {
                 self.run_task = task.run_task.clone();
             }
@@ -825,6 +1175,27 @@ impl PlatformOverrideTask {
     }
 }
 
+#[derive(Deserialize, Debug, Clone)]
+/// Holds env file path and attributes
+pub struct EnvFileInfo {
+    /// Path to the env file
+    pub path: String,
+    /// The profile name this env file is loaded for (loaded for all profiles if not defined)
+    pub profile: Option<String>,
+    /// Enable optional env file (default to false)
+    pub optional: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+/// Holds an env file definition
+pub enum EnvFile {
+    /// Path to the env file
+    Path(String),
+    /// Env file path and attributes
+    Info(EnvFileInfo),
+}
+
 #[derive(Deserialize, Debug, Clone)]
 /// Holds the configuration found in the makefile toml config section.
 pub struct ConfigSection {
@@ -844,6 +1215,16 @@ pub struct ConfigSection {
     pub windows_load_script: Option<Vec<String>>,
     /// acts like load_script if runtime OS is Mac (takes precedence over load_script)
     pub mac_load_script: Option<Vec<String>>,
+    /// Env files to load (relative paths are resolved from the makefile directory)
+    pub env_files: Option<Vec<EnvFile>>,
+    /// Env var names or glob patterns which values are redacted from the output
+    pub secret_env_patterns: Option<Vec<String>>,
+    /// The directory holding the crate source trees (by crate name) used to install crates without the registry
+    pub offline_tool_source: Option<String>,
+    /// The project local root directory in which crates are installed (relative paths are resolved from the working directory)
+    pub tool_root: Option<String>,
+    /// The default runner used to compile and run @rust scripts (cargo-script, rust-script or cargo-play)
+    pub rust_script_runner: Option<String>,
 }
 
 impl ConfigSection {
@@ -858,6 +1239,11 @@ impl ConfigSection {
             linux_load_script: None,
             windows_load_script: None,
             mac_load_script: None,
+            env_files: None,
+            secret_env_patterns: None,
+            offline_tool_source: None,
+            tool_root: None,
+            rust_script_runner: None,
         }
     }
 
@@ -898,6 +1284,26 @@ impl ConfigSection {
         if extended.mac_load_script.is_some() {
             self.mac_load_script = extended.mac_load_script.clone();
         }
+
+        if extended.env_files.is_some() {
+            self.env_files = extended.env_files.clone();
+        }
+
+        if extended.secret_env_patterns.is_some() {
+            self.secret_env_patterns = extended.secret_env_patterns.clone();
+        }
+
+        if extended.offline_tool_source.is_some() {
+            self.offline_tool_source = extended.offline_tool_source.clone();
+        }
+
+        if extended.tool_root.is_some() {
+            self.tool_root = extended.tool_root.clone();
+        }
+
+        if extended.rust_script_runner.is_some() {
+            self.rust_script_runner = extended.rust_script_runner.clone();
+        }
     }
 
     /// Returns the load script based on the current platform
@@ -937,11 +1343,32 @@ pub struct Config {
     pub tasks: IndexMap<String, Task>,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+/// Holds extend file path and attributes
+pub struct ExtendOptions {
+    /// Path to another toml file to extend
+    pub path: String,
+    /// Enable optional extend (default to false)
+    pub optional: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+/// Holds makefile extend value
+pub enum Extend {
+    /// Path to another toml file to extend
+    Path(String),
+    /// Extend options for more fine tune control
+    Options(ExtendOptions),
+    /// Multiple extends which are merged in order
+    List(Vec<Extend>),
+}
+
 #[derive(Deserialize, Debug, Clone)]
 /// Holds the entire externally read configuration such as task definitions and env vars where all values are optional
 pub struct ExternalConfig {
     /// Path to another toml file to extend
-    pub extend: Option<String>,
+    pub extend: Option<Extend>,
     /// Runtime config
     pub config: Option<ConfigSection>,
     /// The env vars to setup before running the tasks
@@ -962,6 +1389,28 @@ impl ExternalConfig {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// Describes why a task condition was not met
+pub enum SkipReason {
+    /// A condition criterion (such as platforms or env) which was not met and the failure details
+    Criterion(String, String),
+    /// The condition script exited with the provided (non zero) exit code
+    ConditionScript(i32),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SkipReason::Criterion(ref name, ref details) => {
+                write!(formatter, "{} condition not met, {}", name, details)
+            }
+            SkipReason::ConditionScript(exit_code) => {
+                write!(formatter, "condition_script exit code {}", exit_code)
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 /// Execution plan step to execute
 pub struct Step {
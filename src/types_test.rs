@@ -14,6 +14,9 @@ fn cli_args_new() {
     assert!(!cli_args.disable_on_error);
     assert!(!cli_args.disable_check_for_updates);
     assert!(!cli_args.print_only);
+    assert!(!cli_args.install_first);
+    assert!(!cli_args.install_only);
+    assert!(!cli_args.offline);
     assert!(!cli_args.list_all_steps);
     assert!(!cli_args.experimental);
     assert!(cli_args.arguments.is_none());
@@ -38,6 +41,13 @@ fn cache_new() {
     assert!(cache.last_update_check.is_none());
 }
 
+#[test]
+fn tool_lock_new() {
+    let tool_lock = ToolLock::new();
+
+    assert!(tool_lock.tools.is_empty());
+}
+
 #[test]
 fn task_new() {
     let task = Task::new();
@@ -61,10 +71,14 @@ fn task_new() {
     assert!(task.windows_alias.is_none());
     assert!(task.mac_alias.is_none());
     assert!(task.install_script.is_none());
+    assert!(task.toolchain.is_none());
     assert!(task.args.is_none());
     assert!(task.script.is_none());
     assert!(task.script_runner.is_none());
+    assert!(task.script_runner_args.is_none());
     assert!(task.script_extension.is_none());
+    assert!(task.rust_script_runner.is_none());
+    assert!(task.script_dependencies.is_none());
     assert!(task.run_task.is_none());
     assert!(task.dependencies.is_none());
     assert!(task.linux.is_none());
@@ -105,7 +119,7 @@ fn task_is_force_true() {
 #[test]
 fn task_extend_both_have_misc_data() {
     let mut base = Task::new();
-    base.install_crate = Some("my crate1".to_string());
+    base.install_crate = Some(InstallCrate::Value("my crate1".to_string()));
     base.command = Some("test1".to_string());
     base.disabled = Some(false);
     base.private = Some(false);
@@ -113,7 +127,7 @@ fn task_extend_both_have_misc_data() {
 
     let extended = Task {
         clear: Some(false),
-        install_crate: Some("my crate2".to_string()),
+        install_crate: Some(InstallCrate::Value("my crate2".to_string())),
         command: None,
         description: None,
         category: None,
@@ -131,10 +145,14 @@ fn task_extend_both_have_misc_data() {
         mac_alias: None,
         install_crate_args: None,
         install_script: None,
+        toolchain: None,
         args: None,
         script: None,
         script_runner: None,
+        script_runner_args: None,
         script_extension: None,
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: None,
         dependencies: None,
         linux: None,
@@ -163,6 +181,7 @@ fn task_extend_both_have_misc_data() {
     assert!(base.mac_alias.is_none());
     assert!(base.install_crate_args.is_none());
     assert!(base.install_script.is_none());
+    assert!(base.toolchain.is_none());
     assert!(base.script_runner.is_none());
     assert!(base.script_extension.is_none());
     assert!(base.run_task.is_none());
@@ -173,7 +192,10 @@ fn task_extend_both_have_misc_data() {
     assert!(base.windows.is_none());
     assert!(base.mac.is_none());
 
-    assert_eq!(base.install_crate.unwrap(), "my crate2");
+    assert_eq!(
+        base.install_crate.unwrap(),
+        InstallCrate::Value("my crate2".to_string())
+    );
     assert_eq!(base.command.unwrap(), "test1");
     assert!(base.disabled.unwrap());
     assert!(base.private.unwrap());
@@ -185,9 +207,12 @@ fn task_extend_both_have_misc_data() {
 
 #[test]
 fn task_extend_extended_have_all_fields() {
+    let mut script_dependencies = IndexMap::new();
+    script_dependencies.insert("serde".to_string(), toml::Value::String("1".to_string()));
+
     let mut base = Task {
         clear: Some(true),
-        install_crate: Some("my crate1".to_string()),
+        install_crate: Some(InstallCrate::Value("my crate1".to_string())),
         command: Some("test1".to_string()),
         description: None,
         category: None,
@@ -205,10 +230,14 @@ fn task_extend_extended_have_all_fields() {
         mac_alias: None,
         install_crate_args: None,
         install_script: None,
+        toolchain: None,
         args: None,
         script: Some(vec!["1".to_string(), "2".to_string()]),
         script_runner: Some("sh1".to_string()),
+        script_runner_args: None,
         script_extension: Some("ext1".to_string()),
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: Some("task1".to_string()),
         dependencies: None,
         linux: None,
@@ -220,7 +249,7 @@ fn task_extend_extended_have_all_fields() {
     env.insert("test".to_string(), EnvValue::Value("value".to_string()));
     let extended = Task {
         clear: Some(false),
-        install_crate: Some("my crate2".to_string()),
+        install_crate: Some(InstallCrate::Value("my crate2".to_string())),
         install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
         command: Some("test2".to_string()),
         description: Some("description".to_string()),
@@ -235,6 +264,27 @@ fn task_extend_extended_have_all_fields() {
             env_not_set: None,
             env: None,
             rust_version: None,
+            profiles: None,
+            any: None,
+            all: None,
+            not: None,
+            files_exist: None,
+            files_not_exist: None,
+            files_modified: None,
+            git_branches: None,
+            git_clean: None,
+            git_changed_files: None,
+            env_contains: None,
+            env_regex: None,
+            env_true: None,
+            nightly_date: None,
+            ci: None,
+            ci_vendors: None,
+            crate_has_feature: None,
+            crate_has_dependency: None,
+            crate_has_bin: None,
+            crate_has_examples: None,
+            crate_has_benches: None,
         }),
         condition_script: Some(vec!["exit 0".to_string()]),
         force: Some(false),
@@ -245,15 +295,19 @@ fn task_extend_extended_have_all_fields() {
         windows_alias: Some("windows".to_string()),
         mac_alias: Some("mac".to_string()),
         install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+        toolchain: Some("nightly".to_string()),
         args: Some(vec!["a1".to_string(), "a2".to_string()]),
         script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
         script_runner: Some("sh2".to_string()),
+        script_runner_args: Some(vec!["-u".to_string()]),
         script_extension: Some("ext2".to_string()),
+        rust_script_runner: Some("rust-script".to_string()),
+        script_dependencies: Some(script_dependencies),
         run_task: Some("task2".to_string()),
         dependencies: Some(vec!["A".to_string()]),
         linux: Some(PlatformOverrideTask {
             clear: Some(true),
-            install_crate: Some("my crate2".to_string()),
+            install_crate: Some(InstallCrate::Value("my crate2".to_string())),
             install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
             command: Some("test2".to_string()),
             disabled: Some(true),
@@ -265,22 +319,47 @@ fn task_extend_extended_have_all_fields() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["exit 0".to_string()]),
             force: Some(true),
             env: Some(env.clone()),
             cwd: Some("cwd".to_string()),
             install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+            toolchain: None,
             args: Some(vec!["a1".to_string(), "a2".to_string()]),
             script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script_runner: Some("sh3".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext3".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task3".to_string()),
             dependencies: Some(vec!["A".to_string()]),
         }),
         windows: Some(PlatformOverrideTask {
             clear: Some(false),
-            install_crate: Some("my crate2".to_string()),
+            install_crate: Some(InstallCrate::Value("my crate2".to_string())),
             install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
             command: Some("test2".to_string()),
             disabled: Some(true),
@@ -292,22 +371,47 @@ fn task_extend_extended_have_all_fields() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["exit 0".to_string()]),
             force: Some(true),
             env: Some(env.clone()),
             cwd: Some("cwd".to_string()),
             install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+            toolchain: None,
             args: Some(vec!["a1".to_string(), "a2".to_string()]),
             script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script_runner: Some("sh3".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext3".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task3".to_string()),
             dependencies: Some(vec!["A".to_string()]),
         }),
         mac: Some(PlatformOverrideTask {
             clear: None,
-            install_crate: Some("my crate2".to_string()),
+            install_crate: Some(InstallCrate::Value("my crate2".to_string())),
             install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
             command: Some("test2".to_string()),
             disabled: Some(true),
@@ -319,16 +423,41 @@ fn task_extend_extended_have_all_fields() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["exit 0".to_string()]),
             force: Some(true),
             env: Some(env.clone()),
             cwd: Some("cwd".to_string()),
             install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+            toolchain: None,
             args: Some(vec!["a1".to_string(), "a2".to_string()]),
             script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script_runner: Some("sh3".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext3".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task3".to_string()),
             dependencies: Some(vec!["A".to_string()]),
         }),
@@ -355,17 +484,24 @@ fn task_extend_extended_have_all_fields() {
     assert!(base.windows_alias.is_some());
     assert!(base.mac_alias.is_some());
     assert!(base.install_script.is_some());
+    assert!(base.toolchain.is_some());
     assert!(base.args.is_some());
     assert!(base.script.is_some());
     assert!(base.script_runner.is_some());
+    assert!(base.script_runner_args.is_some());
     assert!(base.script_extension.is_some());
+    assert!(base.rust_script_runner.is_some());
+    assert!(base.script_dependencies.is_some());
     assert!(base.run_task.is_some());
     assert!(base.dependencies.is_some());
     assert!(base.linux.is_some());
     assert!(base.windows.is_some());
     assert!(base.mac.is_some());
 
-    assert_eq!(base.install_crate.unwrap(), "my crate2");
+    assert_eq!(
+        base.install_crate.unwrap(),
+        InstallCrate::Value("my crate2".to_string())
+    );
     assert_eq!(base.install_crate_args.unwrap().len(), 2);
     assert_eq!(base.command.unwrap(), "test2");
     assert_eq!(base.description.unwrap(), "description");
@@ -382,10 +518,14 @@ fn task_extend_extended_have_all_fields() {
     assert_eq!(base.windows_alias.unwrap(), "windows");
     assert_eq!(base.mac_alias.unwrap(), "mac");
     assert_eq!(base.install_script.unwrap().len(), 2);
+    assert_eq!(base.toolchain.unwrap(), "nightly");
     assert_eq!(base.args.unwrap().len(), 2);
     assert_eq!(base.script.unwrap().len(), 3);
     assert_eq!(base.script_runner.unwrap(), "sh2");
+    assert_eq!(base.script_runner_args.unwrap(), vec!["-u".to_string()]);
     assert_eq!(base.script_extension.unwrap(), "ext2");
+    assert_eq!(base.rust_script_runner.unwrap(), "rust-script");
+    assert_eq!(base.script_dependencies.unwrap().len(), 1);
     assert_eq!(base.run_task.unwrap(), "task2");
     assert_eq!(base.dependencies.unwrap().len(), 1);
     assert!(base.linux.unwrap().clear.unwrap());
@@ -402,7 +542,7 @@ fn task_extend_clear_with_no_data() {
     let env = IndexMap::new();
     let mut base = Task {
         clear: Some(false),
-        install_crate: Some("my crate2".to_string()),
+        install_crate: Some(InstallCrate::Value("my crate2".to_string())),
         install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
         command: Some("test2".to_string()),
         description: Some("description".to_string()),
@@ -417,6 +557,27 @@ fn task_extend_clear_with_no_data() {
             env_not_set: None,
             env: None,
             rust_version: None,
+            profiles: None,
+            any: None,
+            all: None,
+            not: None,
+            files_exist: None,
+            files_not_exist: None,
+            files_modified: None,
+            git_branches: None,
+            git_clean: None,
+            git_changed_files: None,
+            env_contains: None,
+            env_regex: None,
+            env_true: None,
+            nightly_date: None,
+            ci: None,
+            ci_vendors: None,
+            crate_has_feature: None,
+            crate_has_dependency: None,
+            crate_has_bin: None,
+            crate_has_examples: None,
+            crate_has_benches: None,
         }),
         condition_script: Some(vec!["exit 0".to_string()]),
         force: Some(false),
@@ -427,15 +588,19 @@ fn task_extend_clear_with_no_data() {
         windows_alias: Some("windows".to_string()),
         mac_alias: Some("mac".to_string()),
         install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+        toolchain: None,
         args: Some(vec!["a1".to_string(), "a2".to_string()]),
         script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
         script_runner: Some("sh2".to_string()),
+        script_runner_args: None,
         script_extension: Some("ext2".to_string()),
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: Some("task2".to_string()),
         dependencies: Some(vec!["A".to_string()]),
         linux: Some(PlatformOverrideTask {
             clear: Some(true),
-            install_crate: Some("my crate2".to_string()),
+            install_crate: Some(InstallCrate::Value("my crate2".to_string())),
             install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
             command: Some("test2".to_string()),
             disabled: Some(true),
@@ -447,22 +612,47 @@ fn task_extend_clear_with_no_data() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["exit 0".to_string()]),
             force: Some(true),
             env: Some(env.clone()),
             cwd: Some("cwd".to_string()),
             install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+            toolchain: None,
             args: Some(vec!["a1".to_string(), "a2".to_string()]),
             script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script_runner: Some("sh3".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext3".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task3".to_string()),
             dependencies: Some(vec!["A".to_string()]),
         }),
         windows: Some(PlatformOverrideTask {
             clear: Some(false),
-            install_crate: Some("my crate2".to_string()),
+            install_crate: Some(InstallCrate::Value("my crate2".to_string())),
             install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
             command: Some("test2".to_string()),
             disabled: Some(true),
@@ -474,22 +664,47 @@ fn task_extend_clear_with_no_data() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["exit 0".to_string()]),
             force: Some(true),
             env: Some(env.clone()),
             cwd: Some("cwd".to_string()),
             install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+            toolchain: None,
             args: Some(vec!["a1".to_string(), "a2".to_string()]),
             script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script_runner: Some("sh3".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext3".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task3".to_string()),
             dependencies: Some(vec!["A".to_string()]),
         }),
         mac: Some(PlatformOverrideTask {
             clear: None,
-            install_crate: Some("my crate2".to_string()),
+            install_crate: Some(InstallCrate::Value("my crate2".to_string())),
             install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
             command: Some("test2".to_string()),
             disabled: Some(true),
@@ -501,16 +716,41 @@ fn task_extend_clear_with_no_data() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["exit 0".to_string()]),
             force: Some(true),
             env: Some(env.clone()),
             cwd: Some("cwd".to_string()),
             install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+            toolchain: None,
             args: Some(vec!["a1".to_string(), "a2".to_string()]),
             script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script_runner: Some("sh3".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext3".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task3".to_string()),
             dependencies: Some(vec!["A".to_string()]),
         }),
@@ -540,6 +780,7 @@ fn task_extend_clear_with_no_data() {
     assert!(base.mac_alias.is_none());
     assert!(base.install_crate_args.is_none());
     assert!(base.install_script.is_none());
+    assert!(base.toolchain.is_none());
     assert!(base.script_runner.is_none());
     assert!(base.script_extension.is_none());
     assert!(base.run_task.is_none());
@@ -558,7 +799,7 @@ fn task_extend_clear_with_all_data() {
     let env = IndexMap::new();
     let extended = Task {
         clear: Some(true),
-        install_crate: Some("my crate2".to_string()),
+        install_crate: Some(InstallCrate::Value("my crate2".to_string())),
         install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
         command: Some("test2".to_string()),
         description: Some("description".to_string()),
@@ -573,6 +814,27 @@ fn task_extend_clear_with_all_data() {
             env_not_set: None,
             env: None,
             rust_version: None,
+            profiles: None,
+            any: None,
+            all: None,
+            not: None,
+            files_exist: None,
+            files_not_exist: None,
+            files_modified: None,
+            git_branches: None,
+            git_clean: None,
+            git_changed_files: None,
+            env_contains: None,
+            env_regex: None,
+            env_true: None,
+            nightly_date: None,
+            ci: None,
+            ci_vendors: None,
+            crate_has_feature: None,
+            crate_has_dependency: None,
+            crate_has_bin: None,
+            crate_has_examples: None,
+            crate_has_benches: None,
         }),
         condition_script: Some(vec!["exit 0".to_string()]),
         force: Some(false),
@@ -583,15 +845,19 @@ fn task_extend_clear_with_all_data() {
         windows_alias: Some("windows".to_string()),
         mac_alias: Some("mac".to_string()),
         install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+        toolchain: None,
         args: Some(vec!["a1".to_string(), "a2".to_string()]),
         script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
         script_runner: Some("sh2".to_string()),
+        script_runner_args: None,
         script_extension: Some("ext2".to_string()),
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: Some("task2".to_string()),
         dependencies: Some(vec!["A".to_string()]),
         linux: Some(PlatformOverrideTask {
             clear: Some(true),
-            install_crate: Some("my crate2".to_string()),
+            install_crate: Some(InstallCrate::Value("my crate2".to_string())),
             install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
             command: Some("test2".to_string()),
             disabled: Some(true),
@@ -603,22 +869,47 @@ fn task_extend_clear_with_all_data() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["exit 0".to_string()]),
             force: Some(true),
             env: Some(env.clone()),
             cwd: Some("cwd".to_string()),
             install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+            toolchain: None,
             args: Some(vec!["a1".to_string(), "a2".to_string()]),
             script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script_runner: Some("sh3".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext3".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task3".to_string()),
             dependencies: Some(vec!["A".to_string()]),
         }),
         windows: Some(PlatformOverrideTask {
             clear: Some(false),
-            install_crate: Some("my crate2".to_string()),
+            install_crate: Some(InstallCrate::Value("my crate2".to_string())),
             install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
             command: Some("test2".to_string()),
             disabled: Some(true),
@@ -630,22 +921,47 @@ fn task_extend_clear_with_all_data() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["exit 0".to_string()]),
             force: Some(true),
             env: Some(env.clone()),
             cwd: Some("cwd".to_string()),
             install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+            toolchain: None,
             args: Some(vec!["a1".to_string(), "a2".to_string()]),
             script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script_runner: Some("sh3".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext3".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task3".to_string()),
             dependencies: Some(vec!["A".to_string()]),
         }),
         mac: Some(PlatformOverrideTask {
             clear: None,
-            install_crate: Some("my crate2".to_string()),
+            install_crate: Some(InstallCrate::Value("my crate2".to_string())),
             install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
             command: Some("test2".to_string()),
             disabled: Some(true),
@@ -657,16 +973,41 @@ fn task_extend_clear_with_all_data() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["exit 0".to_string()]),
             force: Some(true),
             env: Some(env.clone()),
             cwd: Some("cwd".to_string()),
             install_script: Some(vec!["i1".to_string(), "i2".to_string()]),
+            toolchain: None,
             args: Some(vec!["a1".to_string(), "a2".to_string()]),
             script: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script_runner: Some("sh3".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext3".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task3".to_string()),
             dependencies: Some(vec!["A".to_string()]),
         }),
@@ -747,7 +1088,7 @@ fn task_get_normalized_task_undefined() {
         linux_alias: Some("linux".to_string()),
         windows_alias: Some("windows".to_string()),
         mac_alias: Some("mac".to_string()),
-        install_crate: Some("install_crate".to_string()),
+        install_crate: Some(InstallCrate::Value("install_crate".to_string())),
         install_crate_args: None,
         command: Some("command".to_string()),
         disabled: Some(false),
@@ -758,10 +1099,14 @@ fn task_get_normalized_task_undefined() {
         env: None,
         cwd: None,
         install_script: Some(vec!["A".to_string(), "B".to_string(), "C".to_string()]),
+        toolchain: None,
         args: Some(vec!["1".to_string(), "2".to_string()]),
         script: Some(vec!["a".to_string(), "b".to_string()]),
         script_runner: Some("sh1".to_string()),
+        script_runner_args: None,
         script_extension: Some("ext1".to_string()),
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: Some("task1".to_string()),
         dependencies: Some(vec!["1".to_string()]),
         description: Some("description".to_string()),
@@ -803,7 +1148,10 @@ fn task_get_normalized_task_undefined() {
     assert!(normalized_task.windows.is_none());
     assert!(normalized_task.mac.is_none());
 
-    assert_eq!(normalized_task.install_crate.unwrap(), "install_crate");
+    assert_eq!(
+        normalized_task.install_crate.unwrap(),
+        InstallCrate::Value("install_crate".to_string())
+    );
     assert_eq!(normalized_task.command.unwrap(), "command");
     assert_eq!(normalized_task.description.unwrap(), "description");
     assert_eq!(normalized_task.category.unwrap(), "category");
@@ -836,7 +1184,7 @@ fn task_get_normalized_task_with_override_no_clear() {
         linux_alias: Some("bad".to_string()),
         windows_alias: Some("bad".to_string()),
         mac_alias: Some("bad".to_string()),
-        install_crate: Some("install_crate".to_string()),
+        install_crate: Some(InstallCrate::Value("install_crate".to_string())),
         install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
         command: Some("command".to_string()),
         description: Some("description".to_string()),
@@ -851,21 +1199,46 @@ fn task_get_normalized_task_with_override_no_clear() {
             env_not_set: None,
             env: None,
             rust_version: None,
+            profiles: None,
+            any: None,
+            all: None,
+            not: None,
+            files_exist: None,
+            files_not_exist: None,
+            files_modified: None,
+            git_branches: None,
+            git_clean: None,
+            git_changed_files: None,
+            env_contains: None,
+            env_regex: None,
+            env_true: None,
+            nightly_date: None,
+            ci: None,
+            ci_vendors: None,
+            crate_has_feature: None,
+            crate_has_dependency: None,
+            crate_has_bin: None,
+            crate_has_examples: None,
+            crate_has_benches: None,
         }),
         condition_script: Some(vec!["exit 0".to_string()]),
         force: Some(false),
         env: Some(IndexMap::new()),
         cwd: Some("cwd".to_string()),
         install_script: Some(vec!["A".to_string(), "B".to_string(), "C".to_string()]),
+        toolchain: None,
         args: Some(vec!["1".to_string(), "2".to_string()]),
         script: Some(vec!["a".to_string(), "b".to_string()]),
         script_runner: Some("sh1".to_string()),
+        script_runner_args: None,
         script_extension: Some("ext1".to_string()),
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: Some("task1".to_string()),
         dependencies: Some(vec!["1".to_string()]),
         linux: Some(PlatformOverrideTask {
             clear: None,
-            install_crate: Some("linux_crate".to_string()),
+            install_crate: Some(InstallCrate::Value("linux_crate".to_string())),
             install_crate_args: Some(vec!["c1".to_string(), "c2".to_string(), "c3".to_string()]),
             command: Some("linux_command".to_string()),
             disabled: Some(true),
@@ -877,6 +1250,27 @@ fn task_get_normalized_task_with_override_no_clear() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["exit 0".to_string()]),
             force: Some(true),
@@ -888,10 +1282,14 @@ fn task_get_normalized_task_with_override_no_clear() {
                 "C".to_string(),
                 "D".to_string(),
             ]),
+            toolchain: Some("nightly".to_string()),
             args: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script: Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
             script_runner: Some("sh2".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext2".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task2".to_string()),
             dependencies: Some(vec!["1".to_string(), "2".to_string()]),
         }),
@@ -920,6 +1318,7 @@ fn task_get_normalized_task_with_override_no_clear() {
     assert!(normalized_task.windows_alias.is_none());
     assert!(normalized_task.mac_alias.is_none());
     assert!(normalized_task.install_script.is_some());
+    assert!(normalized_task.toolchain.is_some());
     assert!(normalized_task.args.is_some());
     assert!(normalized_task.script.is_some());
     assert!(normalized_task.script_runner.is_some());
@@ -930,7 +1329,10 @@ fn task_get_normalized_task_with_override_no_clear() {
     assert!(normalized_task.windows.is_none());
     assert!(normalized_task.mac.is_none());
 
-    assert_eq!(normalized_task.install_crate.unwrap(), "linux_crate");
+    assert_eq!(
+        normalized_task.install_crate.unwrap(),
+        InstallCrate::Value("linux_crate".to_string())
+    );
     assert_eq!(normalized_task.install_crate_args.unwrap().len(), 3);
     assert_eq!(normalized_task.command.unwrap(), "linux_command");
     assert_eq!(normalized_task.description.unwrap(), "description");
@@ -943,6 +1345,7 @@ fn task_get_normalized_task_with_override_no_clear() {
     assert_eq!(normalized_task.env.unwrap().len(), 1);
     assert_eq!(normalized_task.cwd.unwrap(), "cwd2".to_string());
     assert_eq!(normalized_task.install_script.unwrap().len(), 4);
+    assert_eq!(normalized_task.toolchain.unwrap(), "nightly");
     assert_eq!(normalized_task.args.unwrap().len(), 3);
     assert_eq!(normalized_task.script.unwrap().len(), 3);
     assert_eq!(normalized_task.script_runner.unwrap(), "sh2");
@@ -967,7 +1370,7 @@ fn task_get_normalized_task_with_override_clear_false() {
         linux_alias: Some("bad".to_string()),
         windows_alias: Some("bad".to_string()),
         mac_alias: Some("bad".to_string()),
-        install_crate: Some("install_crate".to_string()),
+        install_crate: Some(InstallCrate::Value("install_crate".to_string())),
         install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
         command: Some("command".to_string()),
         description: Some("description".to_string()),
@@ -982,21 +1385,46 @@ fn task_get_normalized_task_with_override_clear_false() {
             env_not_set: None,
             env: None,
             rust_version: None,
+            profiles: None,
+            any: None,
+            all: None,
+            not: None,
+            files_exist: None,
+            files_not_exist: None,
+            files_modified: None,
+            git_branches: None,
+            git_clean: None,
+            git_changed_files: None,
+            env_contains: None,
+            env_regex: None,
+            env_true: None,
+            nightly_date: None,
+            ci: None,
+            ci_vendors: None,
+            crate_has_feature: None,
+            crate_has_dependency: None,
+            crate_has_bin: None,
+            crate_has_examples: None,
+            crate_has_benches: None,
         }),
         condition_script: Some(vec!["exit 0".to_string()]),
         force: Some(false),
         env: Some(IndexMap::new()),
         cwd: Some("cwd".to_string()),
         install_script: Some(vec!["A".to_string(), "B".to_string(), "C".to_string()]),
+        toolchain: None,
         args: Some(vec!["1".to_string(), "2".to_string()]),
         script: Some(vec!["a".to_string(), "b".to_string()]),
         script_runner: Some("sh1".to_string()),
+        script_runner_args: None,
         script_extension: Some("ext1".to_string()),
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: Some("task1".to_string()),
         dependencies: Some(vec!["1".to_string()]),
         linux: Some(PlatformOverrideTask {
             clear: Some(false),
-            install_crate: Some("linux_crate".to_string()),
+            install_crate: Some(InstallCrate::Value("linux_crate".to_string())),
             command: Some("linux_command".to_string()),
             disabled: Some(true),
             private: Some(false),
@@ -1011,6 +1439,27 @@ fn task_get_normalized_task_with_override_clear_false() {
                 env_not_set: None,
                 env: None,
                 rust_version: None,
+                profiles: None,
+                any: None,
+                all: None,
+                not: None,
+                files_exist: None,
+                files_not_exist: None,
+                files_modified: None,
+                git_branches: None,
+                git_clean: None,
+                git_changed_files: None,
+                env_contains: None,
+                env_regex: None,
+                env_true: None,
+                nightly_date: None,
+                ci: None,
+                ci_vendors: None,
+                crate_has_feature: None,
+                crate_has_dependency: None,
+                crate_has_bin: None,
+                crate_has_examples: None,
+                crate_has_benches: None,
             }),
             condition_script: Some(vec!["echo test".to_string(), "exit 1".to_string()]),
             force: Some(true),
@@ -1023,10 +1472,14 @@ fn task_get_normalized_task_with_override_clear_false() {
                 "C".to_string(),
                 "D".to_string(),
             ]),
+            toolchain: None,
             args: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
             script: Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
             script_runner: Some("sh2".to_string()),
+            script_runner_args: None,
             script_extension: Some("ext2".to_string()),
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: Some("task2".to_string()),
             dependencies: Some(vec!["1".to_string(), "2".to_string()]),
         }),
@@ -1065,7 +1518,10 @@ fn task_get_normalized_task_with_override_clear_false() {
     assert!(normalized_task.windows.is_none());
     assert!(normalized_task.mac.is_none());
 
-    assert_eq!(normalized_task.install_crate.unwrap(), "linux_crate");
+    assert_eq!(
+        normalized_task.install_crate.unwrap(),
+        InstallCrate::Value("linux_crate".to_string())
+    );
     assert_eq!(normalized_task.command.unwrap(), "linux_command");
     assert_eq!(normalized_task.description.unwrap(), "description");
     assert_eq!(normalized_task.category.unwrap(), "category");
@@ -1099,7 +1555,7 @@ fn task_get_normalized_task_with_override_clear_false_partial_override() {
         linux_alias: Some("bad".to_string()),
         windows_alias: Some("bad".to_string()),
         mac_alias: Some("bad".to_string()),
-        install_crate: Some("install_crate".to_string()),
+        install_crate: Some(InstallCrate::Value("install_crate".to_string())),
         install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
         command: Some("command".to_string()),
         disabled: Some(false),
@@ -1111,16 +1567,41 @@ fn task_get_normalized_task_with_override_clear_false_partial_override() {
             env_not_set: None,
             env: None,
             rust_version: None,
+            profiles: None,
+            any: None,
+            all: None,
+            not: None,
+            files_exist: None,
+            files_not_exist: None,
+            files_modified: None,
+            git_branches: None,
+            git_clean: None,
+            git_changed_files: None,
+            env_contains: None,
+            env_regex: None,
+            env_true: None,
+            nightly_date: None,
+            ci: None,
+            ci_vendors: None,
+            crate_has_feature: None,
+            crate_has_dependency: None,
+            crate_has_bin: None,
+            crate_has_examples: None,
+            crate_has_benches: None,
         }),
         condition_script: Some(vec!["exit 0".to_string()]),
         force: Some(false),
         env: Some(IndexMap::new()),
         cwd: Some("cwd".to_string()),
         install_script: Some(vec!["A".to_string(), "B".to_string(), "C".to_string()]),
+        toolchain: None,
         args: Some(vec!["1".to_string(), "2".to_string()]),
         script: Some(vec!["a".to_string(), "b".to_string()]),
         script_runner: Some("sh1".to_string()),
+        script_runner_args: None,
         script_extension: Some("ext1".to_string()),
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: Some("task1".to_string()),
         dependencies: Some(vec!["1".to_string()]),
         description: None,
@@ -1139,10 +1620,14 @@ fn task_get_normalized_task_with_override_clear_false_partial_override() {
             env: None,
             cwd: None,
             install_script: None,
+            toolchain: None,
             args: None,
             script: None,
             script_runner: None,
+            script_runner_args: None,
             script_extension: None,
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: None,
             dependencies: None,
         }),
@@ -1181,7 +1666,10 @@ fn task_get_normalized_task_with_override_clear_false_partial_override() {
     assert!(normalized_task.windows.is_none());
     assert!(normalized_task.mac.is_none());
 
-    assert_eq!(normalized_task.install_crate.unwrap(), "install_crate");
+    assert_eq!(
+        normalized_task.install_crate.unwrap(),
+        InstallCrate::Value("install_crate".to_string())
+    );
     assert_eq!(normalized_task.command.unwrap(), "command");
     assert!(!normalized_task.disabled.unwrap());
     assert!(normalized_task.private.unwrap());
@@ -1207,7 +1695,7 @@ fn task_get_normalized_task_with_override_clear_true() {
         linux_alias: Some("bad".to_string()),
         windows_alias: Some("bad".to_string()),
         mac_alias: Some("bad".to_string()),
-        install_crate: Some("install_crate".to_string()),
+        install_crate: Some(InstallCrate::Value("install_crate".to_string())),
         install_crate_args: Some(vec!["c1".to_string(), "c2".to_string()]),
         command: Some("command".to_string()),
         disabled: Some(false),
@@ -1219,16 +1707,41 @@ fn task_get_normalized_task_with_override_clear_true() {
             env_not_set: None,
             env: None,
             rust_version: None,
+            profiles: None,
+            any: None,
+            all: None,
+            not: None,
+            files_exist: None,
+            files_not_exist: None,
+            files_modified: None,
+            git_branches: None,
+            git_clean: None,
+            git_changed_files: None,
+            env_contains: None,
+            env_regex: None,
+            env_true: None,
+            nightly_date: None,
+            ci: None,
+            ci_vendors: None,
+            crate_has_feature: None,
+            crate_has_dependency: None,
+            crate_has_bin: None,
+            crate_has_examples: None,
+            crate_has_benches: None,
         }),
         condition_script: Some(vec!["exit 0".to_string()]),
         force: Some(false),
         env: Some(IndexMap::new()),
         cwd: Some("cwd".to_string()),
         install_script: Some(vec!["A".to_string(), "B".to_string(), "C".to_string()]),
+        toolchain: None,
         args: Some(vec!["1".to_string(), "2".to_string()]),
         script: Some(vec!["a".to_string(), "b".to_string()]),
         script_runner: Some("sh1".to_string()),
+        script_runner_args: None,
         script_extension: Some("ext1".to_string()),
+        rust_script_runner: None,
+        script_dependencies: None,
         run_task: Some("task1".to_string()),
         dependencies: Some(vec!["1".to_string()]),
         description: Some("description".to_string()),
@@ -1236,7 +1749,7 @@ fn task_get_normalized_task_with_override_clear_true() {
         workspace: Some(false),
         linux: Some(PlatformOverrideTask {
             clear: Some(true),
-            install_crate: Some("linux_crate".to_string()),
+            install_crate: Some(InstallCrate::Value("linux_crate".to_string())),
             install_crate_args: None,
             command: None,
             disabled: None,
@@ -1247,10 +1760,14 @@ fn task_get_normalized_task_with_override_clear_true() {
             env: None,
             cwd: None,
             install_script: None,
+            toolchain: None,
             args: None,
             script: None,
             script_runner: None,
+            script_runner_args: None,
             script_extension: None,
+            rust_script_runner: None,
+            script_dependencies: None,
             run_task: None,
             dependencies: None,
         }),
@@ -1276,6 +1793,7 @@ fn task_get_normalized_task_with_override_clear_true() {
     assert!(normalized_task.windows_alias.is_none());
     assert!(normalized_task.mac_alias.is_none());
     assert!(normalized_task.install_script.is_none());
+    assert!(normalized_task.toolchain.is_none());
     assert!(normalized_task.args.is_none());
     assert!(normalized_task.script.is_none());
     assert!(normalized_task.script_runner.is_none());
@@ -1289,7 +1807,10 @@ fn task_get_normalized_task_with_override_clear_true() {
     assert!(normalized_task.windows.is_none());
     assert!(normalized_task.mac.is_none());
 
-    assert_eq!(normalized_task.install_crate.unwrap(), "linux_crate");
+    assert_eq!(
+        normalized_task.install_crate.unwrap(),
+        InstallCrate::Value("linux_crate".to_string())
+    );
     assert_eq!(normalized_task.description.unwrap(), "description");
     assert_eq!(normalized_task.category.unwrap(), "category");
 }
@@ -1364,6 +1885,11 @@ fn config_section_new() {
     assert!(config.linux_load_script.is_none());
     assert!(config.windows_load_script.is_none());
     assert!(config.mac_load_script.is_none());
+    assert!(config.env_files.is_none());
+    assert!(config.secret_env_patterns.is_none());
+    assert!(config.offline_tool_source.is_none());
+    assert!(config.tool_root.is_none());
+    assert!(config.rust_script_runner.is_none());
 }
 
 #[test]
@@ -1379,6 +1905,11 @@ fn config_section_extend_all_values() {
     base.linux_load_script = Some(vec!["linux".to_string(), "base_info".to_string()]);
     base.windows_load_script = Some(vec!["windows".to_string(), "base_info".to_string()]);
     base.mac_load_script = Some(vec!["mac".to_string(), "base_info".to_string()]);
+    base.env_files = Some(vec![EnvFile::Path("base.env".to_string())]);
+    base.secret_env_patterns = Some(vec!["BASE_*".to_string()]);
+    base.offline_tool_source = Some("base_vendor".to_string());
+    base.tool_root = Some("base_tools".to_string());
+    base.rust_script_runner = Some("cargo-script".to_string());
 
     extended.skip_core_tasks = Some(false);
     extended.init_task = Some("extended_init".to_string());
@@ -1388,6 +1919,14 @@ fn config_section_extend_all_values() {
     extended.linux_load_script = Some(vec!["extended_info".to_string()]);
     extended.windows_load_script = Some(vec!["extended_info".to_string()]);
     extended.mac_load_script = Some(vec!["extended_info".to_string()]);
+    extended.env_files = Some(vec![
+        EnvFile::Path("extended.env".to_string()),
+        EnvFile::Path("extended2.env".to_string()),
+    ]);
+    extended.secret_env_patterns = Some(vec!["*_TOKEN".to_string(), "*_KEY".to_string()]);
+    extended.offline_tool_source = Some("extended_vendor".to_string());
+    extended.tool_root = Some("extended_tools".to_string());
+    extended.rust_script_runner = Some("rust-script".to_string());
 
     base.extend(&mut extended);
 
@@ -1399,6 +1938,14 @@ fn config_section_extend_all_values() {
     assert_eq!(base.linux_load_script.unwrap().len(), 1);
     assert_eq!(base.windows_load_script.unwrap().len(), 1);
     assert_eq!(base.mac_load_script.unwrap().len(), 1);
+    assert_eq!(base.env_files.unwrap().len(), 2);
+    assert_eq!(base.secret_env_patterns.unwrap().len(), 2);
+    assert_eq!(
+        base.offline_tool_source.unwrap(),
+        "extended_vendor".to_string()
+    );
+    assert_eq!(base.tool_root.unwrap(), "extended_tools".to_string());
+    assert_eq!(base.rust_script_runner.unwrap(), "rust-script".to_string());
 }
 
 #[test]
@@ -1515,3 +2062,23 @@ fn git_info_new() {
     assert!(git_info.user_name.is_none());
     assert!(git_info.user_email.is_none());
 }
+
+#[test]
+fn skip_reason_display_criterion() {
+    let reason = SkipReason::Criterion(
+        "platforms".to_string(),
+        "current platform: linux".to_string(),
+    );
+
+    assert_eq!(
+        reason.to_string(),
+        "platforms condition not met, current platform: linux"
+    );
+}
+
+#[test]
+fn skip_reason_display_condition_script() {
+    let reason = SkipReason::ConditionScript(2);
+
+    assert_eq!(reason.to_string(), "condition_script exit code 2");
+}